@@ -0,0 +1,100 @@
+//! Cost of reading the longest chapter: Psalm 119 from a SWORD module, and
+//! the text layout each verse row is bound from. Binding the rows into the
+//! `ListView` needs a display, so it is not measured here. Run with
+//! `cargo bench --bench chapter_load`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use x_bible::features::{
+    bible::{
+        components::page::{layout::VerseLayout, reader::ChapterReader},
+        transilation_engines::usfm_engine::usfm::UsfmParser,
+    },
+    core::{
+        module_engine::{sword_engine::SwordEngine, sword_module::ModuleSpec},
+        settings::AddedWordStyle,
+    },
+};
+
+const RUNS: u32 = 30;
+
+/// Psalm 119 in USFM: 176 verses of two poetry lines, every word tagged
+/// with a Strong's number and some supplied by the translators
+fn psalm_119() -> String {
+    let mut usfm = String::from("\\id PSA Bench\n\\c 119\n");
+    for verse in 1..=176 {
+        if verse % 8 == 1 {
+            usfm.push_str(&format!("\\s1 Stanza {}\n", verse / 8 + 1));
+        }
+        usfm.push_str(&format!("\\q1 \\v {verse}"));
+        for index in 0..24 {
+            if index == 12 {
+                usfm.push_str("\n\\q2");
+            }
+            let strong = 1000 + (verse * 24 + index) % 8000;
+            if index % 7 == 0 {
+                usfm.push_str(&format!(
+                    " \\add \\+w word{index}|strong=\"H{strong}\"\\+w*\\add*"
+                ));
+            } else {
+                usfm.push_str(&format!(" \\w word{index}|strong=\"H{strong}\"\\w*"));
+            }
+        }
+        usfm.push('\n');
+    }
+    usfm
+}
+
+fn measure(name: &str, mut run: impl FnMut()) {
+    run();
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let started = Instant::now();
+            run();
+            started.elapsed()
+        })
+        .collect();
+    times.sort();
+    println!(
+        "{name:<24} median {:>10.2?}   min {:>10.2?}   max {:>10.2?}",
+        times[times.len() / 2],
+        times[0],
+        times[times.len() - 1]
+    );
+}
+
+fn main() {
+    let dir = std::env::temp_dir().join(format!("xbible-bench-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let engine = SwordEngine::open(dir.clone()).expect("open a scratch SWORD library");
+    engine
+        .create_module(
+            &ModuleSpec::new("Bench", "Chapter load benchmark"),
+            &UsfmParser::parse(&psalm_119()),
+        )
+        .expect("write the Psalm 119 module");
+    let reader = ChapterReader::new(engine);
+
+    let verses = reader
+        .render_content_to_verses("Bench", "Ps 119")
+        .expect("read Psalm 119");
+    assert_eq!(verses.len(), 176, "the module should hold all of Psalm 119");
+
+    measure("read Psalm 119", || {
+        black_box(reader.render_content_to_verses("Bench", "Ps 119").unwrap());
+    });
+    measure("lay out Psalm 119", || {
+        for verse in &verses {
+            black_box(VerseLayout::build(verse, AddedWordStyle::Italic));
+        }
+    });
+    measure("read and lay out", || {
+        let verses = reader.render_content_to_verses("Bench", "Ps 119").unwrap();
+        for verse in &verses {
+            black_box(VerseLayout::build(verse, AddedWordStyle::Italic));
+        }
+    });
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -6,14 +6,54 @@ fn main() {
     let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
     let sword_src = root.join("sword");
 
-    let dst = cmake::Config::new(&sword_src)
+    // BibleSync lives in an external library SWORD does not vendor; the
+    // native session shim is a no-op unless it is found and compiled in
+    println!("cargo:rustc-check-cfg=cfg(bible_sync)");
+    let bible_sync = pkg_config::Config::new()
+        .cargo_metadata(false)
+        .probe("biblesync")
+        .ok();
+    if bible_sync.is_some() {
+        println!("cargo:rustc-cfg=bible_sync");
+    }
+
+    let mut sword = cmake::Config::new(&sword_src);
+    sword
         .define("SWORD_BUILD_SHARED", "OFF") // Keep engine static
         .define("SWORD_BUILD_EXAMPLES", "OFF")
-        .define("SWORD_BUILD_TESTS", "OFF")
-        .build();
+        .define("SWORD_BUILD_TESTS", "OFF");
+    let dst = sword.build();
+
+    // The native shims link against libsword, so they must be listed first
+    let include_path = dst.join("include");
+    let mut native = cc::Build::new();
+    native
+        .cpp(true)
+        .file(root.join("native").join("module_writer.cpp"))
+        .file(root.join("native").join("log_bridge.cpp"))
+        .file(root.join("native").join("bible_sync.cpp"))
+        .include(include_path.join("sword"))
+        .include(root.join("native"))
+        .warnings(false);
+    if let Some(library) = &bible_sync {
+        native.define("BIBLESYNC", None);
+        for include in &library.include_paths {
+            native.include(include);
+        }
+    }
+    native.compile("xbible_native");
+    println!("cargo:rerun-if-changed=native");
 
     println!("cargo:rustc-link-search=native={}/lib", dst.display());
     println!("cargo:rustc-link-lib=static=sword");
+    if let Some(library) = &bible_sync {
+        for lib_path in &library.link_paths {
+            println!("cargo:rustc-link-search=native={}", lib_path.display());
+        }
+        for lib in &library.libs {
+            println!("cargo:rustc-link-lib=dylib={}", lib);
+        }
+    }
 
     // 2. --- LINK SYSTEM DEPENDENCIES PER OS ---
     let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
@@ -58,13 +98,28 @@ fn main() {
     }
 
     // 3. --- GENERATE BINDINGS ---
-    let include_path = dst.join("include");
     let header_path = include_path.join("sword").join("flatapi.h");
+    let writer_header = root.join("native").join("module_writer.h");
+    let log_header = root.join("native").join("log_bridge.h");
+    let bible_sync_header = root.join("native").join("bible_sync.h");
 
     let bindings = bindgen::Builder::default()
         .header(header_path.to_str().expect("Could not find flatapi.h"))
+        .header(
+            writer_header
+                .to_str()
+                .expect("Could not find module_writer.h"),
+        )
+        .header(log_header.to_str().expect("Could not find log_bridge.h"))
+        .header(
+            bible_sync_header
+                .to_str()
+                .expect("Could not find bible_sync.h"),
+        )
         .clang_arg(format!("-I{}", include_path.display()))
         .allowlist_function("org_crosswire_sword.*")
+        .allowlist_function("xbible_.*")
+        .allowlist_var("XBIBLE_.*")
         .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
         .generate()
         .expect("Unable to generate bindings");
@@ -75,6 +130,13 @@ fn main() {
         .expect("Couldn't write bindings!");
 
     // 4. --- COMPILE GTK RESOURCES ---
+    // Only the GTK application embeds them; the library and CLI build without
+    #[cfg(feature = "gui")]
+    compile_resources();
+}
+
+#[cfg(feature = "gui")]
+fn compile_resources() {
     glib_build_tools::compile_resources(
         &["data"],
         "data/resources.gresource.xml",
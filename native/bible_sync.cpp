@@ -0,0 +1,90 @@
+#include "bible_sync.h"
+
+#ifdef BIBLESYNC
+
+#include <mutex>
+#include <string>
+
+#include <biblesync.hh>
+
+using std::string;
+
+namespace {
+
+// Receive runs on xBible's polling thread and Transmit on the UI thread
+std::mutex lock;
+BibleSync *session = 0;
+xbible_BibleSyncCallback listener = 0;
+
+void navigated(char cmd, string, string, string ref, string, string, string, string, string) {
+	// Chat, presence and error packets are not shown anywhere
+	if (cmd == 'N' && listener) listener(ref.c_str());
+}
+
+BibleSync_mode protocolMode(int mode) {
+	switch (mode) {
+	case XBIBLE_BIBLE_SYNC_SPEAKER: return BSP_MODE_SPEAKER;
+	case XBIBLE_BIBLE_SYNC_AUDIENCE: return BSP_MODE_AUDIENCE;
+	default: return BSP_MODE_PERSONAL;
+	}
+}
+
+}
+
+extern "C" {
+
+int xbible_BibleSync_start(const char *app, const char *version, const char *user,
+		const char *passphrase, int mode, xbible_BibleSyncCallback callback) {
+	std::lock_guard<std::mutex> guard(lock);
+	delete session;
+	listener = callback;
+	session = new BibleSync(app, version, user);
+	if (session->setMode(protocolMode(mode), navigated, passphrase) == BSP_MODE_DISABLE) {
+		delete session;
+		session = 0;
+		listener = 0;
+		return -1;
+	}
+	return 0;
+}
+
+void xbible_BibleSync_receive(void) {
+	std::lock_guard<std::mutex> guard(lock);
+	if (session) BibleSync::Receive(session);
+}
+
+int xbible_BibleSync_transmit(const char *bible, const char *osisRef) {
+	std::lock_guard<std::mutex> guard(lock);
+	if (!session) return -1;
+	return session->Transmit(bible, osisRef) == BSP_XMIT_OK ? 0 : -1;
+}
+
+void xbible_BibleSync_stop(void) {
+	std::lock_guard<std::mutex> guard(lock);
+	delete session;
+	session = 0;
+	listener = 0;
+}
+
+}
+
+#else
+
+extern "C" {
+
+int xbible_BibleSync_start(const char *, const char *, const char *, const char *, int,
+		xbible_BibleSyncCallback) {
+	return -1;
+}
+
+void xbible_BibleSync_receive(void) {}
+
+int xbible_BibleSync_transmit(const char *, const char *) {
+	return -1;
+}
+
+void xbible_BibleSync_stop(void) {}
+
+}
+
+#endif
@@ -0,0 +1,41 @@
+/*
+ * BibleSync session driven from Rust.
+ *
+ * The flat API always joins in personal mode and runs its own polling loop,
+ * which cannot be stopped before it has started. Here xBible picks the
+ * protocol mode and polls the session from a thread it owns.
+ */
+#ifndef XBIBLE_BIBLE_SYNC_H
+#define XBIBLE_BIBLE_SYNC_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Protocol modes: send and follow, speak to an audience, follow a speaker */
+#define XBIBLE_BIBLE_SYNC_PERSONAL 1
+#define XBIBLE_BIBLE_SYNC_SPEAKER 2
+#define XBIBLE_BIBLE_SYNC_AUDIENCE 3
+
+/* Receives each navigation ("John.3.16", or several separated by ';') */
+typedef void (*xbible_BibleSyncCallback)(const char *osisRef);
+
+/* Joins the group for passphrase, replacing any session; returns 0 on
+ * success and -1 when BibleSync is not compiled in or could not start */
+int xbible_BibleSync_start(const char *app, const char *version, const char *user,
+		const char *passphrase, int mode, xbible_BibleSyncCallback callback);
+
+/* Handles the packets waiting, calling back for navigations; never blocks */
+void xbible_BibleSync_receive(void);
+
+/* Announces osisRef as read in bible; returns 0 once sent */
+int xbible_BibleSync_transmit(const char *bible, const char *osisRef);
+
+/* Leaves the group */
+void xbible_BibleSync_stop(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
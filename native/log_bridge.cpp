@@ -0,0 +1,30 @@
+#include "log_bridge.h"
+
+#include <swlog.h>
+
+using namespace sword;
+
+namespace {
+
+class CallbackLog : public SWLog {
+	xbible_LogCallback callback;
+
+public:
+	CallbackLog(xbible_LogCallback callback) : callback(callback) {}
+
+	void logMessage(const char *message, int level) const {
+		callback(level, message);
+	}
+};
+
+}
+
+extern "C" {
+
+void xbible_SWLog_setCallback(xbible_LogCallback callback, int level) {
+	// SWLog owns the system log and deletes it at exit
+	SWLog::setSystemLog(new CallbackLog(callback));
+	SWLog::getSystemLog()->setLogLevel((char)level);
+}
+
+}
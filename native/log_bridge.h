@@ -0,0 +1,24 @@
+/*
+ * Routes SWORD's system log to a callback.
+ *
+ * The flat API can set the log level but not the destination, and SWLog
+ * writes straight to stderr, so xBible installs its own SWLog subclass.
+ */
+#ifndef XBIBLE_LOG_BRIDGE_H
+#define XBIBLE_LOG_BRIDGE_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Receives each message with its SWLog level (1 = error ... 5 = debug) */
+typedef void (*xbible_LogCallback)(int level, const char *message);
+
+/* Replaces the system log; messages above level are dropped by SWORD */
+void xbible_SWLog_setCallback(xbible_LogCallback callback, int level);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
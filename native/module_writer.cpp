@@ -0,0 +1,62 @@
+#include "module_writer.h"
+
+#include <swmodule.h>
+#include <versekey.h>
+#include <zipcomprs.h>
+#include <ztext.h>
+
+using namespace sword;
+
+namespace {
+
+struct ModuleWriter {
+	zText *module;
+	VerseKey key;
+};
+
+}
+
+extern "C" {
+
+int xbible_ModuleWriter_create(const char *path, const char *versification) {
+	return zText::createModule(path, BOOKBLOCKS, versification);
+}
+
+intptr_t xbible_ModuleWriter_open(const char *path, const char *versification) {
+	ModuleWriter *writer = new ModuleWriter();
+	writer->module = new zText(path, 0, 0, BOOKBLOCKS, new ZipCompress(), 0,
+			ENC_UTF8, DIRECTION_LTR, FMT_OSIS, 0, versification);
+	if (!writer->module->isWritable()) {
+		delete writer->module;
+		delete writer;
+		return 0;
+	}
+
+	writer->key.setVersificationSystem(versification);
+	writer->key.setAutoNormalize(false);
+	writer->key.setIntros(true);
+	return (intptr_t)writer;
+}
+
+int xbible_ModuleWriter_setEntry(intptr_t hWriter, const char *osisRef, const char *text) {
+	ModuleWriter *writer = (ModuleWriter *)hWriter;
+	if (!writer) return -1;
+
+	writer->key.setText(osisRef);
+	if (writer->key.popError()) return -1;
+
+	writer->module->setKey(writer->key);
+	writer->module->setEntry(text);
+	return 0;
+}
+
+void xbible_ModuleWriter_close(intptr_t hWriter) {
+	ModuleWriter *writer = (ModuleWriter *)hWriter;
+	if (!writer) return;
+
+	// zText compresses and writes the last open block on destruction
+	delete writer->module;
+	delete writer;
+}
+
+}
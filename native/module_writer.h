@@ -0,0 +1,32 @@
+/*
+ * Minimal C interface for writing compressed SWORD text modules.
+ *
+ * osis2mod exits the process on errors and keeps function-local state, so
+ * xBible drives the same zText writer directly instead.
+ */
+#ifndef XBIBLE_MODULE_WRITER_H
+#define XBIBLE_MODULE_WRITER_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Creates empty zText data files under path; returns 0 on success */
+int xbible_ModuleWriter_create(const char *path, const char *versification);
+
+/* Opens a zText module for writing; returns 0 on failure */
+intptr_t xbible_ModuleWriter_open(const char *path, const char *versification);
+
+/* Stores text at an OSIS verse reference ("Gen.1.1"); returns 0 on success */
+int xbible_ModuleWriter_setEntry(intptr_t hWriter, const char *osisRef, const char *text);
+
+/* Flushes the pending compressed block and frees the writer */
+void xbible_ModuleWriter_close(intptr_t hWriter);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -3,19 +3,26 @@ use relm4::prelude::*;
 use std::{collections::HashMap, sync::Arc};
 
 use crate::features::core::{
-    components::sidebar::{NavigationPage, SideBar, SidebarMessage},
+    components::{
+        preferences::{PreferencesDialog, PreferencesOutput},
+        sidebar::{NavigationPage, SideBar, SidebarMessage},
+    },
     module_engine::sword_engine::SwordEngine,
     pages::{
         library::library_page::{LibraryPage, LibraryPageCategory, LibraryPageOutput},
+        plans::plans_page::{PlansPage, PlansPageOutput},
         store::store_page::{StorePageOutput, StorePage},
-        study::study_page::{StudyPage, StudyPageOutPut},
+        study::study_tabs::{StudyTabs, StudyTabsInput, StudyTabsOutput},
     },
+    logging,
+    settings::{LogLevel, ReadingSettings, Settings},
 };
 
 enum PageController {
-    Bible(Controller<StudyPage>),
+    Bible(Controller<StudyTabs>),
     Store(Controller<StorePage>),
     Library(Controller<LibraryPage>),
+    Plans(Controller<PlansPage>),
 }
 
 impl PageController {
@@ -24,13 +31,19 @@ impl PageController {
             Self::Bible(c) => c.widget(),
             Self::Store(c) => c.widget(),
             Self::Library(c) => c.widget(),
+            Self::Plans(c) => c.widget(),
         }
     }
 }
 pub struct AppModel {
+    window: adw::ApplicationWindow,
     side_bar: Controller<SideBar>,
+    preferences: Controller<PreferencesDialog>,
     pages_cache: HashMap<String, PageController>,
     engine: Arc<SwordEngine>,
+    reading: ReadingSettings,
+    /// Font and spacing of the verse text, over the application stylesheet
+    reading_style: gtk::CssProvider,
     is_sidebar_visible: bool,
     current_page_key: String,
 }
@@ -40,6 +53,12 @@ pub enum AppInputMessage {
     ToggleSidebar,
     SetContentPage(NavigationPage),
     SetSidebarVisibility(bool),
+    OpenPreferences,
+    /// Apply the reading appearance everywhere and keep it for next time
+    SetReading(ReadingSettings),
+    SetLogLevel(LogLevel),
+    /// Show a reading plan's reading in the Study page
+    OpenReading(String),
 }
 
 #[relm4::component(pub)]
@@ -82,23 +101,41 @@ impl SimpleComponent for AppModel {
 
     fn init(
         _init: Self::Init,
-        _root: Self::Root,
+        root: Self::Root,
         sender: ComponentSender<Self>,
     ) -> ComponentParts<Self> {
-        let engine = SwordEngine::new();
+        let engine = SwordEngine::new().expect("Could not start the SWORD engine");
+        let settings = Settings::load(&engine.sword_path);
+        let reading = settings.reading;
+
+        let reading_style = gtk::CssProvider::new();
+        reading_style.load_from_string(&reading.css());
+        gtk::style_context_add_provider_for_display(
+            &root.display(),
+            &reading_style,
+            gtk::STYLE_PROVIDER_PRIORITY_APPLICATION + 1,
+        );
 
         let side_bar = SideBar::builder()
             .launch(())
             .forward(sender.input_sender(), |message| match message {
                 SidebarMessage::ToggleSidebar => AppInputMessage::ToggleSidebar,
                 SidebarMessage::SelectPage(page) => AppInputMessage::SetContentPage(page),
+                SidebarMessage::OpenPreferences => AppInputMessage::OpenPreferences,
+            });
+
+        let preferences = PreferencesDialog::builder()
+            .launch((reading.clone(), settings.log_level))
+            .forward(sender.input_sender(), |message| match message {
+                PreferencesOutput::Changed(reading) => AppInputMessage::SetReading(reading),
+                PreferencesOutput::LogLevelChanged(level) => AppInputMessage::SetLogLevel(level),
             });
 
         let bible_page = PageController::Bible(
-            StudyPage::builder()
-                .launch((engine.clone(), false))
+            StudyTabs::builder()
+                .launch((engine.clone(), false, reading.clone()))
                 .forward(sender.input_sender(), |message| match message {
-                    StudyPageOutPut::ToggleSidebar => AppInputMessage::ToggleSidebar,
+                    StudyTabsOutput::ToggleSidebar => AppInputMessage::ToggleSidebar,
                 }),
         );
 
@@ -106,8 +143,12 @@ impl SimpleComponent for AppModel {
         pages_cache.insert(NavigationPage::Bible.to_key(), bible_page);
 
         let model = AppModel {
+            window: root.clone(),
             side_bar,
+            preferences,
             engine,
+            reading,
+            reading_style,
             is_sidebar_visible: false,
             pages_cache: pages_cache,
             current_page_key: NavigationPage::Bible.to_key(),
@@ -137,10 +178,10 @@ impl SimpleComponent for AppModel {
                     match page {
                         NavigationPage::Bible => {
                             let bible_page = PageController::Bible(
-                                StudyPage::builder()
-                                    .launch((self.engine.clone(), false))
+                                StudyTabs::builder()
+                                    .launch((self.engine.clone(), false, self.reading.clone()))
                                     .forward(sender.input_sender(), |message| match message {
-                                        StudyPageOutPut::ToggleSidebar => {
+                                        StudyTabsOutput::ToggleSidebar => {
                                             AppInputMessage::ToggleSidebar
                                         }
                                     }),
@@ -162,6 +203,19 @@ impl SimpleComponent for AppModel {
                             self.pages_cache
                                 .insert(key.clone(), PageController::Library(libarary_page));
                         }
+                        NavigationPage::Plans => {
+                            let plans_page = PlansPage::builder()
+                                .launch((self.engine.clone(), self.is_sidebar_visible))
+                                .forward(sender.input_sender(), |message| match message {
+                                    PlansPageOutput::ToggleSidebar => AppInputMessage::ToggleSidebar,
+                                    PlansPageOutput::OpenReading(reference) => {
+                                        AppInputMessage::OpenReading(reference)
+                                    }
+                                });
+
+                            self.pages_cache
+                                .insert(key.clone(), PageController::Plans(plans_page));
+                        }
                         NavigationPage::Store => {
                             let store_page = StorePage::builder()
                                 .launch((self.engine.clone(), self.is_sidebar_visible))
@@ -177,6 +231,42 @@ impl SimpleComponent for AppModel {
 
                 self.current_page_key = key;
             }
+            AppInputMessage::OpenPreferences => {
+                self.preferences.widget().present(Some(&self.window));
+            }
+            AppInputMessage::SetReading(reading) => {
+                self.reading_style.load_from_string(&reading.css());
+                if let Some(PageController::Bible(page)) =
+                    self.pages_cache.get(&NavigationPage::Bible.to_key())
+                {
+                    page.emit(StudyTabsInput::SetReading(reading.clone()));
+                }
+
+                let dir = &self.engine.sword_path;
+                let mut settings = Settings::load(dir);
+                settings.reading = reading.clone();
+                if let Err(e) = settings.save(dir) {
+                    log::warn!("Could not save {}: {}", Settings::path(dir).display(), e);
+                }
+                self.reading = reading;
+            }
+            AppInputMessage::SetLogLevel(level) => {
+                logging::set_level(level.filter());
+
+                let dir = &self.engine.sword_path;
+                let mut settings = Settings::load(dir);
+                settings.log_level = level;
+                if let Err(e) = settings.save(dir) {
+                    log::warn!("Could not save {}: {}", Settings::path(dir).display(), e);
+                }
+            }
+            AppInputMessage::OpenReading(reference) => {
+                let key = NavigationPage::Bible.to_key();
+                if let Some(PageController::Bible(page)) = self.pages_cache.get(&key) {
+                    page.emit(StudyTabsInput::OpenReference(reference));
+                    self.current_page_key = key;
+                }
+            }
         }
     }
 }
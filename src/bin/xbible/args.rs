@@ -0,0 +1,348 @@
+use std::path::PathBuf;
+
+use x_bible::features::{
+    bible::export::{ExportFormat, ExportOptions},
+    core::module_engine::sword_module::SearchType,
+};
+
+pub const USAGE: &str = "\
+Usage: xbible [--data-dir DIR] [--verbose] COMMAND
+
+Commands:
+  list [bible|commentary|dictionary|book|map]
+                        Installed modules, optionally of one kind
+  read MODULE REFERENCE [--format plain|markdown|html|osis|json]
+                        [--no-verse-numbers] [--no-red-letter]
+                        A passage such as \"John 3:16-18\", or a dictionary key
+  search MODULE QUERY [--type phrase|words|regex] [--scope RANGE]
+                        Matching keys, one per line
+  install SOURCE MODULE Refreshes SOURCE and installs MODULE from it
+  uninstall MODULE      Removes an installed module
+  sources [SOURCE]      Remote repositories, or the modules SOURCE offers
+  serve [--port PORT] [--allow-origin ORIGIN]
+                        JSON API on http://127.0.0.1:PORT (default 7337):
+                        /modules, /passage?module=M&ref=R,
+                        /search?module=M&q=Q, /strongs/G3056.
+                        Web pages can read it only from ORIGIN
+
+Options:
+  --data-dir DIR        SWORD library to use instead of the user's
+                        (also XBIBLE_DATA_DIR)
+  -v, --verbose         Log progress to stderr (also XBIBLE_LOG=LEVEL)
+";
+
+pub const DEFAULT_PORT: u16 = 7337;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Args {
+    pub data_dir: Option<PathBuf>,
+    pub verbose: bool,
+    pub command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Category {
+    Bible,
+    Commentary,
+    Dictionary,
+    Book,
+    Map,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    List {
+        category: Option<Category>,
+    },
+    Read {
+        module: String,
+        reference: String,
+        format: ExportFormat,
+        options: ExportOptions,
+    },
+    Search {
+        module: String,
+        query: String,
+        search_type: SearchType,
+        scope: Option<String>,
+    },
+    Install {
+        source: String,
+        module: String,
+    },
+    Uninstall {
+        module: String,
+    },
+    Sources {
+        source: Option<String>,
+    },
+    Serve {
+        port: u16,
+        /// The one web origin whose pages may read the API
+        allow_origin: Option<String>,
+    },
+    Help,
+}
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ArgsError {
+    #[error("unknown command “{0}”")]
+    UnknownCommand(String),
+    #[error("unknown option “{0}”")]
+    UnknownOption(String),
+    #[error("{0} needs a value")]
+    MissingValue(&'static str),
+    #[error("missing {0}")]
+    Missing(&'static str),
+    #[error("unexpected argument “{0}”")]
+    Unexpected(String),
+    #[error("“{value}” is not a {what}")]
+    Invalid { what: &'static str, value: String },
+}
+
+/// Parses the arguments after the program name
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, ArgsError> {
+    let mut args: Vec<String> = args.into_iter().collect();
+    let data_dir = take_option(&mut args, "--data-dir")?.map(PathBuf::from);
+    let verbose = take_flag(&mut args, "--verbose") | take_flag(&mut args, "-v");
+    let help = take_flag(&mut args, "--help") | take_flag(&mut args, "-h");
+
+    let mut args = args.into_iter();
+    let command = match args.next() {
+        _ if help => Command::Help,
+        None => Command::Help,
+        Some(name) => parse_command(&name, args.collect())?,
+    };
+
+    Ok(Args {
+        data_dir,
+        verbose,
+        command,
+    })
+}
+
+fn parse_command(name: &str, mut rest: Vec<String>) -> Result<Command, ArgsError> {
+    let command = match name {
+        "help" => Command::Help,
+        "list" => Command::List {
+            category: take_positional(&mut rest)
+                .map(|value| parse_category(&value))
+                .transpose()?,
+        },
+        "read" => {
+            let format = match take_option(&mut rest, "--format")? {
+                Some(value) => parse_format(&value)?,
+                None => ExportFormat::PlainText,
+            };
+            let options = ExportOptions {
+                verse_numbers: !take_flag(&mut rest, "--no-verse-numbers"),
+                red_letter: !take_flag(&mut rest, "--no-red-letter"),
+            };
+            Command::Read {
+                module: take_positional(&mut rest).ok_or(ArgsError::Missing("MODULE"))?,
+                reference: take_words(&mut rest).ok_or(ArgsError::Missing("REFERENCE"))?,
+                format,
+                options,
+            }
+        }
+        "search" => {
+            let search_type = match take_option(&mut rest, "--type")? {
+                Some(value) => parse_search_type(&value)?,
+                None => SearchType::MultiWord,
+            };
+            let scope = take_option(&mut rest, "--scope")?;
+            Command::Search {
+                module: take_positional(&mut rest).ok_or(ArgsError::Missing("MODULE"))?,
+                query: take_words(&mut rest).ok_or(ArgsError::Missing("QUERY"))?,
+                search_type,
+                scope,
+            }
+        }
+        "install" => Command::Install {
+            source: take_positional(&mut rest).ok_or(ArgsError::Missing("SOURCE"))?,
+            module: take_positional(&mut rest).ok_or(ArgsError::Missing("MODULE"))?,
+        },
+        "uninstall" => Command::Uninstall {
+            module: take_positional(&mut rest).ok_or(ArgsError::Missing("MODULE"))?,
+        },
+        "sources" => Command::Sources {
+            source: take_positional(&mut rest),
+        },
+        "serve" => Command::Serve {
+            port: match take_option(&mut rest, "--port")? {
+                Some(value) => value.parse().map_err(|_| ArgsError::Invalid {
+                    what: "port",
+                    value,
+                })?,
+                None => DEFAULT_PORT,
+            },
+            allow_origin: take_option(&mut rest, "--allow-origin")?,
+        },
+        _ => return Err(ArgsError::UnknownCommand(name.to_string())),
+    };
+
+    match rest.into_iter().next() {
+        Some(arg) if arg.starts_with('-') => Err(ArgsError::UnknownOption(arg)),
+        Some(arg) => Err(ArgsError::Unexpected(arg)),
+        None => Ok(command),
+    }
+}
+
+/// Removes `name` and the value after it
+fn take_option(args: &mut Vec<String>, name: &'static str) -> Result<Option<String>, ArgsError> {
+    let Some(index) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+    if index + 1 >= args.len() {
+        return Err(ArgsError::MissingValue(name));
+    }
+    args.remove(index);
+    Ok(Some(args.remove(index)))
+}
+
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let found = args.iter().position(|arg| arg == name);
+    if let Some(index) = found {
+        args.remove(index);
+    }
+    found.is_some()
+}
+
+/// The first argument that is not an option
+fn take_positional(args: &mut Vec<String>) -> Option<String> {
+    let index = args.iter().position(|arg| !arg.starts_with("--"))?;
+    Some(args.remove(index))
+}
+
+/// Every remaining argument that is not an option, joined by spaces, so
+/// `read KJV John 3:16` needs no quoting
+fn take_words(args: &mut Vec<String>) -> Option<String> {
+    let mut words = Vec::new();
+    while let Some(word) = take_positional(args) {
+        words.push(word);
+    }
+    (!words.is_empty()).then(|| words.join(" "))
+}
+
+fn parse_category(value: &str) -> Result<Category, ArgsError> {
+    match value.to_ascii_lowercase().as_str() {
+        "bible" | "bibles" => Ok(Category::Bible),
+        "commentary" | "commentaries" => Ok(Category::Commentary),
+        "dictionary" | "dictionaries" => Ok(Category::Dictionary),
+        "book" | "books" => Ok(Category::Book),
+        "map" | "maps" => Ok(Category::Map),
+        _ => Err(ArgsError::Invalid {
+            what: "module category",
+            value: value.to_string(),
+        }),
+    }
+}
+
+fn parse_format(value: &str) -> Result<ExportFormat, ArgsError> {
+    match value.to_ascii_lowercase().as_str() {
+        "plain" | "txt" => Ok(ExportFormat::PlainText),
+        "markdown" | "md" => Ok(ExportFormat::Markdown),
+        "html" => Ok(ExportFormat::Html),
+        "osis" => Ok(ExportFormat::Osis),
+        "json" => Ok(ExportFormat::Json),
+        _ => Err(ArgsError::Invalid {
+            what: "format",
+            value: value.to_string(),
+        }),
+    }
+}
+
+fn parse_search_type(value: &str) -> Result<SearchType, ArgsError> {
+    SearchType::from_name(value).ok_or_else(|| ArgsError::Invalid {
+        what: "search type",
+        value: value.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(line: &str) -> Result<Args, ArgsError> {
+        parse(line.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn parses_read_with_unquoted_reference() {
+        let args = parse_str("--data-dir /tmp/lib read KJV John 3:16-18 --format md").unwrap();
+        assert_eq!(args.data_dir, Some(PathBuf::from("/tmp/lib")));
+        assert_eq!(
+            args.command,
+            Command::Read {
+                module: "KJV".into(),
+                reference: "John 3:16-18".into(),
+                format: ExportFormat::Markdown,
+                options: ExportOptions::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_other_commands() {
+        assert_eq!(
+            parse_str("list bible -v").unwrap(),
+            Args {
+                data_dir: None,
+                verbose: true,
+                command: Command::List {
+                    category: Some(Category::Bible)
+                },
+            }
+        );
+        assert_eq!(
+            parse_str("search KJV --type phrase in the beginning --scope Gen")
+                .unwrap()
+                .command,
+            Command::Search {
+                module: "KJV".into(),
+                query: "in the beginning".into(),
+                search_type: SearchType::Phrase,
+                scope: Some("Gen".into()),
+            }
+        );
+        assert_eq!(
+            parse_str("serve --port 8080").unwrap().command,
+            Command::Serve {
+                port: 8080,
+                allow_origin: None,
+            }
+        );
+        assert_eq!(
+            parse_str("serve --allow-origin http://localhost:3000")
+                .unwrap()
+                .command,
+            Command::Serve {
+                port: DEFAULT_PORT,
+                allow_origin: Some("http://localhost:3000".into()),
+            }
+        );
+        assert_eq!(parse_str("").unwrap().command, Command::Help);
+        assert_eq!(parse_str("sources --help").unwrap().command, Command::Help);
+    }
+
+    #[test]
+    fn reports_bad_arguments() {
+        assert_eq!(parse_str("read KJV"), Err(ArgsError::Missing("REFERENCE")));
+        assert_eq!(
+            parse_str("read KJV John 1 --format"),
+            Err(ArgsError::MissingValue("--format"))
+        );
+        assert_eq!(
+            parse_str("uninstall KJV --force"),
+            Err(ArgsError::UnknownOption("--force".into()))
+        );
+        assert_eq!(
+            parse_str("install CrossWire KJV extra"),
+            Err(ArgsError::Unexpected("extra".into()))
+        );
+        assert_eq!(
+            parse_str("fetch KJV"),
+            Err(ArgsError::UnknownCommand("fetch".into()))
+        );
+    }
+}
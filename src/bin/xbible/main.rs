@@ -0,0 +1,194 @@
+//! `xbible`: the library from the command line, in the spirit of SWORD's
+//! `diatheke`. Output goes to stdout, logs and errors to stderr.
+
+mod args;
+
+use std::io::{self, Write};
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use log::LevelFilter;
+use x_bible::features::{
+    bible::{
+        components::page::reader::ChapterReader,
+        export::{ExportFormat, ExportOptions, Exporter, PassageRange},
+    },
+    core::{
+        logging,
+        module_engine::{error::EngineError, sword_engine::SwordEngine, sword_module::SwordModule},
+    },
+    server::Server,
+    store::{error::StoreError, services::download::DownloadService},
+};
+
+use crate::args::{Args, Category, Command, USAGE};
+
+#[derive(Debug, thiserror::Error)]
+enum CliError {
+    #[error(transparent)]
+    Engine(#[from] EngineError),
+    #[error(transparent)]
+    Store(#[from] StoreError),
+    #[error("{module} has no entry at “{key}”")]
+    NoEntry { module: String, key: String },
+    #[error("Could not write output: {0}")]
+    Output(#[from] io::Error),
+    #[error("Could not serve the API: {0}")]
+    Serve(io::Error),
+}
+
+fn main() -> ExitCode {
+    let args = match args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("xbible: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    if args.command == Command::Help {
+        print!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let level = if args.verbose {
+        LevelFilter::Info
+    } else {
+        LevelFilter::Warn
+    };
+    logging::init(None, level);
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        // `xbible read ... | head` closes stdout early
+        Err(CliError::Output(e)) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("xbible: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), CliError> {
+    let path = match args.data_dir {
+        Some(path) => path,
+        None => SwordEngine::get_sword_path()?,
+    };
+    let engine = SwordEngine::open(path)?;
+    let mut out = io::stdout().lock();
+
+    match args.command {
+        Command::List { category } => {
+            let modules = match category {
+                None => engine.get_modules()?,
+                Some(Category::Bible) => engine.get_bible_modules()?,
+                Some(Category::Commentary) => engine.get_commentary_modules()?,
+                Some(Category::Dictionary) => engine.get_dictionary_modules()?,
+                Some(Category::Book) => engine.get_book_modules()?,
+                Some(Category::Map) => engine.get_map_modules()?,
+            };
+            write_modules(&mut out, &modules)?;
+        }
+        Command::Read {
+            module,
+            reference,
+            format,
+            options,
+        } => {
+            let text = read(&engine, &module, &reference, format, &options)?;
+            writeln!(out, "{}", text.trim_end())?;
+        }
+        Command::Search {
+            module,
+            query,
+            search_type,
+            scope,
+        } => {
+            let hits = engine.search(&module, &query, search_type, scope.as_deref())?;
+            for hit in &hits {
+                writeln!(out, "{}", hit.key)?;
+            }
+            log::info!("{} matches for “{}” in {}", hits.len(), query, module);
+        }
+        Command::Install { source, module } => {
+            let service = DownloadService::new(engine);
+            // Installing reads the repository's cached module list
+            service.get_available_modules(&source)?;
+            service.download(&source, &module)?;
+            writeln!(out, "Installed {} from {}", module, source)?;
+        }
+        Command::Uninstall { module } => {
+            engine.uninstall_module(&module)?;
+            writeln!(out, "Removed {}", module)?;
+        }
+        Command::Sources { source: None } => {
+            for source in engine.get_remote_source_list()? {
+                writeln!(out, "{}", source)?;
+            }
+        }
+        Command::Sources {
+            source: Some(source),
+        } => {
+            let modules = DownloadService::new(engine).get_available_modules(&source)?;
+            write_modules(&mut out, &modules)?;
+        }
+        Command::Serve { port, allow_origin } => {
+            // Loopback only: the API has no authentication
+            let server = Server::bind(("127.0.0.1", port), engine)
+                .map_err(CliError::Serve)?
+                .allow_origin(allow_origin);
+            let addr = server.local_addr().map_err(CliError::Serve)?;
+            writeln!(out, "Serving on http://{}", addr)?;
+            out.flush()?;
+            drop(out);
+            server.run().map_err(CliError::Serve)?;
+        }
+        Command::Help => unreachable!("handled before the engine opens"),
+    }
+    Ok(())
+}
+
+/// A passage for verse references, otherwise the entry at `key` as plain
+/// text, e.g. `read StrongsGreek 03056`
+fn read(
+    engine: &Arc<SwordEngine>,
+    module: &str,
+    key: &str,
+    format: ExportFormat,
+    options: &ExportOptions,
+) -> Result<String, CliError> {
+    let no_entry = || CliError::NoEntry {
+        module: module.to_string(),
+        key: key.to_string(),
+    };
+
+    if let Some(range) = PassageRange::parse(key) {
+        let reader = ChapterReader::new(engine.clone());
+        let passage = reader.read_passage(module, None, &range, key)?;
+        if passage.book.chapters.is_empty() {
+            return Err(no_entry());
+        }
+        return Ok(Exporter::export(&passage, format, options));
+    }
+
+    let text = engine.get_plain_text(module, key)?.ok_or_else(no_entry)?;
+    Ok(match format {
+        ExportFormat::Json => serde_json::json!({
+            "module": module,
+            "key": key,
+            "text": text,
+        })
+        .to_string(),
+        _ => text,
+    })
+}
+
+fn write_modules(out: &mut impl Write, modules: &[SwordModule]) -> io::Result<()> {
+    for module in modules {
+        writeln!(
+            out,
+            "{:<16} {:<20} {:<6} {}",
+            module.name, module.category, module.language, module.description
+        )?;
+    }
+    Ok(())
+}
@@ -1 +1 @@
-pub mod page;
\ No newline at end of file
+pub mod page;
@@ -0,0 +1,236 @@
+use std::ops::Range;
+
+use crate::features::{
+    bible::model::{BlockKind, NoteKind, SegmentStyle, Verse},
+    core::settings::AddedWordStyle,
+};
+
+/// Indent per poetry level, drawn with em spaces
+const POETRY_INDENT: &str = "\u{2003}\u{2003}";
+
+/// How a run of the verse text is drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpanStyle {
+    Red,
+    Added,
+    Italic,
+    Bold,
+    /// Superscript marker of a footnote or cross-reference
+    Marker(NoteKind),
+    Heading,
+    /// Psalm superscriptions and colophons
+    Aside,
+}
+
+/// A verse flattened into one string with the styles, words and note markers
+/// it covers as byte ranges, so the reader can draw it with a single label and
+/// map clicks back onto the model.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VerseLayout {
+    pub text: String,
+    /// Headings ahead of the first word, drawn above the verse number,
+    /// with the CSS class of each
+    pub headings: Vec<(String, &'static str)>,
+    pub spans: Vec<(Range<usize>, SpanStyle)>,
+    /// Range of each word, indexed like `Verse::words`
+    pub words: Vec<Range<usize>>,
+    /// Range of each note marker, indexed like `Verse::notes`
+    pub notes: Vec<Range<usize>>,
+}
+
+impl VerseLayout {
+    pub fn build(verse: &Verse, added_style: AddedWordStyle) -> Self {
+        let mut layout = Self {
+            words: Vec::with_capacity(verse.words.len()),
+            notes: vec![0..0; verse.notes.len()],
+            ..Self::default()
+        };
+
+        for position in 0..=verse.words.len() {
+            // Markers stick to the preceding word, ahead of any line break
+            if position > 0 {
+                layout.push_notes(verse, position);
+            }
+            for block in verse.blocks.iter().filter(|b| b.anchor == position) {
+                match &block.kind {
+                    BlockKind::PoetryLine(level) => {
+                        layout.new_line();
+                        layout.text.push_str(&POETRY_INDENT.repeat(*level as usize));
+                    }
+                    BlockKind::Paragraph | BlockKind::LineBreak => layout.new_line(),
+                    kind => {
+                        let Some((text, css_class, style)) = Self::block_text(kind) else {
+                            continue;
+                        };
+                        if position == 0 {
+                            layout.headings.push((text.to_string(), css_class));
+                        } else {
+                            // Mid-verse titles and colophons get their own line
+                            layout.new_line();
+                            layout.push_styled(text, &[style]);
+                            layout.text.push('\n');
+                        }
+                    }
+                }
+            }
+
+            if position == 0 {
+                layout.push_notes(verse, position);
+            }
+
+            let Some(word) = verse.words.get(position) else {
+                continue;
+            };
+            if !layout.at_line_start() && !word.is_punctuation {
+                layout.text.push(' ');
+            }
+
+            let added = word.style == SegmentStyle::Added;
+            let mut styles = Vec::with_capacity(4);
+            if word.is_red {
+                styles.push(SpanStyle::Red);
+            }
+            if added && added_style == AddedWordStyle::Italic {
+                styles.push(SpanStyle::Added);
+            }
+            if word.is_italic {
+                styles.push(SpanStyle::Italic);
+            }
+            if word.is_bold_text {
+                styles.push(SpanStyle::Bold);
+            }
+
+            let start = layout.text.len();
+            if added && added_style == AddedWordStyle::Brackets && word.is_first_in_group {
+                layout.text.push('[');
+            }
+            layout.push_styled(&word.text, &styles);
+            if added && added_style == AddedWordStyle::Brackets && word.is_last_in_group {
+                layout.text.push(']');
+            }
+            layout.words.push(start..layout.text.len());
+        }
+
+        layout
+    }
+
+    /// Index of the word drawn at byte `index` of the text
+    pub fn word_at(&self, index: usize) -> Option<usize> {
+        Self::range_at(&self.words, index)
+    }
+
+    /// Index of the note whose marker is drawn at byte `index` of the text
+    pub fn note_at(&self, index: usize) -> Option<usize> {
+        self.notes.iter().position(|range| range.contains(&index))
+    }
+
+    /// Words are laid out in order, so their ranges can be bisected
+    fn range_at(ranges: &[Range<usize>], index: usize) -> Option<usize> {
+        let candidate = ranges.partition_point(|range| range.end <= index);
+        ranges
+            .get(candidate)
+            .filter(|range| range.contains(&index))
+            .map(|_| candidate)
+    }
+
+    fn push_notes(&mut self, verse: &Verse, position: usize) {
+        for (index, note) in verse.notes.iter().enumerate() {
+            if note.anchor == position {
+                let start = self.text.len();
+                self.push_styled(&note.marker, &[SpanStyle::Marker(note.kind)]);
+                self.notes[index] = start..self.text.len();
+            }
+        }
+    }
+
+    fn push_styled(&mut self, text: &str, styles: &[SpanStyle]) {
+        let start = self.text.len();
+        self.text.push_str(text);
+        for style in styles {
+            self.spans.push((start..self.text.len(), *style));
+        }
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.text.is_empty() || self.text.ends_with('\n') || self.text.ends_with('\u{2003}')
+    }
+
+    fn new_line(&mut self) {
+        if !self.at_line_start() {
+            self.text.push('\n');
+        }
+    }
+
+    fn block_text(kind: &BlockKind) -> Option<(&str, &'static str, SpanStyle)> {
+        match kind {
+            BlockKind::Heading(text) => Some((text, "section-heading", SpanStyle::Heading)),
+            BlockKind::Superscription(text) => {
+                Some((text, "psalm-superscription", SpanStyle::Aside))
+            }
+            BlockKind::Colophon(text) => Some((text, "colophon", SpanStyle::Aside)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::bible::model::{LexicalInfo, Note, Word};
+
+    fn sample() -> Verse {
+        Verse::builder("John.3.16", 16)
+            .block(BlockKind::Heading("For God So Loved the World".into()))
+            .word(Word::builder("For").build())
+            .word(
+                Word::builder("God")
+                    .red(true)
+                    .lex(Some(LexicalInfo::default().strong("G2316")))
+                    .build(),
+            )
+            .word(Word::builder("so").red(true).added(true).build())
+            .note(Note::new(NoteKind::Footnote, "a", 0).text("Or only begotten"))
+            .block(BlockKind::PoetryLine(1))
+            .word(Word::builder("loved").red(true).build())
+            .word(Word::builder(",").punctuation(true).build())
+            .build()
+    }
+
+    #[test]
+    fn flattens_words_notes_and_lines() {
+        let verse = sample();
+        let layout = VerseLayout::build(&verse, AddedWordStyle::Italic);
+
+        assert_eq!(layout.text, "For God soa\n\u{2003}\u{2003}loved,");
+        assert_eq!(
+            layout.headings,
+            vec![("For God So Loved the World".to_string(), "section-heading")]
+        );
+        let words: Vec<&str> = layout
+            .words
+            .iter()
+            .map(|r| &layout.text[r.clone()])
+            .collect();
+        assert_eq!(words, vec!["For", "God", "so", "loved", ","]);
+        assert_eq!(&layout.text[layout.notes[0].clone()], "a");
+        assert!(layout.spans.contains(&(8..10, SpanStyle::Added)));
+        assert!(layout.spans.contains(&(4..7, SpanStyle::Red)));
+    }
+
+    #[test]
+    fn hit_tests_words_and_markers() {
+        let mut verse = sample();
+        verse.words[2].is_first_in_group = true;
+        verse.words[2].is_last_in_group = true;
+        let layout = VerseLayout::build(&verse, AddedWordStyle::Brackets);
+
+        assert_eq!(layout.text, "For God [so]a\n\u{2003}\u{2003}loved,");
+        assert_eq!(layout.word_at(0), Some(0));
+        assert_eq!(layout.word_at(5), Some(1));
+        assert_eq!(layout.word_at(3), None);
+        assert_eq!(layout.word_at(9), Some(2));
+        assert_eq!(layout.word_at(12), None);
+        assert_eq!(layout.note_at(12), Some(0));
+        assert_eq!(layout.word_at(layout.text.len() - 1), Some(4));
+    }
+}
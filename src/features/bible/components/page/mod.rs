@@ -1,4 +1,15 @@
-pub mod helpers;
+//! The chapter view. `position` and `reader` hold no widgets, so they are
+//! part of the library without the `gui` feature.
+
+#[cfg(feature = "gui")]
+pub mod layout;
+#[cfg(feature = "gui")]
 pub mod model;
+#[cfg(feature = "gui")]
+pub mod note;
+pub mod position;
+pub mod reader;
+#[cfg(feature = "gui")]
 pub mod verse;
+#[cfg(feature = "gui")]
 pub mod word;
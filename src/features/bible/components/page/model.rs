@@ -1,57 +1,231 @@
 use adw::prelude::*;
-use ego_tree::NodeRef;
-use relm4::prelude::*;
-use std::os::raw::c_char;
-use std::{ffi::CStr, sync::Arc};
-
-use crate::{
-    features::{
-        bible::components::page::helpers::{LexicalInfo, SegmentStyle, Verse, Word},
-        core::module_engine::sword_engine::SwordEngine,
+use relm4::{prelude::*, typed_view::list::TypedListView};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::features::{
+    bible::{
+        components::page::{
+            position::ChapterPosition,
+            reader::{ChapterReader, ChapterRequest},
+            verse::{RowContext, VerseOutput, VerseRow},
+            word::{InterlinearRows, ReaderOptions, TextPalette},
+        },
+        export::{Citation, CitationOptions, ExportRequest, Exporter, PassageRange},
+        model::Verse,
+        transilation_engines::document::DocumentBible,
+    },
+    core::{
+        components::toast::show_error,
+        module_engine::{error::EngineResult, sword_engine::SwordEngine, sword_module::ModuleBook},
+        settings::ReadingSettings,
     },
-    sword_sys::*,
 };
 
+/// Where a chapter read lands in the list
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Placement {
+    Replace(Option<ChapterPosition>),
+    Append(ChapterPosition),
+    Prepend(ChapterPosition),
+}
+
+/// The chapter read in flight
+#[derive(Debug, Clone, Copy)]
+struct PendingRead {
+    generation: u64,
+    started: Instant,
+    placement: Placement,
+}
+
 pub struct BiblePage {
-    pub mgr_ptr: isize,
+    reader: ChapterReader,
+    loading: Option<PendingRead>,
     module: String,
-    verses: FactoryVecDeque<Verse>,
+    /// Set while a local file (OSIS, USFM, USX, ...) is read instead of `module`
+    document: Option<Arc<dyn DocumentBible>>,
+    reference: String,
+    options: ReaderOptions,
+    /// Book outline of the module or document, for chapter boundaries
+    structure: Vec<ModuleBook>,
+    /// Append and prepend neighbouring chapters while scrolling
+    continuous: bool,
+    /// First and last chapters in the list
+    shown: Option<(ChapterPosition, ChapterPosition)>,
+    /// Chapter at the top of the viewport
+    in_view: Option<ChapterPosition>,
+    scroller: gtk::ScrolledWindow,
+    /// Recycled rows: only the verses on screen are built
+    verses: TypedListView<VerseRow, gtk::NoSelection>,
+    row_context: RowContext,
+    /// Module whose words are paired by Strong's number in interlinear mode
+    paired_module: Option<String>,
+    /// Ids of the verses picked for copying
+    selected: HashSet<String>,
+    citation: CitationOptions,
+    toasts: adw::ToastOverlay,
+    /// The style manager outlives the page, so this is disconnected on shutdown
+    theme_handler: Option<gtk::glib::SignalHandlerId>,
 }
 
 #[derive(Debug)]
 pub enum StudyInput {
     LoadReference(String),
+    NavigateTo(String),
+    /// A cross-reference in a note was clicked
+    FollowReference(String),
     SelectStrong(String),
     SetModule(String),
+    SetDocument(Arc<dyn DocumentBible>),
+    SetInterlinear(bool),
+    SetInterlinearRows(InterlinearRows),
+    /// Appearance preferences changed
+    SetReading(ReadingSettings),
+    /// The light or dark theme was switched
+    ThemeChanged,
+    /// Pair with another Strong's-tagged module for a reverse interlinear
+    SetPairedModule(Option<String>),
+    /// Write a passage of the current module or document to a file
+    Export(ExportRequest, PathBuf),
+    SelectVerse(String, bool),
+    /// Put the selected verses on the clipboard as a citation
+    CopySelection,
+    ClearSelection,
+    SetCitationTemplate(String),
+    SetCitationVerseNumbers(bool),
+    /// Book outline of the current module or document
+    SetStructure(Vec<ModuleBook>),
+    SetContinuous(bool),
+    /// The view moved or its content changed size
+    Scrolled,
+}
+
+#[derive(Debug)]
+pub enum BiblePageOutput {
+    /// Find every verse tagged with this Strong's number
+    SearchLemma(String),
+    /// Open this OSIS reference, as the study page decides
+    FollowReference(String),
+    /// Continuous scrolling brought another chapter to the top of the view
+    ChapterInView(ChapterPosition),
+}
+
+#[derive(Debug)]
+pub enum BiblePageCommand {
+    /// A chapter read finished on the worker; `None` when a newer request
+    /// overtook it
+    ChapterLoaded(u64, EngineResult<Option<Vec<Verse>>>),
+    /// A passage export finished writing, or why it could not
+    Exported(Result<(), String>),
 }
 
 #[relm4::component(pub)]
-impl SimpleComponent for BiblePage {
-    type Init = (Arc<SwordEngine>, String, String);
+impl Component for BiblePage {
+    type Init = (Arc<SwordEngine>, String, String, ReadingSettings);
     type Input = StudyInput;
-    type Output = ();
+    type Output = BiblePageOutput;
+    type CommandOutput = BiblePageCommand;
 
     view! {
         adw::NavigationPage {
             #[wrap(Some)]
             set_child = &gtk::Box {
                 set_orientation: gtk::Orientation::Vertical,
-                gtk::ScrolledWindow {
+                #[local_ref]
+                toasts -> adw::ToastOverlay {
                     set_vexpand: true,
-                    set_hscrollbar_policy: gtk::PolicyType::Never,
-
-                    #[name="page_overlay"]
-                    gtk::Overlay {
-                        add_css_class: "page-overlay",
 
+                    #[wrap(Some)]
+                    set_child = &gtk::Overlay {
                         #[local_ref]
-                        verse_list -> gtk::Box {
-                            set_orientation: gtk::Orientation::Vertical,
-                            set_margin_all: 30,
-                            set_spacing: 8,
-                        }
-                    }
-                }
+                        scroller -> gtk::ScrolledWindow {
+                            set_hscrollbar_policy: gtk::PolicyType::Never,
+                            // The previous chapter stays dimmed until the next one arrives
+                            #[watch]
+                            set_opacity: if model.is_replacing() { 0.4 } else { 1.0 },
+
+                            #[local_ref]
+                            verse_list -> gtk::ListView {
+                                add_css_class: "verse-list",
+                            }
+                        },
+
+                        add_overlay = &adw::Spinner {
+                            set_halign: gtk::Align::Center,
+                            set_valign: gtk::Align::Center,
+                            set_width_request: 48,
+                            set_height_request: 48,
+                            #[watch]
+                            set_visible: model.is_replacing(),
+                        },
+                    },
+                },
+
+                gtk::ActionBar {
+                    #[watch]
+                    set_revealed: !model.selected.is_empty(),
+
+                    pack_start = &gtk::Label {
+                        #[watch]
+                        set_label: &match model.selected.len() {
+                            1 => "1 verse selected".to_string(),
+                            n => format!("{} verses selected", n),
+                        },
+                    },
+
+                    pack_end = &gtk::Button {
+                        set_label: "Copy",
+                        add_css_class: "suggested-action",
+                        connect_clicked => StudyInput::CopySelection,
+                    },
+
+                    pack_end = &gtk::MenuButton {
+                        set_icon_name: "emblem-system-symbolic",
+                        set_tooltip_text: Some("Citation Format"),
+
+                        #[wrap(Some)]
+                        set_popover = &gtk::Popover {
+                            #[wrap(Some)]
+                            set_child = &gtk::Box {
+                                set_orientation: gtk::Orientation::Vertical,
+                                set_spacing: 6,
+                                set_margin_all: 8,
+
+                                gtk::Entry {
+                                    set_text: &model.citation.template,
+                                    set_width_chars: 32,
+                                    connect_changed[sender] => move |entry| {
+                                        sender.input(StudyInput::SetCitationTemplate(entry.text().to_string()));
+                                    },
+                                },
+
+                                gtk::Label {
+                                    set_label: "{text}, {ref} and {module} are replaced",
+                                    add_css_class: "dimmed",
+                                    set_xalign: 0.0,
+                                },
+
+                                gtk::CheckButton {
+                                    set_label: Some("Verse Numbers"),
+                                    set_active: model.citation.verse_numbers,
+                                    connect_toggled[sender] => move |check| {
+                                        sender.input(StudyInput::SetCitationVerseNumbers(check.is_active()));
+                                    },
+                                },
+                            },
+                        },
+                    },
+
+                    pack_end = &gtk::Button {
+                        set_icon_name: "edit-clear-symbolic",
+                        set_tooltip_text: Some("Clear Selection"),
+                        connect_clicked => StudyInput::ClearSelection,
+                    },
+                },
             }
         }
     }
@@ -61,411 +235,410 @@ impl SimpleComponent for BiblePage {
         _root: Self::Root,
         sender: ComponentSender<Self>,
     ) -> ComponentParts<Self> {
-        let (engine, module, query) = init;
-        let verse_container = gtk::Box::new(gtk::Orientation::Vertical, 0);
-        let verses = FactoryVecDeque::builder().launch(verse_container).detach();
-        let mgr_ptr = engine.inner.lock().unwrap().mgr;
+        let (engine, module, query, reading) = init;
+        let verses = TypedListView::<VerseRow, gtk::NoSelection>::new();
+        let input = sender.input_sender().clone();
+        let on_output = Rc::new(move |output: VerseOutput| {
+            input.emit(match output {
+                VerseOutput::Navigate(osis_ref) => StudyInput::FollowReference(osis_ref),
+                VerseOutput::SearchLemma(strong) => StudyInput::SelectStrong(strong),
+                VerseOutput::Selected(osis_id, selected) => {
+                    StudyInput::SelectVerse(osis_id, selected)
+                }
+            })
+        });
 
-        let model = BiblePage {
-            mgr_ptr,
+        let mut model = BiblePage {
+            reader: ChapterReader::new(engine),
+            loading: None,
             module: module.clone(),
+            document: None,
+            reference: String::new(),
+            options: ReaderOptions {
+                palette: TextPalette::from_theme(),
+                interlinear: reading.interlinear,
+                ..ReaderOptions::default()
+            },
+            structure: Vec::new(),
+            continuous: false,
+            shown: None,
+            in_view: None,
+            scroller: gtk::ScrolledWindow::new(),
             verses,
+            row_context: RowContext {
+                on_output,
+                bound_rows: Rc::new(RefCell::new(Vec::new())),
+            },
+            paired_module: None,
+            selected: HashSet::new(),
+            citation: CitationOptions::default(),
+            toasts: adw::ToastOverlay::new(),
+            theme_handler: None,
         };
 
-        let verse_list = model.verses.widget();
+        model.options.apply(&reading);
+
+        // Pango draws the text colors, so they are read again for the new theme
+        model.theme_handler = Some(adw::StyleManager::default().connect_dark_notify({
+            let input = sender.input_sender().clone();
+            move |_| {
+                let _ = input.send(StudyInput::ThemeChanged);
+            }
+        }));
+
+        // Both moving and growing the list can bring a chapter edge into view
+        let adjustment = model.scroller.vadjustment();
+        adjustment.connect_value_changed({
+            let sender = sender.clone();
+            move |_| sender.input(StudyInput::Scrolled)
+        });
+        adjustment.connect_changed({
+            let sender = sender.clone();
+            move |_| sender.input(StudyInput::Scrolled)
+        });
+        let verse_list = &model.verses.view;
+        let scroller = &model.scroller.clone();
+        let toasts = &model.toasts.clone();
         let widgets = view_output!();
         sender.input(StudyInput::LoadReference(query));
 
         ComponentParts { model, widgets }
     }
 
-    fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>) {
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>, _root: &Self::Root) {
         match message {
-            StudyInput::LoadReference(refe) => self.load_reference(&refe),
-            StudyInput::SelectStrong(_) => {}
-            StudyInput::SetModule(name) => self.module = name,
-        }
-    }
-}
-
-impl BiblePage {
-    pub fn load_reference(&mut self, reference: &str) {
-        let verses = self.render_content_to_verses(reference);
-        let mut guard = self.verses.guard();
-        guard.clear();
-        for v in verses {
-            guard.push_back(v);
-        }
-    }
-
-    pub fn render_content_to_verses(&self, reference: &str) -> Vec<Verse> {
-        use std::ffi::CString;
-        let mut verses = Vec::new();
-
-        unsafe {
-            let key_ref = CString::new(reference).unwrap();
-            let module_name = CString::new(self.module.as_str()).unwrap();
-
-            let options = [
-                "Strong's Numbers",
-                "Morphological Tags",
-                "Footnotes",
-                "Cross-references",
-            ];
-            let on = CString::new("On").unwrap();
-
-            for opt in options {
-                let opt_c = CString::new(opt).unwrap();
-                org_crosswire_sword_SWMgr_setGlobalOption(
-                    self.mgr_ptr,
-                    opt_c.as_ptr(),
-                    on.as_ptr(),
-                );
+            StudyInput::LoadReference(refe) => self.load_reference(&refe, &sender),
+            StudyInput::NavigateTo(osis_ref) => {
+                self.load_reference(&Self::chapter_of_osis_ref(&osis_ref), &sender)
             }
-
-            let h_mod =
-                org_crosswire_sword_SWMgr_getModuleByName(self.mgr_ptr, module_name.as_ptr());
-            if h_mod == 0 {
-                return verses;
+            StudyInput::FollowReference(osis_ref) => {
+                let _ = sender.output(BiblePageOutput::FollowReference(osis_ref));
             }
-
-            org_crosswire_sword_SWModule_setKeyText(h_mod, key_ref.as_ptr());
-            let initial_key = self
-                .sword_ptr_to_string(org_crosswire_sword_SWModule_getKeyText(h_mod))
-                .unwrap_or_default();
-
-            let chapter_boundary = initial_key
-                .split(|c| c == ':' || c == '.')
-                .next()
-                .unwrap_or(&initial_key)
-                .to_string();
-
-            loop {
-                let key = match self
-                    .sword_ptr_to_string(org_crosswire_sword_SWModule_getKeyText(h_mod))
-                {
-                    Some(k) => k,
-                    None => break,
-                };
-                if !key.starts_with(&chapter_boundary) {
-                    break;
+            StudyInput::SelectStrong(strong) => {
+                let _ = sender.output(BiblePageOutput::SearchLemma(strong));
+            }
+            StudyInput::SetModule(name) => {
+                self.module = name;
+                self.document = None;
+            }
+            StudyInput::SetDocument(document) => self.document = Some(document),
+            StudyInput::SetInterlinear(interlinear) => {
+                if self.options.interlinear != interlinear {
+                    self.options.interlinear = interlinear;
+                    self.reload(&sender);
                 }
-
-                let raw_osis = match self
-                    .sword_ptr_to_string(org_crosswire_sword_SWModule_getRawEntry(h_mod))
-                {
-                    Some(s) => s,
-                    None => break,
-                };
-
-                // Debug print raw OSIS
-                println!("[+] {}\n", raw_osis);
-
-                let (mut words, notes) = self.parse_osis_content(&raw_osis);
-
-                // Apply grouping markers (brackets for Added, potential spans for Red)
-                self.apply_group_metadata(&mut words);
-
-                verses.push(Verse {
-                    osis_id: key.clone(),
-                    number: self.extract_verse_number(&key),
-                    words,
-                    notes,
-                    is_paragraph_start: raw_osis.contains("type=\"paragraph\"")
-                        || key.ends_with(":1"),
-                });
-
-                org_crosswire_sword_SWModule_next(h_mod);
-                if org_crosswire_sword_SWModule_popError(h_mod) != 0 {
-                    break;
+            }
+            StudyInput::SetInterlinearRows(rows) => {
+                if self.options.interlinear_rows != rows {
+                    self.options.interlinear_rows = rows;
+                    self.reload(&sender);
                 }
             }
-        }
-        verses
-    }
-
-    fn parse_osis_content(&self, osis: &str) -> (Vec<Word>, Vec<String>) {
-        use scraper::Html;
-        let fragment = Html::parse_fragment(osis);
-        let mut words = Vec::new();
-        let mut verse_notes = Vec::new();
-
-        self.walk_osis(
-            fragment.tree.root(),
-            &mut words,
-            &mut Vec::new(),
-            None,
-            false, // is_red (Jesus block)
-            false, // is_added (Theological status)
-            false, // is_italic (General style)
-            false, // is_inside_note
-        );
-
-        let note_selector = scraper::Selector::parse("note").unwrap();
-        let catch_selector = scraper::Selector::parse("catchWord").unwrap();
-
-        for note_node in fragment.select(&note_selector) {
-            let el = note_node.value();
-            let note_type = el.attr("type").unwrap_or("");
-            let osis_ref = el.attr("osisRef").unwrap_or("");
-            let full_note_text = note_node.text().collect::<Vec<_>>().join(" ");
-
-            if note_type == "crossReference" || !osis_ref.is_empty() {
-                let cross_ref_data = if !osis_ref.is_empty() {
-                    format!("[Cross-Ref: {}] {}", osis_ref, full_note_text)
+            StudyInput::SetReading(reading) => {
+                let mut options = self.options;
+                options.apply(&reading);
+                if self.options != options {
+                    self.options = options;
+                    self.reload(&sender);
+                }
+            }
+            StudyInput::ThemeChanged => {
+                self.options.palette = TextPalette::from_theme();
+                self.reload(&sender);
+            }
+            StudyInput::SetPairedModule(module) => {
+                if self.paired_module != module {
+                    self.paired_module = module;
+                    self.reload(&sender);
+                }
+            }
+            StudyInput::Export(request, path) => self.export(request, path, &sender),
+            StudyInput::SelectVerse(osis_id, selected) => {
+                if selected {
+                    self.selected.insert(osis_id);
                 } else {
-                    full_note_text.clone()
-                };
-                verse_notes.push(cross_ref_data);
-                continue;
+                    self.selected.remove(&osis_id);
+                }
             }
-
-            if let Some(catch_node) = note_node.select(&catch_selector).next() {
-                let clean_catch = catch_node
-                    .text()
-                    .collect::<String>()
-                    .replace("…", "")
-                    .to_lowercase()
-                    .trim()
-                    .to_string();
-
-                let mut attached = false;
-                for word in words.iter_mut() {
-                    if word.text.to_lowercase().contains(&clean_catch) {
-                        word.note = Some(full_note_text.clone());
-                        attached = true;
-                        break;
-                    }
+            StudyInput::CopySelection => {
+                let rows = self.rows();
+                let verses: Vec<(usize, &Verse)> = rows
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, row)| self.selected.contains(&row.verse.osis_id))
+                    .map(|(index, row)| (index, row.verse.as_ref()))
+                    .collect();
+                let text = Citation::format(&self.source_name(), &verses, &self.citation);
+                self.verses.view.clipboard().set_text(&text);
+            }
+            StudyInput::ClearSelection => {
+                for row in self.rows() {
+                    row.selected.set(false);
                 }
-                if !attached {
-                    verse_notes.push(full_note_text);
+                self.selected.clear();
+                // Rebind the rows on screen so their toggles follow
+                let len = self.verses.len();
+                self.verses.selection_model.items_changed(0, len, len);
+            }
+            StudyInput::SetCitationTemplate(template) => self.citation.template = template,
+            StudyInput::SetCitationVerseNumbers(verse_numbers) => {
+                self.citation.verse_numbers = verse_numbers;
+            }
+            StudyInput::SetStructure(structure) => self.structure = structure,
+            StudyInput::SetContinuous(continuous) => {
+                if self.continuous != continuous {
+                    self.continuous = continuous;
+                    // Leaving the mode keeps only the chapter being read
+                    if !continuous && self.shown.is_some_and(|(first, last)| first != last) {
+                        self.reload(&sender);
+                    } else {
+                        self.follow_scroll(&sender);
+                    }
                 }
-            } else {
-                verse_notes.push(full_note_text);
             }
+            StudyInput::Scrolled => self.follow_scroll(&sender),
         }
-        (words, verse_notes)
     }
 
-    fn walk_osis(
-        &self,
-        node: NodeRef<scraper::node::Node>,
-        words: &mut Vec<Word>,
-        _notes: &mut Vec<String>,
-        parent_lex: Option<LexicalInfo>,
-        is_red: bool,
-        is_added: bool,
-        is_italic: bool,
-        is_inside_note: bool,
+    fn update_cmd(
+        &mut self,
+        message: Self::CommandOutput,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
     ) {
-        use scraper::node::Node;
-
-        match node.value() {
-            Node::Text(t) => {
-                if !is_inside_note {
-                    let text = t.text.trim();
-                    if !text.is_empty() {
-                        for piece in text.split_whitespace() {
-                            words.push(Word {
-                                text: piece.to_string(),
-                                // This determines if we see "Plain" or "Added" in your debug
-                                style: if is_added {
-                                    SegmentStyle::Added
-                                } else {
-                                    SegmentStyle::Plain
-                                },
-                                is_red,
-                                is_italic,
-                                is_bold_text: false,
-                                lex: parent_lex.clone(),
-                                note: None,
-                                is_first_in_group: false,
-                                is_last_in_group: false,
-                                is_punctuation: piece.chars().all(|c| c.is_ascii_punctuation()),
-                            });
-                        }
-                    }
+        match message {
+            BiblePageCommand::ChapterLoaded(generation, verses) => {
+                let Some(pending) = self.loading else {
+                    return;
+                };
+                // Stale reads are dropped; only the newest request is shown
+                if generation != pending.generation {
+                    return;
                 }
-            }
-            Node::Element(el) => {
-                // Inheritance: start with the parent's state
-                let mut current_lex = parent_lex.clone();
-                let mut active_red = is_red;
-                let mut active_added = is_added;
-                let mut active_italic = is_italic;
-                let mut active_note = is_inside_note;
-
-                match el.name() {
-                    "w" => {
-                        let raw_lemma = el.attr("lemma").unwrap_or("");
-                        let raw_morph = el.attr("morph").unwrap_or("");
-
-                        let strongs: Vec<String> = raw_lemma
-                            .split_whitespace()
-                            .filter(|s| s.starts_with("strong:"))
-                            .map(|s| s.trim_start_matches("strong:").to_string())
-                            .collect();
-
-                        let tr_lemma = raw_lemma
-                            .split_whitespace()
-                            .find(|s| s.starts_with("lemma.TR:"))
-                            .map(|s| s.trim_start_matches("lemma.TR:").to_string());
-
-                        current_lex = Some(LexicalInfo {
-                            strongs,
-                            lemma: tr_lemma,
-                            morph: Some(self.decode_morph(raw_morph)),
-                            ..Default::default()
-                        });
-                    }
-                    "q" if el.attr("who") == Some("Jesus") => {
-                        active_red = true;
-                    }
-                    "transChange" if el.attr("type") == Some("added") => {
-                        active_added = true;
-                        //active_italic = true;
+                let verses = match verses {
+                    Ok(Some(verses)) => verses,
+                    Ok(None) => return,
+                    Err(e) => {
+                        self.loading = None;
+                        show_error(&self.toasts, &e);
+                        return;
                     }
-                    "hi" if el.attr("type") == Some("italic") => {
-                        active_italic = true;
-                    }
-                    "note" => {
-                        active_note = true;
-                    }
-                    _ => {}
-                }
-
-                // Recurse into children with the UPDATED state
-                for child in node.children() {
-                    self.walk_osis(
-                        child,
-                        words,
-                        _notes,
-                        current_lex.clone(),
-                        active_red,
-                        active_added,
-                        active_italic,
-                        active_note,
-                    );
-                }
-            }
-            _ => {
-                // For non-elements/non-text, just pass the state through
-                for child in node.children() {
-                    self.walk_osis(
-                        child,
-                        words,
-                        _notes,
-                        parent_lex.clone(),
-                        is_red,
-                        is_added,
-                        is_italic,
-                        is_inside_note,
-                    );
-                }
+                };
+                self.loading = None;
+                let count = verses.len();
+                self.place_chapter(verses, pending.placement);
+                log::debug!(
+                    "Loaded {:?} ({} verses) in {:?}",
+                    pending.placement,
+                    count,
+                    pending.started.elapsed()
+                );
+                // A short chapter may leave the next edge in view already
+                self.follow_scroll(&sender);
             }
+            BiblePageCommand::Exported(Err(message)) => show_error(&self.toasts, &message),
+            BiblePageCommand::Exported(Ok(())) => {}
         }
     }
 
-    fn apply_group_metadata(&self, words: &mut [Word]) {
-        let len = words.len();
-        if len == 0 {
-            return;
+    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {
+        if let Some(handler) = self.theme_handler.take() {
+            adw::StyleManager::default().disconnect(handler);
         }
+    }
+}
 
-        for i in 0..len {
-            // 1. Check for Added words (Theological status)
-            if words[i].style == SegmentStyle::Added {
-                let is_prev_added = if i > 0 {
-                    words[i - 1].style == SegmentStyle::Added
-                } else {
-                    false
-                };
-                let is_next_added = if i < len - 1 {
-                    words[i + 1].style == SegmentStyle::Added
-                } else {
-                    false
-                };
+impl BiblePage {
+    /// Reads the chapter on a worker thread; the verses arrive as
+    /// `BiblePageCommand::ChapterLoaded`. Asking for another chapter before
+    /// then cancels this read.
+    pub fn load_reference(&mut self, reference: &str, sender: &ComponentSender<Self>) {
+        self.reference = reference.to_string();
+        let position = ChapterPosition::find(reference, &self.structure);
+        self.read(reference, Placement::Replace(position), sender);
+    }
+
+    fn read(&mut self, reference: &str, placement: Placement, sender: &ComponentSender<Self>) {
+        let request = ChapterRequest {
+            generation: self.reader.begin(),
+            reference: reference.to_string(),
+            module: self.module.clone(),
+            document: self.document.clone(),
+            paired_module: self
+                .paired_module
+                .clone()
+                .filter(|_| self.options.interlinear),
+        };
+        self.loading = Some(PendingRead {
+            generation: request.generation,
+            started: Instant::now(),
+            placement,
+        });
+
+        let reader = self.reader.clone();
+        sender.spawn_oneshot_command(move || {
+            BiblePageCommand::ChapterLoaded(request.generation, reader.read(&request))
+        });
+    }
 
-                if !is_prev_added {
-                    words[i].is_first_in_group = true;
+    fn place_chapter(&mut self, verses: Vec<Verse>, placement: Placement) {
+        let chapter = match placement {
+            Placement::Replace(chapter) => chapter,
+            Placement::Append(chapter) | Placement::Prepend(chapter) => Some(chapter),
+        };
+        let rows = verses
+            .into_iter()
+            .map(|v| VerseRow::new(v, self.options, chapter, self.row_context.clone()));
+
+        match placement {
+            Placement::Replace(chapter) => {
+                self.selected.clear();
+                self.verses.clear();
+                self.verses.extend_from_iter(rows);
+                self.shown = chapter.map(|c| (c, c));
+                self.in_view = chapter;
+                if !self.verses.is_empty() {
+                    self.verses
+                        .view
+                        .scroll_to(0, gtk::ListScrollFlags::NONE, None);
                 }
-                if !is_next_added {
-                    words[i].is_last_in_group = true;
+            }
+            Placement::Append(chapter) => {
+                self.verses.extend_from_iter(rows);
+                if let Some((_, last)) = self.shown.as_mut() {
+                    *last = chapter;
                 }
             }
-            // 2. Check for Jesus words (is_red) ONLY if not already marked by an Added group
-            else if words[i].is_red {
-                let is_prev_red = if i > 0 { words[i - 1].is_red } else { false };
-                let is_next_red = if i < len - 1 {
-                    words[i + 1].is_red
-                } else {
-                    false
-                };
-
-                if !is_prev_red {
-                    words[i].is_first_in_group = true;
+            Placement::Prepend(chapter) => {
+                // The list view keeps its anchor row, so the reader stays put
+                for (index, row) in rows.enumerate() {
+                    self.verses.insert(index as u32, row);
                 }
-                if !is_next_red {
-                    words[i].is_last_in_group = true;
+                if let Some((first, _)) = self.shown.as_mut() {
+                    *first = chapter;
                 }
             }
         }
     }
 
-    fn extract_verse_number(&self, key: &str) -> i32 {
-        key.split(|c| c == '.' || c == ':')
-            .last()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(0)
+    fn is_replacing(&self) -> bool {
+        self.loading
+            .is_some_and(|pending| matches!(pending.placement, Placement::Replace(_)))
     }
 
-    fn decode_morph(&self, morph: &str) -> String {
-        let code = morph.split(':').last().unwrap_or(morph);
-        let mut parts = Vec::new();
-
-        let chars: Vec<char> = code.chars().collect();
-        if chars.is_empty() {
-            return code.to_string();
+    /// In continuous mode, reads the neighbouring chapter once the view nears
+    /// either end of the list, and reports the chapter at the top of the view.
+    fn follow_scroll(&mut self, sender: &ComponentSender<Self>) {
+        if !self.continuous {
+            return;
         }
 
-        // First char: Part of Speech
-        match chars[0] {
-            'N' => parts.push("Noun"),
-            'V' => parts.push("Verb"),
-            'A' => parts.push("Adjective"),
-            'R' => parts.push("Pronoun"),
-            'D' => parts.push("Adverb"),
-            'P' => parts.push("Preposition"),
-            'C' => parts.push("Conjunction"),
-            'I' => parts.push("Interjection"),
-            _ => {}
+        let adjustment = self.scroller.vadjustment();
+        let (value, page) = (adjustment.value(), adjustment.page_size());
+        if self.loading.is_none()
+            && let Some((first, last)) = self.shown
+        {
+            let neighbour = if value + page * 2.0 >= adjustment.upper() {
+                last.next(&self.structure).map(Placement::Append)
+            } else if value <= page {
+                first.previous(&self.structure).map(Placement::Prepend)
+            } else {
+                None
+            };
+            if let Some(placement @ (Placement::Append(chapter) | Placement::Prepend(chapter))) =
+                neighbour
+                && let Some(reference) = chapter.reference(&self.structure)
+            {
+                self.read(&reference, placement, sender);
+            }
         }
 
-        // This is a simplified logic - Robinson's codes are positional.
-        // Example: N-DSM -> Noun, Dative, Singular, Masculine
-        for c in chars.iter().skip(1) {
-            match c {
-                '-' => continue,
-                'N' => parts.push("Nominative"),
-                'G' => parts.push("Genitive"),
-                'D' => parts.push("Dative"),
-                'A' => parts.push("Accusative"),
-                'S' => parts.push("Singular"),
-                'P' => parts.push("Plural"),
-                'M' => parts.push("Masculine"),
-                'F' => parts.push("Feminine"),
-                'N' if parts.contains(&"Noun") => parts.push("Neuter"),
-                _ => {}
+        if let Some(chapter) = self.chapter_in_view()
+            && self.in_view != Some(chapter)
+        {
+            self.in_view = Some(chapter);
+            if let Some(reference) = chapter.reference(&self.structure) {
+                self.reference = reference;
             }
+            let _ = sender.output(BiblePageOutput::ChapterInView(chapter));
+        }
+    }
+
+    /// Chapter of the highest bound row still reaching into the viewport
+    fn chapter_in_view(&self) -> Option<ChapterPosition> {
+        self.row_context
+            .bound_rows
+            .borrow()
+            .iter()
+            .filter_map(|(root, chapter)| {
+                let bounds = root.compute_bounds(&self.scroller)?;
+                (bounds.y() + bounds.height() > 0.0).then_some((bounds.y(), (*chapter)?))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, chapter)| chapter)
+    }
+
+    /// Rows of the loaded chapter, in reading order
+    fn rows(&self) -> Vec<VerseRow> {
+        (0..self.verses.len())
+            .filter_map(|index| self.verses.get(index))
+            .map(|item| item.borrow().clone())
+            .collect()
+    }
+
+    /// Name of the module or document being read
+    fn source_name(&self) -> String {
+        match self.document.as_ref() {
+            Some(document) => document.name(),
+            None => self.module.clone(),
         }
+    }
 
-        parts.join(", ")
+    /// Reads and writes the passage on a worker thread; failures come back
+    /// as `BiblePageCommand::Exported`
+    fn export(&self, request: ExportRequest, path: PathBuf, sender: &ComponentSender<Self>) {
+        let Some(range) = PassageRange::parse(&request.range) else {
+            let message = format!("Cannot export “{}”: not a reference", request.range);
+            show_error(&self.toasts, &message);
+            return;
+        };
+        let reader = self.reader.clone();
+        let module = self.module.clone();
+        let document = self.document.clone();
+        sender.spawn_oneshot_command(move || {
+            let passage =
+                match reader.read_passage(&module, document.as_ref(), &range, &request.range) {
+                    Ok(passage) => passage,
+                    Err(e) => return BiblePageCommand::Exported(Err(e.to_string())),
+                };
+            let text = Exporter::export(&passage, request.format, &request.options);
+            BiblePageCommand::Exported(match std::fs::write(&path, text) {
+                Ok(()) => {
+                    log::info!("Exported {} to {}", request.range, path.display());
+                    Ok(())
+                }
+                Err(e) => Err(format!("Could not write {}: {}", path.display(), e)),
+            })
+        });
     }
 
-    unsafe fn sword_ptr_to_string(&self, ptr: *const std::os::raw::c_char) -> Option<String> {
-        if ptr.is_null() {
-            return None;
+    fn reload(&mut self, sender: &ComponentSender<Self>) {
+        let reference = self.reference.clone();
+        self.load_reference(&reference, sender);
+    }
+
+    /// "Job.38.4-Job.38.7" -> "Job.38" and "John 3:16" -> "John 3", so
+    /// navigation opens the whole chapter.
+    fn chapter_of_osis_ref(osis_ref: &str) -> String {
+        let start = osis_ref.split('-').next().unwrap_or(osis_ref);
+        if let Some((chapter, _verse)) = start.split_once(':') {
+            return chapter.to_string();
+        }
+        let parts: Vec<&str> = start.split('.').collect();
+        if parts.len() > 2 {
+            parts[..2].join(".")
+        } else {
+            start.to_string()
         }
-        Some(unsafe { std::ffi::CStr::from_ptr(ptr).to_string_lossy().into_owned() })
     }
 }
@@ -0,0 +1,130 @@
+use adw::prelude::*;
+use relm4::prelude::*;
+
+use crate::features::bible::model::{Note, NoteKind};
+
+impl Note {
+    /// Superscript marker drawn inline at the note's anchor. Clicking it opens
+    /// a popover with the note text, or the referenced verses for
+    /// cross-references; clicking a reference hands its osisRef to `on_navigate`.
+    pub fn build_marker<F>(&self, on_navigate: F) -> gtk::Widget
+    where
+        F: Fn(String) + Clone + 'static,
+    {
+        let popover = self.build_popover(on_navigate);
+
+        let marker_label = gtk::Label::builder().use_markup(true).build();
+        marker_label.set_markup(&format!(
+            "<sup>{}</sup>",
+            gtk::glib::markup_escape_text(&self.marker)
+        ));
+
+        let button = gtk::MenuButton::builder()
+            .popover(&popover)
+            .valign(gtk::Align::Start)
+            .css_classes(match self.kind {
+                NoteKind::Footnote => ["flat", "note-marker"],
+                NoteKind::CrossReference => ["flat", "crossref-marker"],
+            })
+            .build();
+        button.set_child(Some(&marker_label));
+
+        button.upcast()
+    }
+
+    /// The note text and referenced verses, unparented
+    pub fn build_popover<F>(&self, on_navigate: F) -> gtk::Popover
+    where
+        F: Fn(String) + Clone + 'static,
+    {
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(8)
+            .margin_top(8)
+            .margin_bottom(8)
+            .margin_start(8)
+            .margin_end(8)
+            .build();
+
+        let popover = gtk::Popover::builder()
+            .child(&content)
+            .css_classes(["note-popover"])
+            .build();
+
+        let show_text = self.kind == NoteKind::Footnote || self.resolved.is_empty();
+        if show_text && !self.text.is_empty() {
+            let text_label = gtk::Label::builder()
+                .label(&self.text)
+                .wrap(true)
+                .max_width_chars(48)
+                .xalign(0.0)
+                .selectable(true)
+                .css_classes(["note-text"])
+                .build();
+            content.append(&text_label);
+        }
+
+        if self.resolved.is_empty() {
+            for target in &self.targets {
+                content.append(&Self::reference_button(target, &popover, on_navigate.clone()));
+            }
+        } else {
+            for reference in &self.resolved {
+                content.append(&Self::reference_button(
+                    &reference.osis_ref,
+                    &popover,
+                    on_navigate.clone(),
+                ));
+
+                let verse_label = gtk::Label::builder()
+                    .label(&reference.text)
+                    .wrap(true)
+                    .max_width_chars(48)
+                    .xalign(0.0)
+                    .css_classes(["note-reference-text"])
+                    .build();
+                content.append(&verse_label);
+            }
+        }
+
+        popover
+    }
+
+    fn reference_button<F>(osis_ref: &str, popover: &gtk::Popover, on_navigate: F) -> gtk::Button
+    where
+        F: Fn(String) + 'static,
+    {
+        let button = gtk::Button::builder()
+            .label(Self::display_reference(osis_ref))
+            .halign(gtk::Align::Start)
+            .css_classes(["flat", "note-reference"])
+            .build();
+
+        let popover = popover.clone();
+        let osis_ref = osis_ref.to_string();
+        button.connect_clicked(move |_| {
+            popover.popdown();
+            on_navigate(osis_ref.clone());
+        });
+
+        button
+    }
+
+    /// "Job.38.4-Job.38.7" -> "Job 38:4-Job 38:7"
+    fn display_reference(osis_ref: &str) -> String {
+        osis_ref
+            .split('-')
+            .map(|part| {
+                let mut pieces = part.splitn(3, '.');
+                match (pieces.next(), pieces.next(), pieces.next()) {
+                    (Some(book), Some(chapter), Some(verse)) => {
+                        format!("{} {}:{}", book, chapter, verse)
+                    }
+                    (Some(book), Some(chapter), None) => format!("{} {}", book, chapter),
+                    _ => part.to_string(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+}
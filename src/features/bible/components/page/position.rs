@@ -0,0 +1,176 @@
+use crate::features::{
+    bible::transilation_engines::usfm_engine::books::resolve_book_name,
+    core::module_engine::sword_module::ModuleBook,
+};
+
+/// A chapter as indexes into a module's book outline, the shape
+/// `SwordEngine::get_bible_structure` returns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ChapterPosition {
+    pub book: usize,
+    pub chapter: usize,
+}
+
+impl ChapterPosition {
+    /// Locates "John 3", "1 John 3:16", "John.3" or "1John.3.16" in the outline
+    pub fn find(reference: &str, structure: &[ModuleBook]) -> Option<Self> {
+        let start = reference.split('-').next()?.trim();
+        let (book, chapter) = match start.rsplit_once(' ') {
+            Some((book, position)) => (book, position.split(':').next()?),
+            None => {
+                let mut parts = start.split('.');
+                (parts.next()?, parts.next()?)
+            }
+        };
+        let number: i32 = chapter.parse().ok()?;
+
+        let wanted = Self::book_key(book);
+        let book = structure
+            .iter()
+            .position(|b| Self::book_key(&b.name) == wanted)?;
+        let chapter = structure[book]
+            .chapters
+            .iter()
+            .position(|c| c.number == number)?;
+        Some(Self { book, chapter })
+    }
+
+    /// The following chapter, crossing into the next book after the last one
+    pub fn next(self, structure: &[ModuleBook]) -> Option<Self> {
+        let book = structure.get(self.book)?;
+        if self.chapter + 1 < book.chapters.len() {
+            return Some(Self {
+                chapter: self.chapter + 1,
+                ..self
+            });
+        }
+        (self.book + 1..structure.len())
+            .find(|&b| !structure[b].chapters.is_empty())
+            .map(|book| Self { book, chapter: 0 })
+    }
+
+    /// The preceding chapter, crossing back to the end of the previous book
+    pub fn previous(self, structure: &[ModuleBook]) -> Option<Self> {
+        if self.chapter > 0 {
+            return Some(Self {
+                chapter: self.chapter - 1,
+                ..self
+            });
+        }
+        (0..self.book.min(structure.len()))
+            .rev()
+            .find(|&b| !structure[b].chapters.is_empty())
+            .map(|book| Self {
+                book,
+                chapter: structure[book].chapters.len() - 1,
+            })
+    }
+
+    /// "Genesis 2", as the Study page builds references
+    pub fn reference(self, structure: &[ModuleBook]) -> Option<String> {
+        let book = structure.get(self.book)?;
+        let chapter = book.chapters.get(self.chapter)?;
+        Some(format!("{} {}", book.name, chapter.number))
+    }
+
+    /// "Gen.2", the OSIS form BibleSync and SWORD keys exchange
+    pub fn osis_ref(self, structure: &[ModuleBook]) -> Option<String> {
+        let book = structure.get(self.book)?;
+        let chapter = book.chapters.get(self.chapter)?;
+        let osis = resolve_book_name(&book.name)
+            .map(|(osis, _)| osis.to_string())
+            .unwrap_or_else(|| book.name.replace(' ', ""));
+        Some(format!("{}.{}", osis, chapter.number))
+    }
+
+    /// Book names compare by OSIS id, so "1John" matches "I John"
+    fn book_key(name: &str) -> String {
+        resolve_book_name(name)
+            .map(|(osis, _)| osis.to_string())
+            .unwrap_or_else(|| name.replace(' ', "").to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::core::module_engine::sword_module::ModuleChapter;
+
+    fn outline() -> Vec<ModuleBook> {
+        [("Malachi", 4), ("Matthew", 28), ("1 John", 5)]
+            .into_iter()
+            .map(|(name, chapters)| ModuleBook {
+                name: name.to_string(),
+                chapters: (1..=chapters)
+                    .map(|number| ModuleChapter {
+                        number,
+                        verse_count: 20,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_references_in_either_form() {
+        let structure = outline();
+        let expected = Some(ChapterPosition {
+            book: 2,
+            chapter: 2,
+        });
+        assert_eq!(ChapterPosition::find("1 John 3", &structure), expected);
+        assert_eq!(ChapterPosition::find("1John.3.16", &structure), expected);
+        assert_eq!(
+            ChapterPosition::find("1 John 3:16-18", &structure),
+            expected
+        );
+        assert_eq!(ChapterPosition::find("Jude 1", &structure), None);
+    }
+
+    #[test]
+    fn builds_osis_references() {
+        let structure = outline();
+        let position = ChapterPosition {
+            book: 2,
+            chapter: 2,
+        };
+        assert_eq!(position.osis_ref(&structure).as_deref(), Some("1John.3"));
+        assert_eq!(ChapterPosition::find("1John.3", &structure), Some(position));
+    }
+
+    #[test]
+    fn steps_across_book_boundaries() {
+        let structure = outline();
+        let last_of_malachi = ChapterPosition {
+            book: 0,
+            chapter: 3,
+        };
+        let first_of_matthew = ChapterPosition {
+            book: 1,
+            chapter: 0,
+        };
+
+        assert_eq!(last_of_malachi.next(&structure), Some(first_of_matthew));
+        assert_eq!(first_of_matthew.previous(&structure), Some(last_of_malachi));
+        assert_eq!(
+            ChapterPosition {
+                book: 0,
+                chapter: 0
+            }
+            .previous(&structure),
+            None
+        );
+        assert_eq!(
+            ChapterPosition {
+                book: 2,
+                chapter: 4
+            }
+            .next(&structure),
+            None
+        );
+        assert_eq!(
+            first_of_matthew.reference(&structure).as_deref(),
+            Some("Matthew 1")
+        );
+    }
+}
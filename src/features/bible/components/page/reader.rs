@@ -0,0 +1,980 @@
+use ego_tree::NodeRef;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::features::{
+    bible::{
+        export::{Passage, PassageRange},
+        model::{
+            BlockKind, Book, Chapter, LexicalInfo, Note, NoteKind, ResolvedReference, SegmentStyle,
+            StrongsDefinition, Verse, VerseBlock, Word,
+        },
+        transilation_engines::document::DocumentBible,
+    },
+    core::module_engine::{
+        error::EngineResult,
+        sword::Module,
+        sword_engine::{SwordEngine, SwordInner},
+        sword_module::StrongsLexicons,
+        text_options::LETTER_OPTIONS,
+    },
+};
+
+/// Upper bound on verses looked up for a single note, so a chapter-wide
+/// cross-reference does not stall rendering.
+const MAX_RESOLVED_VERSES: usize = 12;
+
+/// One chapter to read, with everything that decides how it is read
+#[derive(Debug, Clone)]
+pub struct ChapterRequest {
+    /// Issued by `ChapterReader::begin`; a newer one cancels this request
+    pub generation: u64,
+    pub reference: String,
+    pub module: String,
+    /// Set while a local file is read instead of `module`
+    pub document: Option<Arc<dyn DocumentBible>>,
+    /// Module whose words are paired by Strong's number, in interlinear mode
+    pub paired_module: Option<String>,
+}
+
+/// Reads chapters from SWORD modules and documents into the shared model.
+/// It holds no widgets, so the page runs it on a worker thread; clones share
+/// the lexicon cache and the request counter.
+#[derive(Debug, Clone)]
+pub struct ChapterReader {
+    engine: Arc<SwordEngine>,
+    /// Lexicon lookups by Strong's number; `None` when no lexicon has it
+    definitions: Arc<Mutex<HashMap<String, Option<StrongsDefinition>>>>,
+    /// Generation of the newest request
+    latest: Arc<AtomicU64>,
+}
+
+/// One verse as copied out of SWORD, parsed once the engine lock is released
+struct RawVerse {
+    key: String,
+    osis: String,
+    /// Preverse headings, already stripped of markup
+    headings: Vec<String>,
+}
+
+/// What the user switched off among a module's options, copied out of the
+/// engine so verses are filtered without holding its lock. SWORD's option
+/// filters act on rendered text, not the raw entries parsed here, so only
+/// the letter filters are run through SWORD.
+struct TextFilters {
+    headings: bool,
+    footnotes: bool,
+    cross_references: bool,
+    red: bool,
+    strongs: bool,
+    morph: bool,
+    lemmas: bool,
+    /// Letter options whose filter changes text at the picked value
+    letters: Vec<&'static str>,
+}
+
+impl TextFilters {
+    fn new(inner: &SwordInner, options: &[String]) -> Self {
+        let off =
+            |option: &str| options.iter().any(|o| o == option) && !inner.text_options.is_on(option);
+        Self {
+            headings: off("Headings"),
+            footnotes: off("Footnotes"),
+            cross_references: off("Cross-references"),
+            red: off("Words of Christ in Red"),
+            strongs: off("Strong's Numbers"),
+            morph: off("Morphological Tags"),
+            lemmas: off("Lemmas"),
+            letters: LETTER_OPTIONS
+                .into_iter()
+                .filter(|o| options.iter().any(|option| option == o))
+                .filter(|o| inner.text_options.rewrites(o))
+                .collect(),
+        }
+    }
+
+    /// Drops what is switched off, except the letter options
+    fn apply(&self, words: &mut [Word], notes: &mut Vec<Note>, blocks: &mut Vec<VerseBlock>) {
+        if self.headings {
+            blocks.retain(|b| !matches!(b.kind, BlockKind::Heading(_)));
+        }
+        notes.retain(|note| match note.kind {
+            NoteKind::Footnote => !self.footnotes,
+            NoteKind::CrossReference => !self.cross_references,
+        });
+
+        for word in words {
+            if self.red {
+                word.is_red = false;
+                if word.style == SegmentStyle::RedLetter {
+                    word.style = SegmentStyle::Plain;
+                }
+            }
+            if let Some(lex) = word.lex.as_mut() {
+                if self.strongs {
+                    lex.strongs.clear();
+                }
+                if self.morph {
+                    lex.morph = None;
+                }
+                if self.lemmas {
+                    lex.lemma = None;
+                }
+            }
+        }
+    }
+
+    /// Runs the word through SWORD's filter for each letter option
+    fn filter_letters(&self, inner: &SwordInner, word: &mut Word) {
+        for option in &self.letters {
+            match inner.mgr.filter_text(option, &word.text) {
+                Ok(text) => word.text = text,
+                Err(e) => log::warn!("{} could not filter {:?}: {}", option, word.text, e),
+            }
+        }
+    }
+}
+
+impl ChapterReader {
+    pub fn new(engine: Arc<SwordEngine>) -> Self {
+        Self {
+            engine,
+            definitions: Arc::default(),
+            latest: Arc::default(),
+        }
+    }
+
+    /// Starts a new request. Reads still running for older ones stop at
+    /// their next verse.
+    pub fn begin(&self) -> u64 {
+        self.latest.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    pub fn is_stale(&self, generation: u64) -> bool {
+        self.latest.load(Ordering::SeqCst) != generation
+    }
+
+    /// Reads, pairs and annotates a chapter; `None` if the request was
+    /// overtaken by a newer one.
+    pub fn read(&self, request: &ChapterRequest) -> EngineResult<Option<Vec<Verse>>> {
+        let generation = Some(request.generation);
+        let mut verses = match request.document.as_ref() {
+            Some(document) => document.read_chapter(&request.reference),
+            None => match self.read_module(&request.module, &request.reference, generation)? {
+                Some(verses) => verses,
+                None => return Ok(None),
+            },
+        };
+        if let Some(paired_module) = request.paired_module.as_deref() {
+            let Some(paired) = self.read_module(paired_module, &request.reference, generation)?
+            else {
+                return Ok(None);
+            };
+            Self::pair_verses(&mut verses, &paired);
+        }
+        if self.is_stale(request.generation) {
+            return Ok(None);
+        }
+        self.attach_definitions(&mut verses);
+        Ok(Some(verses))
+    }
+
+    /// Reads one chapter of `module` on the calling thread
+    pub fn render_content_to_verses(
+        &self,
+        module: &str,
+        reference: &str,
+    ) -> EngineResult<Vec<Verse>> {
+        Ok(self
+            .read_module(module, reference, None)?
+            .unwrap_or_default())
+    }
+
+    /// Reads every verse of `range` from `module`, or from `document` when
+    /// a local file is open instead
+    pub fn read_passage(
+        &self,
+        module: &str,
+        document: Option<&Arc<dyn DocumentBible>>,
+        range: &PassageRange,
+        reference: &str,
+    ) -> EngineResult<Passage> {
+        let (book_id, book_name) = range.osis_book();
+        let mut book = Book::new(&book_id, &book_name);
+
+        for number in range.chapters() {
+            let chapter_ref = range.chapter_reference(number);
+            let verses = match document {
+                Some(document) => document.read_chapter(&chapter_ref),
+                None => self.render_content_to_verses(module, &chapter_ref)?,
+            };
+            let mut chapter = Chapter::new(&book_id, number);
+            chapter.verses = verses
+                .into_iter()
+                .filter(|v| range.contains(number, v.number))
+                .collect();
+            if !chapter.verses.is_empty() {
+                book.chapters.push(chapter);
+            }
+        }
+
+        Ok(Passage {
+            reference: reference.to_string(),
+            module: document.map_or_else(|| module.to_string(), |document| document.name()),
+            book,
+        })
+    }
+
+    /// Reverse interlinear: under each word, the words of the same verse in
+    /// the paired module that carry one of its Strong's numbers.
+    fn pair_verses(verses: &mut [Verse], paired: &[Verse]) {
+        let by_number: HashMap<i32, &Verse> = paired.iter().map(|v| (v.number, v)).collect();
+
+        for verse in verses.iter_mut() {
+            let Some(paired_verse) = by_number.get(&verse.number) else {
+                continue;
+            };
+
+            let mut surface_by_strong: HashMap<String, Vec<&str>> = HashMap::new();
+            for word in &paired_verse.words {
+                let Some(lex) = word.lex.as_ref() else {
+                    continue;
+                };
+                for strong in &lex.strongs {
+                    let texts = surface_by_strong
+                        .entry(normalize_strongs(strong))
+                        .or_default();
+                    if !texts.contains(&word.text.as_str()) {
+                        texts.push(&word.text);
+                    }
+                }
+            }
+
+            for word in verse.words.iter_mut() {
+                let Some(lex) = word.lex.as_mut() else {
+                    continue;
+                };
+                let texts: Vec<&str> = lex
+                    .strongs
+                    .iter()
+                    .filter_map(|s| surface_by_strong.get(&normalize_strongs(s)))
+                    .flatten()
+                    .copied()
+                    .collect();
+                lex.paired = (!texts.is_empty()).then(|| texts.join(" "));
+            }
+        }
+    }
+
+    /// Fills every tagged word's lexicon entries, looking each Strong's number
+    /// up at most once per page.
+    fn attach_definitions(&self, verses: &mut [Verse]) {
+        // The cache only ever gains complete entries, so a poisoned lock is harmless
+        let mut definitions = self
+            .definitions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Found at the first number not cached yet, then kept for the chapter
+        let mut lexicons: Option<StrongsLexicons> = None;
+        for word in verses.iter_mut().flat_map(|v| v.words.iter_mut()) {
+            let Some(lex) = word.lex.as_mut() else {
+                continue;
+            };
+            for strong in &lex.strongs {
+                let definition = definitions.entry(strong.clone()).or_insert_with(|| {
+                    let lexicons = lexicons.get_or_insert_with(|| {
+                        self.engine.strongs_lexicons().unwrap_or_else(|e| {
+                            log::warn!("Lexicons could not be listed: {}", e);
+                            StrongsLexicons::default()
+                        })
+                    });
+                    let entry = self
+                        .engine
+                        .lookup_strongs_in(lexicons, strong)
+                        .unwrap_or_else(|e| {
+                            log::warn!("Lexicon lookup failed: {}", e);
+                            None
+                        });
+                    entry.map(|entry| StrongsDefinition {
+                        strong: strong.clone(),
+                        lexicon: entry.module,
+                        text: entry.text,
+                    })
+                });
+                if let Some(definition) = definition {
+                    lex.definitions.push(definition.clone());
+                }
+            }
+            if lex.gloss.is_none() {
+                lex.gloss = lex.definitions.iter().find_map(|d| short_gloss(&d.text));
+            }
+        }
+    }
+
+    /// Walks one chapter of `module`. With a `generation`, the walk stops
+    /// and returns `None` as soon as a newer request has begun.
+    fn read_module(
+        &self,
+        module: &str,
+        reference: &str,
+        generation: Option<u64>,
+    ) -> EngineResult<Option<Vec<Verse>>> {
+        let stale = || generation.is_some_and(|generation| self.is_stale(generation));
+        let options = self.engine.module_option_names(module);
+
+        // The manager is shared with the UI thread; hold it only while the
+        // chapter is copied out, and parse once it is released
+        let (raw_verses, filters) = {
+            let inner = self.engine.lock()?;
+            inner.apply_text_options(&options)?;
+            let filters = TextFilters::new(&inner, &options);
+            let h_mod = inner.mgr.module(module)?;
+            match self.copy_chapter(&h_mod, reference, &stale)? {
+                Some(raw_verses) => (raw_verses, filters),
+                None => return Ok(None),
+            }
+        };
+
+        let mut verses = Vec::with_capacity(raw_verses.len());
+        // A paragraph mark at the very end of a verse opens the next one
+        let mut pending_paragraph = false;
+
+        for raw in raw_verses {
+            if stale() {
+                return Ok(None);
+            }
+            log::trace!("{} {}: {}", module, raw.key, raw.osis);
+
+            let (mut words, mut notes, mut blocks) = Self::parse_osis_content(&raw.osis);
+
+            // Preverse headings live in the entry attributes, ahead of the text
+            let headings = raw.headings.into_iter().map(|text| VerseBlock {
+                kind: BlockKind::Heading(text),
+                anchor: 0,
+            });
+            blocks.splice(0..0, headings);
+            filters.apply(&mut words, &mut notes, &mut blocks);
+
+            // Apply grouping markers (brackets for Added, potential spans for Red)
+            self.apply_group_metadata(&mut words);
+
+            let is_paragraph_start = pending_paragraph
+                || raw.key.ends_with(":1")
+                || blocks
+                    .iter()
+                    .any(|b| b.anchor == 0 && b.kind == BlockKind::Paragraph);
+            pending_paragraph = blocks
+                .iter()
+                .any(|b| b.anchor == words.len() && b.kind == BlockKind::Paragraph)
+                && !words.is_empty();
+
+            verses.push(Verse {
+                number: self.extract_verse_number(&raw.key),
+                osis_id: raw.key,
+                words,
+                notes,
+                blocks,
+                is_paragraph_start,
+            });
+        }
+
+        // Letter filters and cross-reference texts need SWORD again, so the
+        // lock is taken once more, and only when there is work for it
+        let has_targets = verses
+            .iter()
+            .flat_map(|v| &v.notes)
+            .any(|n| !n.targets.is_empty());
+        if filters.letters.is_empty() && !has_targets {
+            return Ok(Some(verses));
+        }
+        if stale() {
+            return Ok(None);
+        }
+        let inner = self.engine.lock()?;
+        inner.apply_text_options(&options)?;
+        for word in verses.iter_mut().flat_map(|v| v.words.iter_mut()) {
+            filters.filter_letters(&inner, word);
+        }
+        let h_mod = inner.mgr.module(module)?;
+        for verse in verses.iter_mut() {
+            for note in verse.notes.iter_mut() {
+                note.resolved = self.resolve_references(&h_mod, &note.targets);
+            }
+        }
+        Ok(Some(verses))
+    }
+
+    /// Copies the raw entries, keys and preverse headings of the chapter
+    /// holding `reference`; `None` once `stale` reports a newer request.
+    fn copy_chapter(
+        &self,
+        h_mod: &Module,
+        reference: &str,
+        stale: &dyn Fn() -> bool,
+    ) -> EngineResult<Option<Vec<RawVerse>>> {
+        h_mod.set_key(reference)?;
+        let initial_key = h_mod.key_text().unwrap_or_default();
+
+        let chapter_boundary = initial_key
+            .split(|c| c == ':' || c == '.')
+            .next()
+            .unwrap_or(&initial_key)
+            .to_string();
+
+        let mut raw_verses = Vec::new();
+        loop {
+            if stale() {
+                return Ok(None);
+            }
+            let key = match h_mod.key_text() {
+                Some(k) => k,
+                None => break,
+            };
+            if !key.starts_with(&chapter_boundary) {
+                break;
+            }
+
+            let osis = match h_mod.raw_entry() {
+                Some(s) => s,
+                None => break,
+            };
+            raw_verses.push(RawVerse {
+                key,
+                osis,
+                headings: self.preverse_headings(h_mod),
+            });
+
+            if !h_mod.next() {
+                break;
+            }
+        }
+        Ok(Some(raw_verses))
+    }
+
+    fn parse_osis_content(osis: &str) -> (Vec<Word>, Vec<Note>, Vec<VerseBlock>) {
+        use scraper::Html;
+        let fragment = Html::parse_fragment(osis);
+        let mut words = Vec::new();
+        let mut notes = Vec::new();
+        let mut blocks = Vec::new();
+
+        Self::walk_osis(
+            fragment.tree.root(),
+            &mut words,
+            &mut notes,
+            &mut blocks,
+            None,
+            false, // is_red (Jesus block)
+            false, // is_added (Theological status)
+            false, // is_italic (General style)
+        );
+
+        (words, notes, blocks)
+    }
+
+    fn walk_osis(
+        node: NodeRef<scraper::node::Node>,
+        words: &mut Vec<Word>,
+        notes: &mut Vec<Note>,
+        blocks: &mut Vec<VerseBlock>,
+        parent_lex: Option<LexicalInfo>,
+        is_red: bool,
+        is_added: bool,
+        is_italic: bool,
+    ) {
+        use scraper::node::Node;
+
+        match node.value() {
+            Node::Text(t) => {
+                let text = t.text.trim();
+                if !text.is_empty() {
+                    for piece in text.split_whitespace() {
+                        words.push(Word {
+                            text: piece.to_string(),
+                            // This determines if we see "Plain" or "Added" in your debug
+                            style: if is_added {
+                                SegmentStyle::Added
+                            } else {
+                                SegmentStyle::Plain
+                            },
+                            is_red,
+                            is_italic,
+                            is_bold_text: false,
+                            lex: parent_lex.clone(),
+                            note: None,
+                            is_first_in_group: false,
+                            is_last_in_group: false,
+                            is_punctuation: piece.chars().all(|c| c.is_ascii_punctuation()),
+                        });
+                    }
+                }
+            }
+            Node::Element(el) => {
+                // Inheritance: start with the parent's state
+                let mut current_lex = parent_lex.clone();
+                let mut active_red = is_red;
+                let mut active_added = is_added;
+                let mut active_italic = is_italic;
+
+                // The HTML parser lowercases OSIS tag names (transChange -> transchange)
+                match el.name().to_ascii_lowercase().as_str() {
+                    "w" => {
+                        let raw_lemma = el.attr("lemma").unwrap_or("");
+                        let raw_morph = el.attr("morph").unwrap_or("");
+
+                        let strongs: Vec<String> = raw_lemma
+                            .split_whitespace()
+                            .filter(|s| s.starts_with("strong:"))
+                            .map(|s| s.trim_start_matches("strong:").to_string())
+                            .collect();
+
+                        let tr_lemma = raw_lemma
+                            .split_whitespace()
+                            .find(|s| s.starts_with("lemma.TR:"))
+                            .map(|s| s.trim_start_matches("lemma.TR:").to_string());
+
+                        current_lex = Some(LexicalInfo {
+                            strongs,
+                            lemma: tr_lemma,
+                            // Kept raw; decoded on demand by the morphology module
+                            morph: Some(raw_morph.to_string()).filter(|m| !m.is_empty()),
+                            gloss: Self::osis_attr(el, "gloss").map(str::to_string),
+                            ..Default::default()
+                        });
+                    }
+                    "q" if el.attr("who") == Some("Jesus") => {
+                        active_red = true;
+                    }
+                    "transchange" if el.attr("type") == Some("added") => {
+                        active_added = true;
+                        //active_italic = true;
+                    }
+                    "hi" if el.attr("type") == Some("italic") => {
+                        active_italic = true;
+                    }
+                    "title" => {
+                        // Preverse headings are read from the entry attributes
+                        if Self::osis_attr(el, "subType") != Some("x-preverse") {
+                            let text = Self::element_text(node);
+                            if !text.is_empty() {
+                                let kind = if el.attr("type") == Some("psalm") {
+                                    BlockKind::Superscription(text)
+                                } else {
+                                    BlockKind::Heading(text)
+                                };
+                                Self::push_block(blocks, kind, words.len());
+                            }
+                        }
+                        return;
+                    }
+                    "div" if el.attr("type") == Some("colophon") => {
+                        let text = Self::element_text(node);
+                        if !text.is_empty() {
+                            Self::push_block(blocks, BlockKind::Colophon(text), words.len());
+                        }
+                        return;
+                    }
+                    "div"
+                        if el.attr("type") == Some("paragraph")
+                            && Self::osis_attr(el, "eID").is_none() =>
+                    {
+                        Self::push_block(blocks, BlockKind::Paragraph, words.len());
+                    }
+                    "lg" if Self::osis_attr(el, "eID").is_none() => {
+                        Self::push_block(blocks, BlockKind::Paragraph, words.len());
+                    }
+                    "p" => {
+                        Self::push_block(blocks, BlockKind::Paragraph, words.len());
+                    }
+                    "milestone" if el.attr("type") == Some("x-p") => {
+                        Self::push_block(blocks, BlockKind::Paragraph, words.len());
+                    }
+                    "l" if Self::osis_attr(el, "eID").is_none() => {
+                        let level = el.attr("level").and_then(|l| l.parse().ok()).unwrap_or(1);
+                        Self::push_block(blocks, BlockKind::PoetryLine(level), words.len());
+                    }
+                    "lb" => {
+                        Self::push_block(blocks, BlockKind::LineBreak, words.len());
+                    }
+                    "note" => {
+                        // Notes never flow into the word stream; they are kept
+                        // aside and anchored after the words emitted so far.
+                        let (note, catch_word) =
+                            Self::build_note(node, el, words.len(), notes.len());
+                        if let Some(catch_word) = catch_word
+                            && let Some(word) = words[..note.anchor]
+                                .iter_mut()
+                                .rev()
+                                .find(|w| w.text.to_lowercase().contains(&catch_word))
+                        {
+                            word.note = Some(note.text.clone());
+                        }
+                        notes.push(note);
+                        return;
+                    }
+                    _ => {}
+                }
+
+                // Recurse into children with the UPDATED state
+                for child in node.children() {
+                    Self::walk_osis(
+                        child,
+                        words,
+                        notes,
+                        blocks,
+                        current_lex.clone(),
+                        active_red,
+                        active_added,
+                        active_italic,
+                    );
+                }
+            }
+            _ => {
+                // For non-elements/non-text, just pass the state through
+                for child in node.children() {
+                    Self::walk_osis(
+                        child,
+                        words,
+                        notes,
+                        blocks,
+                        parent_lex.clone(),
+                        is_red,
+                        is_added,
+                        is_italic,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Turns a `<note>` subtree into a structured [`Note`]. Also returns the
+    /// normalized `<catchWord>` text, if any, so the caller can attach the
+    /// note to the word it comments on.
+    fn build_note(
+        node: NodeRef<scraper::node::Node>,
+        el: &scraper::node::Element,
+        anchor: usize,
+        index: usize,
+    ) -> (Note, Option<String>) {
+        use scraper::{ElementRef, node::Node};
+
+        let mut targets = Vec::new();
+        let mut catch_word = None;
+
+        for descendant in node.descendants() {
+            let Node::Element(child) = descendant.value() else {
+                continue;
+            };
+            match child.name().to_ascii_lowercase().as_str() {
+                "reference" => {
+                    if let Some(osis_ref) = Self::osis_attr(child, "osisRef") {
+                        targets.push(osis_ref.to_string());
+                    }
+                }
+                "catchword" => {
+                    catch_word = ElementRef::wrap(descendant).map(|c| {
+                        c.text()
+                            .collect::<String>()
+                            .replace("…", "")
+                            .to_lowercase()
+                            .trim()
+                            .to_string()
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        let text = ElementRef::wrap(node)
+            .map(|n| n.text().collect::<Vec<_>>().join(" "))
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let kind = if el.attr("type") == Some("crossReference") {
+            NoteKind::CrossReference
+        } else {
+            NoteKind::Footnote
+        };
+
+        let marker = el
+            .attr("n")
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| Note::default_marker(index));
+
+        let mut note = Note::new(kind, marker, anchor).text(text);
+        note.targets = targets;
+        (note, catch_word.filter(|c| !c.is_empty()))
+    }
+
+    /// Records a structural block, skipping exact repeats at the same spot
+    /// (e.g. an `<lg>` immediately followed by a `<p>`).
+    fn push_block(blocks: &mut Vec<VerseBlock>, kind: BlockKind, anchor: usize) {
+        let block = VerseBlock { kind, anchor };
+        if blocks.last() != Some(&block) {
+            blocks.push(block);
+        }
+    }
+
+    /// Plain text of an element. `<title>` is parsed as raw text by the HTML
+    /// parser, so any nested OSIS markup arrives as literal tags to strip.
+    fn element_text(node: NodeRef<scraper::node::Node>) -> String {
+        let raw = scraper::ElementRef::wrap(node)
+            .map(|e| e.text().collect::<String>())
+            .unwrap_or_default();
+        Self::strip_markup(&raw)
+    }
+
+    fn strip_markup(raw: &str) -> String {
+        let mut text = String::with_capacity(raw.len());
+        let mut in_tag = false;
+        for c in raw.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' if in_tag => in_tag = false,
+                _ if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Section headings SWORD files under the current entry's
+    /// "Heading/Preverse" attributes (requires the "Headings" option).
+    fn preverse_headings(&self, h_mod: &Module) -> Vec<String> {
+        h_mod
+            .entry_attribute("Heading", "Preverse", "", false)
+            .unwrap_or_default()
+            .iter()
+            .map(|raw| Self::strip_markup(raw))
+            .filter(|text| !text.is_empty())
+            .collect()
+    }
+
+    /// Attribute lookup that tolerates the HTML parser lowercasing
+    /// camelCase OSIS attributes (osisRef -> osisref).
+    fn osis_attr<'a>(el: &'a scraper::node::Element, name: &str) -> Option<&'a str> {
+        el.attrs()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// Expands the osisRef targets of a note through the module's
+    /// versification and fetches the plain text of every referenced verse.
+    fn resolve_references(&self, h_mod: &Module, targets: &[String]) -> Vec<ResolvedReference> {
+        let mut resolved = Vec::new();
+
+        for target in targets {
+            let keys = h_mod.parse_key_list(target).unwrap_or_default();
+            for key in keys {
+                if resolved.len() >= MAX_RESOLVED_VERSES {
+                    return resolved;
+                }
+                if h_mod.set_key(&key).is_err() {
+                    continue;
+                }
+                let text = h_mod.strip_text().unwrap_or_default();
+                resolved.push(ResolvedReference {
+                    osis_ref: key,
+                    text: text.trim().to_string(),
+                });
+            }
+        }
+        resolved
+    }
+
+    fn apply_group_metadata(&self, words: &mut [Word]) {
+        let len = words.len();
+        if len == 0 {
+            return;
+        }
+
+        for i in 0..len {
+            // 1. Check for Added words (Theological status)
+            if words[i].style == SegmentStyle::Added {
+                let is_prev_added = if i > 0 {
+                    words[i - 1].style == SegmentStyle::Added
+                } else {
+                    false
+                };
+                let is_next_added = if i < len - 1 {
+                    words[i + 1].style == SegmentStyle::Added
+                } else {
+                    false
+                };
+
+                if !is_prev_added {
+                    words[i].is_first_in_group = true;
+                }
+                if !is_next_added {
+                    words[i].is_last_in_group = true;
+                }
+            }
+            // 2. Check for Jesus words (is_red) ONLY if not already marked by an Added group
+            else if words[i].is_red {
+                let is_prev_red = if i > 0 { words[i - 1].is_red } else { false };
+                let is_next_red = if i < len - 1 {
+                    words[i + 1].is_red
+                } else {
+                    false
+                };
+
+                if !is_prev_red {
+                    words[i].is_first_in_group = true;
+                }
+                if !is_next_red {
+                    words[i].is_last_in_group = true;
+                }
+            }
+        }
+    }
+
+    fn extract_verse_number(&self, key: &str) -> i32 {
+        key.split(|c| c == '.' || c == ':')
+            .last()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0)
+    }
+}
+
+/// "H07225", "H7225" and "H7225a" all name the same entry; strip leading
+/// zeros and letter suffixes so modules with different conventions pair up.
+fn normalize_strongs(strong: &str) -> String {
+    let mut chars = strong.chars();
+    let Some(prefix) = chars.next() else {
+        return String::new();
+    };
+    let digits: String = chars.take_while(|c| c.is_ascii_digit()).collect();
+    format!(
+        "{}{}",
+        prefix.to_ascii_uppercase(),
+        digits.trim_start_matches('0')
+    )
+}
+
+/// A few-word gloss from a Strong's lexicon entry. Strong's dictionaries end
+/// each entry with the KJV renderings after ":--"; the first of those is used.
+fn short_gloss(definition: &str) -> Option<String> {
+    let renderings = definition
+        .split_once(":--")
+        .or_else(|| definition.split_once(":-"))
+        .map(|(_, rest)| rest)?;
+    let first = renderings
+        .split([',', ';', '.'])
+        .next()?
+        .trim()
+        .trim_start_matches(['-', ' ', '+', 'X'])
+        .trim();
+    (!first.is_empty()).then(|| first.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(words: &[Word]) -> Vec<&str> {
+        words.iter().map(|w| w.text.as_str()).collect()
+    }
+
+    #[test]
+    fn keeps_notes_apart_from_the_words() {
+        let (words, notes, _) = ChapterReader::parse_osis_content(
+            r#"In the <w lemma="strong:H7225">beginning</w><note type="crossReference" n="x"><reference osisRef="John.1.1">John 1:1</reference>; <reference osisRef="Heb.11.3">Heb 11:3</reference></note> God created<note><catchWord>created</catchWord> Or formed</note> the heaven."#,
+        );
+        assert_eq!(
+            texts(&words),
+            ["In", "the", "beginning", "God", "created", "the", "heaven."]
+        );
+
+        let reference = &notes[0];
+        assert_eq!(reference.kind, NoteKind::CrossReference);
+        assert_eq!(reference.marker, "x");
+        assert_eq!(reference.anchor, 3);
+        assert_eq!(reference.targets, ["John.1.1", "Heb.11.3"]);
+
+        let footnote = &notes[1];
+        assert_eq!(footnote.kind, NoteKind::Footnote);
+        assert_eq!(footnote.marker, Note::default_marker(1));
+        assert_eq!(footnote.anchor, 5);
+        assert_eq!(footnote.text, "created Or formed");
+        assert_eq!(words[4].note.as_deref(), Some("created Or formed"));
+        assert_eq!(words.iter().filter(|w| w.note.is_some()).count(), 1);
+    }
+
+    #[test]
+    fn reads_headings_poetry_and_paragraphs() {
+        let (words, _, blocks) = ChapterReader::parse_osis_content(
+            r#"<title subType="x-preverse">Read from the entry attributes</title><title type="psalm" canonical="true">A Psalm of <hi type="italic">David</hi>.</title><lg><l level="1">The LORD is my shepherd;</l><l level="2">I shall not want.</l></lg>"#,
+        );
+        assert_eq!(words.len(), 9);
+        let kinds: Vec<(&BlockKind, usize)> = blocks.iter().map(|b| (&b.kind, b.anchor)).collect();
+        assert_eq!(
+            kinds,
+            [
+                (&BlockKind::Superscription("A Psalm of David.".into()), 0),
+                (&BlockKind::Paragraph, 0),
+                (&BlockKind::PoetryLine(1), 0),
+                (&BlockKind::PoetryLine(2), 5),
+            ]
+        );
+
+        let (words, _, blocks) = ChapterReader::parse_osis_content(
+            r#"<title>The Greeting</title>Grace to you.<milestone type="x-p"/><p>Amen.</p><div type="colophon">Written from Rome.</div>"#,
+        );
+        assert_eq!(texts(&words), ["Grace", "to", "you.", "Amen."]);
+        let kinds: Vec<(&BlockKind, usize)> = blocks.iter().map(|b| (&b.kind, b.anchor)).collect();
+        assert_eq!(
+            kinds,
+            [
+                (&BlockKind::Heading("The Greeting".into()), 0),
+                (&BlockKind::Paragraph, 3),
+                (&BlockKind::Colophon("Written from Rome.".into()), 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn pairs_words_by_strongs_number() {
+        let tagged = |text: &str, strong: &str| {
+            Word::builder(text)
+                .lex(Some(LexicalInfo::default().strong(strong)))
+                .build()
+        };
+        let mut english = vec![
+            Verse::builder("Gen.1.1", 1)
+                .word(Word::builder("In").build())
+                .word(tagged("beginning", "H7225"))
+                .word(tagged("God", "H430"))
+                .word(tagged("created", "H1254"))
+                .build(),
+        ];
+        let hebrew = [Verse::builder("Gen.1.1", 1)
+            .word(tagged("בְּרֵאשִׁית", "H07225"))
+            .word(tagged("בָּרָא", "H1254a"))
+            .word(tagged("אֱלֹהִים", "h0430"))
+            .build()];
+
+        ChapterReader::pair_verses(&mut english, &hebrew);
+        let paired: Vec<Option<&str>> = english[0]
+            .words
+            .iter()
+            .map(|w| w.lex.as_ref().and_then(|lex| lex.paired.as_deref()))
+            .collect();
+        assert_eq!(paired, [None, Some("בְּרֵאשִׁית"), Some("אֱלֹהִים"), Some("בָּרָא")]);
+    }
+
+    #[test]
+    fn normalizes_strongs_and_shortens_glosses() {
+        assert_eq!(normalize_strongs("h07225"), "H7225");
+        assert_eq!(normalize_strongs("G3056a"), "G3056");
+        assert_eq!(
+            short_gloss(
+                "from the same as 7218; the first :--beginning, chief(-est), first(-fruits)."
+            ),
+            Some("beginning".to_string())
+        );
+        assert_eq!(short_gloss("a word, speech"), None);
+    }
+}
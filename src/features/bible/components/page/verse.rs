@@ -1,97 +1,481 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
 use adw::prelude::*;
-use relm4::{FactorySender, prelude::*};
-
-use crate::features::bible::components::page::{helpers::Verse, word::AddedWordStyle};
-
-// --- VERSE FACTORY ---
-#[relm4::factory(pub)]
-impl FactoryComponent for Verse {
-    type Init = Verse;
-    type Input = ();
-    type Output = ();
-    type CommandOutput = ();
-    type ParentWidget = gtk::Box;
-
-    view! {
-        gtk::Box {
-            set_orientation: gtk::Orientation::Horizontal,
-            set_spacing: 12,
-            set_hexpand: true,
-
-            gtk::Label {
-                add_css_class: "verser-number",
-                set_markup: &format!(
-                    "<span size='large'>{}</span>",
-                    self.number
-                ),
-                set_valign: gtk::Align::Start,
-            },
-
-            gtk::Box {
+use gtk::pango;
+use relm4::{prelude::*, typed_view::list::RelmListItem};
+
+use crate::features::{
+    bible::{
+        components::page::{
+            layout::{SpanStyle, VerseLayout},
+            position::ChapterPosition,
+            word::ReaderOptions,
+        },
+        model::{BlockKind, NoteKind, Verse},
+    },
+    core::settings::VerseNumberStyle,
+};
+
+/// Horizontal indent per poetry level in interlinear mode, in pixels
+const POETRY_INDENT: i32 = 24;
+
+#[derive(Debug)]
+pub enum VerseOutput {
+    /// A cross-reference inside one of the verse's notes was clicked
+    Navigate(String),
+    /// "Search this lemma" was picked in a word popover
+    SearchLemma(String),
+    /// The verse number was toggled, selecting or deselecting the verse
+    Selected(String, bool),
+}
+
+/// Hands a row's output to the page that owns the list
+pub type VerseOutputFn = Rc<dyn Fn(VerseOutput)>;
+
+/// Roots of the rows currently bound, with the chapter each one shows
+pub type BoundRows = Rc<RefCell<Vec<(gtk::Box, Option<ChapterPosition>)>>>;
+
+/// Shared by every row of a page
+#[derive(Clone)]
+pub struct RowContext {
+    pub on_output: VerseOutputFn,
+    /// Lets the page tell which chapter is scrolled into view
+    pub bound_rows: BoundRows,
+}
+
+/// A verse as rendered in the reader, together with the display options in
+/// effect when it was built. Rows of the list view are recycled: only the
+/// verses on screen own widgets, and `bind` refills them.
+#[derive(Clone)]
+pub struct VerseRow {
+    pub verse: Rc<Verse>,
+    pub layout: Rc<VerseLayout>,
+    pub options: ReaderOptions,
+    /// Chapter of the module outline the verse belongs to
+    pub chapter: Option<ChapterPosition>,
+    /// Whether the verse number is toggled, shared with the bound widgets
+    pub selected: Rc<Cell<bool>>,
+    context: RowContext,
+}
+
+/// The row currently shown by a set of widgets, read by their signal handlers
+#[derive(Clone)]
+struct BoundRow {
+    verse: Rc<Verse>,
+    layout: Rc<VerseLayout>,
+    selected: Rc<Cell<bool>>,
+    on_output: VerseOutputFn,
+}
+
+pub struct VerseWidgets {
+    heading_box: gtk::Box,
+    number_button: gtk::ToggleButton,
+    number_label: gtk::Label,
+    text_label: gtk::Label,
+    interlinear_box: gtk::Box,
+    bound: Rc<RefCell<Option<BoundRow>>>,
+}
+
+impl VerseRow {
+    pub fn new(
+        verse: Verse,
+        options: ReaderOptions,
+        chapter: Option<ChapterPosition>,
+        context: RowContext,
+    ) -> Self {
+        let layout = VerseLayout::build(&verse, options.added_style);
+        Self {
+            verse: Rc::new(verse),
+            layout: Rc::new(layout),
+            options,
+            chapter,
+            selected: Rc::new(Cell::new(false)),
+            context,
+        }
+    }
+}
+
+// --- VERSE LIST ITEM ---
+impl RelmListItem for VerseRow {
+    type Root = gtk::Box;
+    type Widgets = VerseWidgets;
+
+    fn setup(_list_item: &gtk::ListItem) -> (gtk::Box, VerseWidgets) {
+        relm4::view! {
+            root = gtk::Box {
                 set_orientation: gtk::Orientation::Vertical,
-                set_spacing: 12,
+                set_spacing: 6,
+                set_margin_start: 30,
+                set_margin_end: 30,
+                set_margin_bottom: 8,
 
-                #[local_ref]
-                word_flow -> adw::WrapBox {
-                    set_line_spacing: 12,
-                    set_hexpand: true,
-                    set_halign: gtk::Align::Start,
+                #[name = "heading_box"]
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_spacing: 4,
                 },
 
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 12,
+                    set_hexpand: true,
+
+                    // Clicking the number selects the verse for copying
+                    #[name = "number_button"]
+                    gtk::ToggleButton {
+                        add_css_class: "flat",
+                        add_css_class: "verse-number-button",
+                        set_valign: gtk::Align::Start,
+                        set_tooltip_text: Some("Select Verse"),
 
+                        #[name = "number_label"]
+                        gtk::Label {
+                            add_css_class: "verser-number",
+                        },
+                    },
 
-                #[local_ref]
-                notes_container -> adw::WrapBox{
-                    #[watch]
-                    set_visible: !self.notes.is_empty(),
+                    // The whole verse is one Pango layout; clicks are mapped
+                    // back onto words and note markers
+                    #[name = "text_label"]
+                    gtk::Label {
+                        add_css_class: "bible-text",
+                        set_wrap: true,
+                        set_wrap_mode: pango::WrapMode::WordChar,
+                        set_xalign: 0.0,
+                        set_valign: gtk::Align::Start,
+                        set_hexpand: true,
+                    },
+
+                    #[name = "interlinear_box"]
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 4,
+                        set_hexpand: true,
+                        set_visible: false,
+                    },
                 }
             }
         }
+
+        let bound: Rc<RefCell<Option<BoundRow>>> = Rc::default();
+
+        number_button.connect_toggled({
+            let bound = bound.clone();
+            move |button| {
+                if let Some(row) = button.parent() {
+                    if button.is_active() {
+                        row.add_css_class("verse-selected");
+                    } else {
+                        row.remove_css_class("verse-selected");
+                    }
+                }
+                // Binding a row sets the button to the stored state, which
+                // is not a change worth reporting
+                if let Some(row) = bound.borrow().as_ref()
+                    && row.selected.get() != button.is_active()
+                {
+                    row.selected.set(button.is_active());
+                    (row.on_output)(VerseOutput::Selected(
+                        row.verse.osis_id.clone(),
+                        button.is_active(),
+                    ));
+                }
+            }
+        });
+
+        let click = gtk::GestureClick::new();
+        click.connect_released({
+            let bound = bound.clone();
+            let label = text_label.downgrade();
+            move |_, _, x, y| {
+                let Some(label) = label.upgrade() else {
+                    return;
+                };
+                if let Some(row) = bound.borrow().as_ref()
+                    && let Some(popover) = Self::popover_at(row, &label, x, y)
+                {
+                    popover.set_parent(&label);
+                    popover
+                        .set_pointing_to(Some(&gtk::gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+                    popover.connect_closed(|popover| {
+                        let popover = popover.clone();
+                        gtk::glib::idle_add_local_once(move || popover.unparent());
+                    });
+                    popover.popup();
+                }
+            }
+        });
+        text_label.add_controller(click);
+
+        // Tagged words and note markers show a pointer, as the word
+        // widgets used to highlight on hover
+        let motion = gtk::EventControllerMotion::new();
+        motion.connect_motion({
+            let bound = bound.clone();
+            let label = text_label.downgrade();
+            move |_, x, y| {
+                let Some(label) = label.upgrade() else {
+                    return;
+                };
+                let clickable = bound.borrow().as_ref().is_some_and(|row| {
+                    Self::index_at(&label, x, y).is_some_and(|index| row.is_clickable(index))
+                });
+                label.set_cursor_from_name(clickable.then_some("pointer"));
+            }
+        });
+        text_label.add_controller(motion);
+
+        let widgets = VerseWidgets {
+            heading_box,
+            number_button,
+            number_label,
+            text_label,
+            interlinear_box,
+            bound,
+        };
+        (root, widgets)
     }
 
-    fn init_model(init: Self::Init, _index: &DynamicIndex, _sender: FactorySender<Self>) -> Self {
-        init
+    fn bind(&mut self, widgets: &mut VerseWidgets, root: &mut gtk::Box) {
+        let row = BoundRow {
+            verse: self.verse.clone(),
+            layout: self.layout.clone(),
+            selected: self.selected.clone(),
+            on_output: self.context.on_output.clone(),
+        };
+        *widgets.bound.borrow_mut() = Some(row.clone());
+        self.context
+            .bound_rows
+            .borrow_mut()
+            .push((root.clone(), self.chapter));
+
+        root.set_margin_top(if self.verse.is_paragraph_start { 12 } else { 0 });
+
+        // Headings ahead of the first word sit above the verse number
+        for (text, css_class) in &self.layout.headings {
+            widgets.heading_box.append(
+                &gtk::Label::builder()
+                    .label(text.as_str())
+                    .wrap(true)
+                    .xalign(0.0)
+                    .css_classes([*css_class])
+                    .build(),
+            );
+        }
+        widgets
+            .heading_box
+            .set_visible(!self.layout.headings.is_empty());
+
+        widgets
+            .number_label
+            .set_text(&self.verse.number.to_string());
+        widgets.number_button.set_active(self.selected.get());
+        widgets
+            .number_button
+            .set_visible(self.options.verse_numbers != VerseNumberStyle::Hidden);
+
+        if self.options.interlinear {
+            // Interlinear cells stack several rows under each word, which
+            // one layout cannot draw; only the rows on screen build them
+            self.append_interlinear(&widgets.interlinear_box, &row);
+            widgets.interlinear_box.set_visible(true);
+            widgets.text_label.set_visible(false);
+        } else {
+            widgets.text_label.set_text(&self.layout.text);
+            widgets
+                .text_label
+                .set_attributes(Some(&Self::attributes(&self.layout, &self.options)));
+            widgets.text_label.set_visible(true);
+            widgets.interlinear_box.set_visible(false);
+        }
     }
 
-    fn init_widgets(
-        &mut self,
-        _index: &DynamicIndex,
-        _root: Self::Root,
-        _returned_widget: &gtk::Widget,
-        _sender: FactorySender<Self>,
-    ) -> Self::Widgets {
-        // Create the WrapBox that will hold flowing words
-        let word_flow_box = adw::WrapBox::builder()
-            .line_spacing(6)
-            .hexpand(true)
-            .halign(gtk::Align::Start)
-            .build();
+    fn unbind(&mut self, widgets: &mut VerseWidgets, root: &mut gtk::Box) {
+        widgets.bound.borrow_mut().take();
+        self.context
+            .bound_rows
+            .borrow_mut()
+            .retain(|(bound, _)| bound != &*root);
+        for container in [&widgets.heading_box, &widgets.interlinear_box] {
+            while let Some(child) = container.first_child() {
+                container.remove(&child);
+            }
+        }
+    }
+}
+
+impl BoundRow {
+    fn on_navigate(&self) -> impl Fn(String) + Clone + 'static {
+        let on_output = self.on_output.clone();
+        move |osis_ref| on_output(VerseOutput::Navigate(osis_ref))
+    }
+
+    fn on_search(&self) -> impl Fn(String) + Clone + 'static {
+        let on_output = self.on_output.clone();
+        move |strong| on_output(VerseOutput::SearchLemma(strong))
+    }
+
+    fn is_clickable(&self, index: usize) -> bool {
+        match self.layout.word_at(index) {
+            Some(position) => {
+                let word = &self.verse.words[position];
+                word.lex.is_some() || word.note.is_some()
+            }
+            None => self.layout.note_at(index).is_some(),
+        }
+    }
+}
 
-        for word in &self.words {
-            let word = word.build_widget(AddedWordStyle::Italic);
+impl VerseRow {
+    /// Byte index of the verse text under a point of the label
+    fn index_at(label: &gtk::Label, x: f64, y: f64) -> Option<usize> {
+        let (offset_x, offset_y) = label.layout_offsets();
+        let (inside, index, _trailing) = label.layout().xy_to_index(
+            (x as i32 - offset_x) * pango::SCALE,
+            (y as i32 - offset_y) * pango::SCALE,
+        );
+        inside.then_some(index as usize)
+    }
 
-            word_flow_box.append(&word);
+    /// Word details or note contents for a click on the label
+    fn popover_at(row: &BoundRow, label: &gtk::Label, x: f64, y: f64) -> Option<gtk::Popover> {
+        let index = Self::index_at(label, x, y).filter(|index| row.is_clickable(*index))?;
+        match row.layout.word_at(index) {
+            Some(position) => Some(row.verse.words[position].build_popover(row.on_search())),
+            None => row
+                .layout
+                .note_at(index)
+                .map(|note| row.verse.notes[note].build_popover(row.on_navigate())),
         }
+    }
+
+    fn attributes(layout: &VerseLayout, options: &ReaderOptions) -> pango::AttrList {
+        let attributes = pango::AttrList::new();
 
-        let word_flow = &word_flow_box;
-        let notes_container = adw::WrapBox::builder()
+        for (range, style) in &layout.spans {
+            let styled: Vec<pango::Attribute> = match style {
+                SpanStyle::Red if !options.red_letter => continue,
+                SpanStyle::Red => vec![Self::foreground(options.palette.red)],
+                SpanStyle::Added | SpanStyle::Italic | SpanStyle::Aside => {
+                    vec![pango::AttrInt::new_style(pango::Style::Italic).into()]
+                }
+                SpanStyle::Bold | SpanStyle::Heading => {
+                    vec![pango::AttrInt::new_weight(pango::Weight::Bold).into()]
+                }
+                SpanStyle::Marker(kind) => {
+                    let color = match kind {
+                        NoteKind::Footnote => options.palette.footnote,
+                        NoteKind::CrossReference => options.palette.cross_reference,
+                    };
+                    vec![
+                        pango::AttrFloat::new_scale(0.7).into(),
+                        pango::AttrInt::new_rise(4 * pango::SCALE).into(),
+                        Self::foreground(color),
+                    ]
+                }
+            };
+            for mut attribute in styled {
+                attribute.set_start_index(range.start as u32);
+                attribute.set_end_index(range.end as u32);
+                attributes.insert(attribute);
+            }
+        }
+        attributes
+    }
+
+    fn foreground(color: gtk::gdk::RGBA) -> pango::Attribute {
+        let channel = |value: f32| (value.clamp(0.0, 1.0) * 65535.0).round() as u16;
+        pango::AttrColor::new_foreground(
+            channel(color.red()),
+            channel(color.green()),
+            channel(color.blue()),
+        )
+        .into()
+    }
+
+    /// Words flow into a WrapBox per line; poetry lines, paragraph and
+    /// line breaks start a new one.
+    fn append_interlinear(&self, segment_box: &gtk::Box, row: &BoundRow) {
+        let mut segment = Self::new_segment(0);
+        let mut segment_is_empty = true;
+
+        for position in 0..=self.verse.words.len() {
+            for block in self.verse.blocks.iter().filter(|b| b.anchor == position) {
+                let indent = match &block.kind {
+                    BlockKind::PoetryLine(level) => Some(*level as i32),
+                    BlockKind::Paragraph | BlockKind::LineBreak => Some(0),
+                    _ if position == 0 => None,
+                    kind => {
+                        // Mid-verse titles and colophons get their own line
+                        if !segment_is_empty {
+                            segment_box.append(&segment);
+                            segment = Self::new_segment(0);
+                            segment_is_empty = true;
+                        }
+                        if let Some(label) = Self::block_label(kind) {
+                            segment_box.append(&label);
+                        }
+                        None
+                    }
+                };
+
+                if let Some(level) = indent {
+                    if !segment_is_empty {
+                        segment_box.append(&segment);
+                        segment = Self::new_segment(level);
+                        segment_is_empty = true;
+                    } else {
+                        segment.set_margin_start(level * POETRY_INDENT);
+                    }
+                    if block.kind == BlockKind::Paragraph && position > 0 {
+                        segment.add_css_class("paragraph-start");
+                    }
+                }
+            }
+
+            for note in self.verse.notes.iter().filter(|n| n.anchor == position) {
+                segment.append(&note.build_marker(row.on_navigate()));
+                segment_is_empty = false;
+            }
+
+            if let Some(word) = self.verse.words.get(position) {
+                segment.append(&word.build_widget(&self.options, row.on_search()));
+                segment_is_empty = false;
+            }
+        }
+
+        if !segment_is_empty {
+            segment_box.append(&segment);
+        }
+    }
+
+    fn new_segment(level: i32) -> adw::WrapBox {
+        adw::WrapBox::builder()
             .line_spacing(6)
             .hexpand(true)
             .halign(gtk::Align::Start)
-            .build();
-        for word in self.notes.clone() {
-            let note_label = gtk::Label::builder()
-                .wrap(true)
-                .margin_end(16)
-                .css_classes(vec!["verse-note"])
-                .build();
-            note_label.set_markup(
-                format!("<span size='large' foreground='#d71452'><span size='small' foreground='#c314d7'><i>Note on Verse {}: </i></span><i>{}</i></span>",self.number, word).as_str(),
-            );
-            notes_container.append(&note_label);
-        }
-        let widgets = view_output!();
+            .margin_start(level * POETRY_INDENT)
+            .build()
+    }
 
-        widgets
+    fn block_label(kind: &BlockKind) -> Option<gtk::Label> {
+        let (text, css_class) = match kind {
+            BlockKind::Heading(text) => (text, "section-heading"),
+            BlockKind::Superscription(text) => (text, "psalm-superscription"),
+            BlockKind::Colophon(text) => (text, "colophon"),
+            _ => return None,
+        };
+
+        Some(
+            gtk::Label::builder()
+                .label(text.as_str())
+                .wrap(true)
+                .xalign(0.0)
+                .css_classes([css_class])
+                .build(),
+        )
     }
 }
@@ -1,16 +1,176 @@
 use adw::prelude::*;
 use relm4::prelude::*;
 
-use crate::features::bible::components::page::helpers::{SegmentStyle, Word};
+use crate::features::{
+    bible::{
+        model::{SegmentStyle, Word},
+        morphology::Morphology,
+        transliteration::transliterate,
+    },
+    core::settings::{AddedWordStyle, ReadingSettings, VerseNumberStyle},
+};
 
+/// Which rows an interlinear word cell shows under the surface text
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub enum AddedWordStyle {
-    Italic,
-    Brackets,
+pub struct InterlinearRows {
+    pub paired: bool,
+    pub lemma: bool,
+    pub transliteration: bool,
+    pub strongs: bool,
+    pub gloss: bool,
+    pub morphology: bool,
+}
+
+impl Default for InterlinearRows {
+    fn default() -> Self {
+        Self {
+            paired: true,
+            lemma: true,
+            transliteration: false,
+            strongs: true,
+            gloss: true,
+            morphology: false,
+        }
+    }
+}
+
+/// One configurable row of an interlinear word cell
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InterlinearRow {
+    Paired,
+    Lemma,
+    Transliteration,
+    Strongs,
+    Gloss,
+    Morphology,
+}
+
+impl InterlinearRow {
+    pub const ALL: [InterlinearRow; 6] = [
+        InterlinearRow::Paired,
+        InterlinearRow::Lemma,
+        InterlinearRow::Transliteration,
+        InterlinearRow::Strongs,
+        InterlinearRow::Gloss,
+        InterlinearRow::Morphology,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InterlinearRow::Paired => "Paired Module",
+            InterlinearRow::Lemma => "Lemma",
+            InterlinearRow::Transliteration => "Transliteration",
+            InterlinearRow::Strongs => "Strong's Number",
+            InterlinearRow::Gloss => "Gloss",
+            InterlinearRow::Morphology => "Morphology",
+        }
+    }
+}
+
+impl InterlinearRows {
+    pub fn is_shown(&self, row: InterlinearRow) -> bool {
+        match row {
+            InterlinearRow::Paired => self.paired,
+            InterlinearRow::Lemma => self.lemma,
+            InterlinearRow::Transliteration => self.transliteration,
+            InterlinearRow::Strongs => self.strongs,
+            InterlinearRow::Gloss => self.gloss,
+            InterlinearRow::Morphology => self.morphology,
+        }
+    }
+
+    pub fn set_shown(&mut self, row: InterlinearRow, shown: bool) {
+        let flag = match row {
+            InterlinearRow::Paired => &mut self.paired,
+            InterlinearRow::Lemma => &mut self.lemma,
+            InterlinearRow::Transliteration => &mut self.transliteration,
+            InterlinearRow::Strongs => &mut self.strongs,
+            InterlinearRow::Gloss => &mut self.gloss,
+            InterlinearRow::Morphology => &mut self.morphology,
+        };
+        *flag = shown;
+    }
+}
+
+/// Colors of the verse text that Pango draws itself, read from the
+/// stylesheet so they follow the light or dark theme
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextPalette {
+    pub red: gtk::gdk::RGBA,
+    pub footnote: gtk::gdk::RGBA,
+    pub cross_reference: gtk::gdk::RGBA,
+}
+
+impl Default for TextPalette {
+    fn default() -> Self {
+        Self {
+            red: gtk::gdk::RGBA::new(0.878, 0.106, 0.141, 1.0),
+            footnote: gtk::gdk::RGBA::new(0.902, 0.380, 0.0, 1.0),
+            cross_reference: gtk::gdk::RGBA::new(0.208, 0.518, 0.894, 1.0),
+        }
+    }
+}
+
+impl TextPalette {
+    /// Colors of the `words-of-christ`, `footnote-text` and `crossref-text`
+    /// classes under the current theme
+    pub fn from_theme() -> Self {
+        let color = |css_class: &str| {
+            gtk::Label::builder()
+                .css_classes([css_class])
+                .build()
+                .color()
+        };
+        Self {
+            red: color("words-of-christ"),
+            footnote: color("footnote-text"),
+            cross_reference: color("crossref-text"),
+        }
+    }
+}
+
+/// Reader-wide display switches handed down to every verse
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReaderOptions {
+    pub added_style: AddedWordStyle,
+    pub red_letter: bool,
+    pub verse_numbers: VerseNumberStyle,
+    pub palette: TextPalette,
+    /// Lay words out as interlinear cells
+    pub interlinear: bool,
+    pub interlinear_rows: InterlinearRows,
+}
+
+impl Default for ReaderOptions {
+    fn default() -> Self {
+        Self {
+            added_style: AddedWordStyle::Italic,
+            red_letter: true,
+            verse_numbers: VerseNumberStyle::Normal,
+            palette: TextPalette::default(),
+            interlinear: false,
+            interlinear_rows: InterlinearRows::default(),
+        }
+    }
+}
+
+impl ReaderOptions {
+    /// Takes the preferences drawn per verse; sizes and fonts are styled
+    /// through CSS and interlinear mode is switched by the study page
+    pub fn apply(&mut self, reading: &ReadingSettings) {
+        self.added_style = reading.added_style;
+        self.red_letter = reading.red_letter;
+        self.verse_numbers = reading.verse_numbers;
+    }
 }
 
 impl Word {
-    pub fn build_widget(&self, added_style: AddedWordStyle) -> gtk::Widget {
+    /// Builds the word widget. Tagged words open a detail popover on click;
+    /// its "Search this lemma" button hands the Strong's number to `on_search`.
+    pub fn build_widget<F>(&self, options: &ReaderOptions, on_search: F) -> gtk::Widget
+    where
+        F: Fn(String) + Clone + 'static,
+    {
         // Main wrapper for each word
         let wrapper = gtk::Box::builder()
             .orientation(gtk::Orientation::Vertical)
@@ -18,8 +178,6 @@ impl Word {
             .halign(gtk::Align::Start)
             .build();
 
-       // println!(" {} {:?}", self.text, self.style);
-
         // The word label
         let label = gtk::Label::builder()
             .use_markup(true)
@@ -29,88 +187,234 @@ impl Word {
             .xalign(0.0)
             .build();
 
-        label.set_markup(&self.render_word(added_style));
+        label.set_markup(&self.render_word(options.added_style));
+        if self.is_red && options.red_letter {
+            label.add_css_class("words-of-christ");
+        }
         wrapper.append(&label);
 
-        if let Some(note) = &self.note {
-            let strong_label = gtk::Label::builder()
-                .use_markup(true)
+        let morph = self
+            .lex
+            .as_ref()
+            .and_then(|lex| lex.morph.as_deref())
+            .and_then(Morphology::parse_attr);
+
+        if options.interlinear {
+            self.append_interlinear_rows(&wrapper, &options.interlinear_rows, morph.as_ref());
+        }
+
+        if self.lex.is_some() || self.note.is_some() {
+            self.attach_popover(&wrapper, morph.as_ref(), on_search);
+        }
+
+        wrapper.upcast()
+    }
+
+    /// Stacks the enabled interlinear rows under the surface text. Untagged
+    /// words keep empty rows so the cells of a line stay aligned.
+    fn append_interlinear_rows(
+        &self,
+        wrapper: &gtk::Box,
+        rows: &InterlinearRows,
+        morph: Option<&Morphology>,
+    ) {
+        let lex = self.lex.clone().unwrap_or_default();
+        let lemma = lex.lemma.clone();
+
+        let cells = [
+            (rows.paired, lex.paired.clone(), "interlinear-paired"),
+            (rows.lemma, lemma.clone(), "interlinear-lemma"),
+            (
+                rows.transliteration,
+                lemma.as_deref().and_then(transliterate),
+                "interlinear-transliteration",
+            ),
+            (
+                rows.strongs,
+                Some(lex.strongs.join(" ")).filter(|s| !s.is_empty()),
+                "interlinear-strongs",
+            ),
+            (rows.gloss, lex.gloss.clone(), "interlinear-gloss"),
+            (
+                rows.morphology,
+                morph.map(|m| m.code.clone()),
+                "interlinear-morph",
+            ),
+        ];
+
+        for (enabled, text, css_class) in cells {
+            if !enabled {
+                continue;
+            }
+            let row = gtk::Label::builder()
+                .label(text.unwrap_or_default())
                 .hexpand(false)
-                .css_classes(["bible-text", "lexical"])
+                .css_classes(["bible-text", "lexical", css_class])
                 .xalign(0.0)
                 .margin_end(8)
-                .margin_start(4)
+                .margin_start(if self.is_punctuation { 0 } else { 8 })
                 .build();
+            wrapper.append(&row);
+        }
+        wrapper.add_css_class("interlinear-word");
+    }
 
-            let cleaned = format!("<span color='#d71452'>{}</span>", note);
-
-            strong_label.set_markup(&cleaned);
-            wrapper.append(&strong_label);
-            wrapper.add_css_class("word-wrapper");
-        }
-
-        // Optional Strong's reference (lexical info)
-        if let Some(lex) = self.lex.as_ref() {
-            if !lex.strongs.is_empty() {
-                let strong_label = gtk::Label::builder()
-                    .use_markup(true)
-                    .hexpand(false)
-                    .css_classes(["bible-text", "lexical"])
-                    .xalign(0.0)
-                    .margin_end(8)
-                    .margin_start(4)
-                    .build();
-
-                let joined = lex
-                    .strongs
-                    .iter()
-                    .map(|s| format!("<span size='small' color='#1086ed'>{}</span>", s))
-                    .collect::<Vec<_>>()
-                    .join(" ");
-
-                strong_label.set_markup(&joined);
-                wrapper.append(&strong_label);
-                wrapper.add_css_class("word-wrapper");
-            }
+    /// Clicking the word pops up its details
+    fn attach_popover<F>(&self, wrapper: &gtk::Box, morph: Option<&Morphology>, on_search: F)
+    where
+        F: Fn(String) + Clone + 'static,
+    {
+        let popover = self.popover(morph, on_search);
+        popover.set_parent(wrapper);
+        wrapper.connect_destroy({
+            let popover = popover.clone();
+            move |_| popover.unparent()
+        });
 
-            if let Some(lemma) = lex.lemma.clone() {
-                let strong_label = gtk::Label::builder()
-                    .use_markup(true)
-                    .hexpand(false)
-                    .css_classes(["bible-text", "lexical"])
-                    .xalign(0.0)
-                    .margin_end(8)
-                    .margin_start(4)
-                    .build();
-
-                let cleaned = format!("<span  size='small' color='#ed10a3'>{}</span>", lemma);
-
-                strong_label.set_markup(&cleaned);
-                wrapper.append(&strong_label);
-                wrapper.add_css_class("word-wrapper");
-            }
+        let click = gtk::GestureClick::new();
+        click.connect_released(move |_, _, _, _| popover.popup());
+        wrapper.add_controller(click);
+        wrapper.add_css_class("word-wrapper");
+    }
+
+    /// Everything known about the word: lemma and transliteration, Strong's
+    /// numbers, decoded morphology and lexicon entries. The caller parents it.
+    pub fn build_popover<F>(&self, on_search: F) -> gtk::Popover
+    where
+        F: Fn(String) + Clone + 'static,
+    {
+        let morph = self
+            .lex
+            .as_ref()
+            .and_then(|lex| lex.morph.as_deref())
+            .and_then(Morphology::parse_attr);
+        self.popover(morph.as_ref(), on_search)
+    }
+
+    fn popover<F>(&self, morph: Option<&Morphology>, on_search: F) -> gtk::Popover
+    where
+        F: Fn(String) + Clone + 'static,
+    {
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(6)
+            .margin_top(8)
+            .margin_bottom(8)
+            .margin_start(8)
+            .margin_end(8)
+            .width_request(280)
+            .build();
+
+        let title = gtk::Label::builder()
+            .label(&self.text)
+            .xalign(0.0)
+            .css_classes(["title-3"])
+            .build();
+        content.append(&title);
+
+        let lex = self.lex.clone().unwrap_or_default();
+
+        if let Some(lemma) = lex.lemma.as_deref() {
+            let text = match transliterate(lemma) {
+                Some(latin) => format!("{lemma} · {latin}"),
+                None => lemma.to_string(),
+            };
+            content.append(&Self::detail_label(&text, "word-lemma"));
+        }
+
+        if !lex.strongs.is_empty() {
+            content.append(&Self::detail_label(&lex.strongs.join(" "), "word-strongs"));
+        }
+
+        if let Some(gloss) = lex.gloss.as_deref() {
+            content.append(&Self::detail_label(gloss, "word-gloss"));
+        }
+
+        if let Some(morph) = morph {
+            let code_label = Self::detail_label(&morph.code, "monospace");
+            code_label.add_css_class("dimmed");
+            content.append(&code_label);
+            content.append(&Self::detail_label(&morph.describe(), "word-morphology"));
+        }
 
-            /*  if let Some(morph) = lex.morph.clone() {
-                println!("{morph}");
-                let strong_label = gtk::Label::builder()
-                    .use_markup(true)
-                    .hexpand(false)
-                    .css_classes(["bible-text", "lexical"])
-                    .xalign(0.0)
-                    .margin_end(8)
-                    .margin_start(4)
-                    .build();
-
-                let cleaned = format!("<span  size='small' color='#6110ed'>{}</span>", morph);
-
-                strong_label.set_markup(&cleaned);
-                wrapper.append(&strong_label);
-                wrapper.add_css_class("word-wrapper");
+        if !lex.definitions.is_empty() {
+            let definitions = gtk::Box::new(gtk::Orientation::Vertical, 6);
+            for definition in &lex.definitions {
+                definitions.append(&Self::detail_label(
+                    &format!("{} ({})", definition.strong, definition.lexicon),
+                    "heading",
+                ));
+                definitions.append(&Self::detail_label(&definition.text, "word-definition"));
             }
-            */
+            let scroller = gtk::ScrolledWindow::builder()
+                .hscrollbar_policy(gtk::PolicyType::Never)
+                .propagate_natural_height(true)
+                .max_content_height(240)
+                .child(&definitions)
+                .build();
+            content.append(&scroller);
         }
 
-        wrapper.upcast()
+        if let Some(note) = self.note.as_deref() {
+            content.append(&Self::detail_label(note, "note-text"));
+        }
+
+        let popover = gtk::Popover::builder()
+            .child(&content)
+            .css_classes(["word-popover"])
+            .build();
+
+        let actions = gtk::Box::builder()
+            .spacing(6)
+            .halign(gtk::Align::End)
+            .build();
+
+        if let Some(strong) = lex.strongs.first().cloned() {
+            let search_button = gtk::Button::builder()
+                .label("Search this lemma")
+                .css_classes(["flat"])
+                .build();
+            search_button.connect_clicked({
+                let popover = popover.clone();
+                move |_| {
+                    popover.popdown();
+                    on_search(strong.clone());
+                }
+            });
+            actions.append(&search_button);
+        }
+
+        let copy_button = gtk::Button::builder()
+            .icon_name("edit-copy-symbolic")
+            .tooltip_text("Copy")
+            .css_classes(["flat"])
+            .build();
+        let copy_text = [
+            Some(self.text.clone()),
+            lex.lemma.clone(),
+            (!lex.strongs.is_empty()).then(|| lex.strongs.join(" ")),
+            morph.map(|m| m.describe()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" — ");
+        copy_button.connect_clicked(move |button| button.clipboard().set_text(&copy_text));
+        actions.append(&copy_button);
+        content.append(&actions);
+
+        popover
+    }
+
+    fn detail_label(text: &str, css_class: &str) -> gtk::Label {
+        gtk::Label::builder()
+            .label(text)
+            .wrap(true)
+            .max_width_chars(40)
+            .xalign(0.0)
+            .selectable(true)
+            .css_classes([css_class])
+            .build()
     }
 
     fn render_word(&self, added_style: AddedWordStyle) -> String {
@@ -128,10 +432,6 @@ impl Word {
             _ => escaped.to_string(),
         };
 
-        if self.is_red {
-            content = format!("<span color='#e01b24'>{}</span>", content);
-        }
-
         if self.is_italic {
             content = format!("<i>{}</i>", content);
         }
@@ -140,25 +440,6 @@ impl Word {
             content = format!("<b>{}</b>", content);
         }
 
-        format!("<span size='large'>{}</span>", content)
-    }
-}
-
-impl Default for Word {
-    fn default() -> Self {
-        Self {
-            text: String::new(),
-            lex: None,
-
-            style: SegmentStyle::Plain,
-            is_red: false,
-            is_italic: false,
-            is_bold_text: false,
-            is_punctuation: false,
-
-            is_first_in_group: false,
-            is_last_in_group: false,
-            note: None,
-        }
+        content
     }
 }
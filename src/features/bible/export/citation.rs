@@ -0,0 +1,136 @@
+use crate::features::bible::{
+    model::Verse, transilation_engines::usfm_engine::books::resolve_book_name,
+};
+
+pub const DEFAULT_CITATION_TEMPLATE: &str = "\"{text}\" — {ref} ({module})";
+
+/// How copied verses are laid out on the clipboard
+#[derive(Debug, Clone, PartialEq)]
+pub struct CitationOptions {
+    /// Free text with `{text}`, `{ref}` and `{module}` placeholders
+    pub template: String,
+    pub verse_numbers: bool,
+}
+
+impl Default for CitationOptions {
+    fn default() -> Self {
+        Self {
+            template: DEFAULT_CITATION_TEMPLATE.to_string(),
+            verse_numbers: false,
+        }
+    }
+}
+
+/// Book, chapter and verse of a verse id ("1 John 3:16" or "1John.3.16")
+#[derive(Debug, Clone, PartialEq)]
+struct VersePosition {
+    book: String,
+    chapter: i32,
+    verse: i32,
+}
+
+impl VersePosition {
+    fn parse(osis_id: &str) -> Option<Self> {
+        let (book, chapter, verse) = match osis_id.rsplit_once(' ') {
+            Some((book, position)) => {
+                let (chapter, verse) = position.split_once(':')?;
+                (book, chapter, verse)
+            }
+            None => {
+                let mut parts = osis_id.rsplitn(3, '.');
+                let verse = parts.next()?;
+                let chapter = parts.next()?;
+                (parts.next()?, chapter, verse)
+            }
+        };
+        let book = resolve_book_name(book)
+            .map(|(_, name)| name.to_string())
+            .unwrap_or_else(|| book.to_string());
+
+        Some(Self {
+            book,
+            chapter: chapter.parse().ok()?,
+            verse: verse.parse().ok()?,
+        })
+    }
+}
+
+pub struct Citation;
+
+impl Citation {
+    /// Fills the template for the selected verses. Each verse comes with its
+    /// position in reading order, so gaps in the selection are detected.
+    pub fn format(module: &str, verses: &[(usize, &Verse)], options: &CitationOptions) -> String {
+        options
+            .template
+            .replace("{text}", &Self::text(verses, options.verse_numbers))
+            .replace("{ref}", &Self::reference(verses))
+            .replace("{module}", module)
+    }
+
+    /// Verse texts in order; an ellipsis marks each gap in the selection
+    pub fn text(verses: &[(usize, &Verse)], verse_numbers: bool) -> String {
+        let mut text = String::new();
+        let mut previous: Option<usize> = None;
+
+        for (index, verse) in verses {
+            if let Some(previous) = previous {
+                text.push_str(if *index == previous + 1 { " " } else { " … " });
+            }
+            if verse_numbers {
+                text.push_str(&format!("{} ", verse.number));
+            }
+            text.push_str(&verse.plain_text());
+            previous = Some(*index);
+        }
+        text
+    }
+
+    /// Merges contiguous verses into ranges: "John 3:16-18, 20; 4:1"
+    pub fn reference(verses: &[(usize, &Verse)]) -> String {
+        // (first, last) positions of each contiguous run
+        let mut runs: Vec<(VersePosition, VersePosition)> = Vec::new();
+        let mut previous: Option<usize> = None;
+
+        for (index, verse) in verses {
+            let Some(position) = VersePosition::parse(&verse.osis_id) else {
+                continue;
+            };
+            match runs.last_mut() {
+                Some((_, last)) if previous.is_some_and(|p| p + 1 == *index) => *last = position,
+                _ => runs.push((position.clone(), position)),
+            }
+            previous = Some(*index);
+        }
+
+        let mut reference = String::new();
+        let mut current: Option<(String, i32)> = None;
+
+        for (first, last) in runs {
+            match &current {
+                Some((book, chapter)) if *book == first.book && *chapter == first.chapter => {
+                    reference.push_str(&format!(", {}", first.verse));
+                }
+                Some((book, _)) if *book == first.book => {
+                    reference.push_str(&format!("; {}:{}", first.chapter, first.verse));
+                }
+                Some(_) => reference.push_str(&format!(
+                    "; {} {}:{}",
+                    first.book, first.chapter, first.verse
+                )),
+                None => {
+                    reference
+                        .push_str(&format!("{} {}:{}", first.book, first.chapter, first.verse));
+                }
+            }
+
+            if last.chapter != first.chapter {
+                reference.push_str(&format!("-{}:{}", last.chapter, last.verse));
+            } else if last.verse != first.verse {
+                reference.push_str(&format!("-{}", last.verse));
+            }
+            current = Some((last.book, last.chapter));
+        }
+        reference
+    }
+}
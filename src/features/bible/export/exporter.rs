@@ -0,0 +1,500 @@
+use crate::features::bible::{
+    export::passage::Passage,
+    model::{BlockKind, Chapter, Note, SegmentStyle, Verse, Word},
+    transilation_engines::osis_engine::writer::OsisWriter,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    PlainText,
+    Markdown,
+    Html,
+    Osis,
+    Json,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 5] = [
+        ExportFormat::PlainText,
+        ExportFormat::Markdown,
+        ExportFormat::Html,
+        ExportFormat::Osis,
+        ExportFormat::Json,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::PlainText => "Plain Text",
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Html => "HTML",
+            ExportFormat::Osis => "OSIS XML",
+            ExportFormat::Json => "JSON",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::PlainText => "txt",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Osis => "osis.xml",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Switches for the text formats; OSIS and JSON always carry everything
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExportOptions {
+    pub verse_numbers: bool,
+    /// Set off the words of Christ (colored in Markdown/HTML, ‹…› in plain text)
+    pub red_letter: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            verse_numbers: true,
+            red_letter: true,
+        }
+    }
+}
+
+/// Everything needed to export a passage once a file has been chosen
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportRequest {
+    pub range: String, // "John 3:16-18"
+    pub format: ExportFormat,
+    pub options: ExportOptions,
+}
+
+/// Inline runs a word can sit in, outermost first
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Span {
+    Red,
+    Added,
+    Italic,
+    Bold,
+}
+
+/// How one text format spells each piece of a passage
+trait Markup {
+    fn escape(&self, text: &str) -> String;
+    fn word(&self, word: &Word) -> String {
+        self.escape(&word.text)
+    }
+    fn title(&self, text: &str) -> String;
+    fn chapter_title(&self, text: &str) -> String;
+    fn block(&self, kind: &BlockKind, text: &str) -> String;
+    fn verse_number(&self, number: i32) -> String;
+    fn open(&self, span: Span) -> &'static str;
+    fn close(&self, span: Span) -> &'static str;
+    fn line_break(&self, level: u8) -> String;
+    /// Inline marker of the n-th note (1-based) of the passage
+    fn note(&self, note: &Note, number: usize) -> String;
+    fn paragraph(&self, content: &str) -> String;
+    fn document(&self, title: &str, body: &str, notes: &[&Note]) -> String;
+}
+
+pub struct Exporter;
+
+impl Exporter {
+    pub fn export(passage: &Passage, format: ExportFormat, options: &ExportOptions) -> String {
+        match format {
+            ExportFormat::PlainText => Self::render(&PlainText, passage, options),
+            ExportFormat::Markdown => Self::render(&Markdown, passage, options),
+            ExportFormat::Html => Self::render(&Html, passage, options),
+            ExportFormat::Osis => OsisWriter::document(
+                &passage.module,
+                &passage.reference,
+                std::slice::from_ref(&passage.book),
+            ),
+            ExportFormat::Json => serde_json::to_string_pretty(passage).unwrap_or_default(),
+        }
+    }
+
+    fn render(markup: &dyn Markup, passage: &Passage, options: &ExportOptions) -> String {
+        let mut body = String::new();
+        let mut notes = Vec::new();
+        let multi_chapter = passage.book.chapters.len() > 1;
+
+        for chapter in &passage.book.chapters {
+            if multi_chapter {
+                let title = format!("{} {}", passage.book.title, chapter.number);
+                body.push_str(&markup.chapter_title(&title));
+            }
+            Self::render_chapter(markup, chapter, options, &mut body, &mut notes);
+        }
+
+        let title = if passage.module.is_empty() {
+            passage.reference.clone()
+        } else {
+            format!("{} ({})", passage.reference, passage.module)
+        };
+        markup.document(&title, &body, &notes)
+    }
+
+    fn render_chapter<'a>(
+        markup: &dyn Markup,
+        chapter: &'a Chapter,
+        options: &ExportOptions,
+        body: &mut String,
+        notes: &mut Vec<&'a Note>,
+    ) {
+        let mut line = String::new();
+        // Set right after a poetry line or line break, where no space goes
+        let mut line_start = false;
+
+        for verse in &chapter.verses {
+            if verse.is_paragraph_start {
+                Self::flush(markup, &mut line, body);
+            }
+            Self::render_verse(
+                markup,
+                verse,
+                options,
+                (&mut line, &mut line_start),
+                body,
+                notes,
+            );
+        }
+        Self::flush(markup, &mut line, body);
+    }
+
+    fn render_verse<'a>(
+        markup: &dyn Markup,
+        verse: &'a Verse,
+        options: &ExportOptions,
+        (line, line_start): (&mut String, &mut bool),
+        body: &mut String,
+        notes: &mut Vec<&'a Note>,
+    ) {
+        let mut open: Vec<Span> = Vec::new();
+
+        for position in 0..=verse.words.len() {
+            for note in verse.notes.iter().filter(|n| n.anchor == position) {
+                notes.push(note);
+                line.push_str(&markup.note(note, notes.len()));
+            }
+
+            for block in verse.blocks.iter().filter(|b| b.anchor == position) {
+                Self::close_spans(markup, &mut open, 0, line);
+                match &block.kind {
+                    BlockKind::Heading(text)
+                    | BlockKind::Superscription(text)
+                    | BlockKind::Colophon(text) => {
+                        Self::flush(markup, line, body);
+                        body.push_str(&markup.block(&block.kind, text));
+                    }
+                    BlockKind::Paragraph => Self::flush(markup, line, body),
+                    BlockKind::PoetryLine(level) => {
+                        if !line.is_empty() {
+                            line.push_str(&markup.line_break(*level));
+                            *line_start = true;
+                        }
+                    }
+                    BlockKind::LineBreak => {
+                        if !line.is_empty() {
+                            line.push_str(&markup.line_break(1));
+                            *line_start = true;
+                        }
+                    }
+                }
+            }
+
+            if position == 0 && options.verse_numbers {
+                if !line.is_empty() && !*line_start && !line.ends_with(' ') {
+                    line.push(' ');
+                }
+                line.push_str(&markup.verse_number(verse.number));
+                *line_start = false;
+            }
+
+            let Some(word) = verse.words.get(position) else {
+                continue;
+            };
+
+            let wanted = Self::spans_of(word, options);
+            let shared = open
+                .iter()
+                .zip(wanted.iter())
+                .take_while(|(a, b)| a == b)
+                .count();
+            Self::close_spans(markup, &mut open, shared, line);
+            if !line.is_empty() && !word.is_punctuation && !*line_start && !line.ends_with(' ') {
+                line.push(' ');
+            }
+            *line_start = false;
+            for span in &wanted[shared..] {
+                line.push_str(markup.open(*span));
+                open.push(*span);
+            }
+            line.push_str(&markup.word(word));
+        }
+
+        Self::close_spans(markup, &mut open, 0, line);
+    }
+
+    fn close_spans(markup: &dyn Markup, open: &mut Vec<Span>, keep: usize, line: &mut String) {
+        while open.len() > keep {
+            line.push_str(markup.close(open.pop().unwrap()));
+        }
+    }
+
+    fn flush(markup: &dyn Markup, line: &mut String, body: &mut String) {
+        let content = line.trim();
+        if !content.is_empty() {
+            body.push_str(&markup.paragraph(content));
+        }
+        line.clear();
+    }
+
+    fn spans_of(word: &Word, options: &ExportOptions) -> Vec<Span> {
+        [
+            (word.is_red && options.red_letter, Span::Red),
+            (word.style == SegmentStyle::Added, Span::Added),
+            (word.is_italic, Span::Italic),
+            (word.is_bold_text, Span::Bold),
+        ]
+        .into_iter()
+        .filter_map(|(on, span)| on.then_some(span))
+        .collect()
+    }
+}
+
+struct PlainText;
+
+impl Markup for PlainText {
+    fn escape(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    fn title(&self, text: &str) -> String {
+        format!("{text}\n\n")
+    }
+
+    fn chapter_title(&self, text: &str) -> String {
+        format!("{text}\n\n")
+    }
+
+    fn block(&self, _kind: &BlockKind, text: &str) -> String {
+        format!("{text}\n\n")
+    }
+
+    fn verse_number(&self, number: i32) -> String {
+        format!("{number} ")
+    }
+
+    fn open(&self, span: Span) -> &'static str {
+        match span {
+            Span::Red => "‹",
+            Span::Added => "[",
+            Span::Italic | Span::Bold => "",
+        }
+    }
+
+    fn close(&self, span: Span) -> &'static str {
+        match span {
+            Span::Red => "›",
+            Span::Added => "]",
+            Span::Italic | Span::Bold => "",
+        }
+    }
+
+    fn line_break(&self, level: u8) -> String {
+        format!("\n{}", "  ".repeat(level.saturating_sub(1) as usize))
+    }
+
+    fn note(&self, _note: &Note, _number: usize) -> String {
+        String::new()
+    }
+
+    fn paragraph(&self, content: &str) -> String {
+        format!("{content}\n\n")
+    }
+
+    fn document(&self, title: &str, body: &str, _notes: &[&Note]) -> String {
+        format!("{}{}", self.title(title), body.trim_end()) + "\n"
+    }
+}
+
+struct Markdown;
+
+impl Markup for Markdown {
+    fn escape(&self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            if matches!(c, '\\' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '`') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+
+    fn title(&self, text: &str) -> String {
+        format!("# {}\n\n", self.escape(text))
+    }
+
+    fn chapter_title(&self, text: &str) -> String {
+        format!("## {}\n\n", self.escape(text))
+    }
+
+    fn block(&self, kind: &BlockKind, text: &str) -> String {
+        match kind {
+            BlockKind::Heading(_) => format!("### {}\n\n", self.escape(text)),
+            _ => format!("_{}_\n\n", self.escape(text)),
+        }
+    }
+
+    fn verse_number(&self, number: i32) -> String {
+        format!("**{number}** ")
+    }
+
+    fn open(&self, span: Span) -> &'static str {
+        match span {
+            Span::Red => r#"<span style="color:#c01c28">"#,
+            Span::Added | Span::Italic => "_",
+            Span::Bold => "**",
+        }
+    }
+
+    fn close(&self, span: Span) -> &'static str {
+        match span {
+            Span::Red => "</span>",
+            Span::Added | Span::Italic => "_",
+            Span::Bold => "**",
+        }
+    }
+
+    fn line_break(&self, level: u8) -> String {
+        format!("  \n{}", "&emsp;".repeat(level.saturating_sub(1) as usize))
+    }
+
+    fn note(&self, _note: &Note, number: usize) -> String {
+        format!("[^{number}]")
+    }
+
+    fn paragraph(&self, content: &str) -> String {
+        format!("{content}\n\n")
+    }
+
+    fn document(&self, title: &str, body: &str, notes: &[&Note]) -> String {
+        let mut out = format!("{}{}", self.title(title), body);
+        for (i, note) in notes.iter().enumerate() {
+            let mut text = self.escape(&note.text);
+            if text.is_empty() {
+                text = note.targets.join("; ");
+            }
+            out.push_str(&format!("[^{}]: {}\n", i + 1, text));
+        }
+        out.trim_end().to_string() + "\n"
+    }
+}
+
+struct Html;
+
+impl Markup for Html {
+    fn escape(&self, text: &str) -> String {
+        OsisWriter::escape(text)
+    }
+
+    fn word(&self, word: &Word) -> String {
+        let text = self.escape(&word.text);
+        match word.lex.as_ref().filter(|l| !l.strongs.is_empty()) {
+            Some(lex) => format!(
+                r#"<span class="w" data-strongs="{}">{text}</span>"#,
+                self.escape(&lex.strongs.join(" "))
+            ),
+            None => text,
+        }
+    }
+
+    fn title(&self, text: &str) -> String {
+        format!("<h1>{}</h1>\n", self.escape(text))
+    }
+
+    fn chapter_title(&self, text: &str) -> String {
+        format!("<h2>{}</h2>\n", self.escape(text))
+    }
+
+    fn block(&self, kind: &BlockKind, text: &str) -> String {
+        match kind {
+            BlockKind::Heading(_) => format!("<h3 class=\"heading\">{}</h3>\n", self.escape(text)),
+            BlockKind::Colophon(_) => format!("<p class=\"colophon\">{}</p>\n", self.escape(text)),
+            _ => format!("<p class=\"superscription\">{}</p>\n", self.escape(text)),
+        }
+    }
+
+    fn verse_number(&self, number: i32) -> String {
+        format!(r#"<sup class="verse-number">{number}</sup>"#)
+    }
+
+    fn open(&self, span: Span) -> &'static str {
+        match span {
+            Span::Red => r#"<span class="words-of-christ">"#,
+            Span::Added => r#"<em class="added">"#,
+            Span::Italic => "<i>",
+            Span::Bold => "<b>",
+        }
+    }
+
+    fn close(&self, span: Span) -> &'static str {
+        match span {
+            Span::Red => "</span>",
+            Span::Added => "</em>",
+            Span::Italic => "</i>",
+            Span::Bold => "</b>",
+        }
+    }
+
+    fn line_break(&self, level: u8) -> String {
+        format!("<br/>{}", "&emsp;".repeat(level.saturating_sub(1) as usize))
+    }
+
+    fn note(&self, note: &Note, number: usize) -> String {
+        format!(
+            r##"<sup class="note"><a href="#note-{number}" title="{}">{}</a></sup>"##,
+            self.escape(&note.text),
+            self.escape(&note.marker)
+        )
+    }
+
+    fn paragraph(&self, content: &str) -> String {
+        format!("<p>{content}</p>\n")
+    }
+
+    fn document(&self, title: &str, body: &str, notes: &[&Note]) -> String {
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\"/>\n<title>{}</title>\n\
+             <style>\n{HTML_STYLE}</style>\n</head>\n<body>\n{}{}",
+            self.escape(title),
+            self.title(title),
+            body
+        );
+        if !notes.is_empty() {
+            out.push_str("<ol class=\"notes\">\n");
+            for (i, note) in notes.iter().enumerate() {
+                let mut text = self.escape(&note.text);
+                if text.is_empty() {
+                    text = self.escape(&note.targets.join("; "));
+                }
+                out.push_str(&format!("<li id=\"note-{}\">{}</li>\n", i + 1, text));
+            }
+            out.push_str("</ol>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+/// Mirrors the reader's look so exported handouts match the screen
+const HTML_STYLE: &str = "body { font-family: serif; font-size: 14pt; line-height: 1.6; max-width: 40em; margin: 2em auto; }
+.verse-number { color: #1c71d8; font-size: 0.7em; margin-right: 0.2em; }
+.words-of-christ { color: #c01c28; }
+.added { font-style: italic; }
+.heading { font-size: 1.1em; }
+.superscription, .colophon { font-style: italic; color: #5e5c64; }
+.note a { color: #9a9996; text-decoration: none; }
+.notes { font-size: 0.85em; color: #5e5c64; }
+";
@@ -0,0 +1,177 @@
+//! Writes passages out as plain text, Markdown, HTML, OSIS XML or JSON
+//! for slides and handouts, and formats copied verses as citations.
+
+mod citation;
+mod exporter;
+mod passage;
+
+pub use citation::{Citation, CitationOptions, DEFAULT_CITATION_TEMPLATE};
+pub use exporter::{ExportFormat, ExportOptions, ExportRequest, Exporter};
+pub use passage::{Passage, PassageRange};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::bible::{
+        model::{BlockKind, Book, Chapter, LexicalInfo, Note, NoteKind, SegmentStyle, Verse, Word},
+        transilation_engines::osis_engine::core::OsisEngine,
+    };
+    use quick_xml::Reader;
+
+    fn sample_passage() -> Passage {
+        let verse_16 = Verse::builder("John.3.16", 16)
+            .paragraph_start(true)
+            .block(BlockKind::Heading("God's Love & the World".into()))
+            .word(Word::builder("For").build())
+            .word(
+                Word::builder("God")
+                    .style(SegmentStyle::RedLetter)
+                    .red(true)
+                    .lex(Some(LexicalInfo::default().strong("G2316").lemma("θεός")))
+                    .build(),
+            )
+            .word(
+                Word::builder("so")
+                    .style(SegmentStyle::Added)
+                    .red(true)
+                    .build(),
+            )
+            .word(
+                Word::builder("loved")
+                    .style(SegmentStyle::RedLetter)
+                    .red(true)
+                    .build(),
+            )
+            .note(Note::new(NoteKind::Footnote, "a", 0).text("Or, thus"))
+            .word(Word::builder(",").red(true).punctuation(true).build())
+            .build();
+        let verse_17 = Verse::builder("John.3.17", 17)
+            .word(Word::builder("Tom").build())
+            .word(Word::builder("&").punctuation(true).build())
+            .block(BlockKind::PoetryLine(2))
+            .word(Word::builder("Jerry").build())
+            .note(Note::new(NoteKind::CrossReference, "b", 0).target("Rom.5.8"))
+            .build();
+
+        Passage {
+            reference: "John 3:16-17".into(),
+            module: "KJV".into(),
+            book: Book::new("John", "John")
+                .chapter(Chapter::new("John", 3).verse(verse_16).verse(verse_17)),
+        }
+    }
+
+    #[test]
+    fn parses_reference_ranges() {
+        let range = PassageRange::parse("1 John 3:16-18").unwrap();
+        assert_eq!(range.book, "1 John");
+        assert_eq!((range.start, range.end), ((3, Some(16)), (3, Some(18))));
+        assert!(range.contains(3, 17) && !range.contains(3, 19));
+
+        let range = PassageRange::parse("John 3:36-4:2").unwrap();
+        assert!(range.contains(3, 36) && range.contains(4, 2) && !range.contains(3, 35));
+
+        let range = PassageRange::parse("Ps 23-24").unwrap();
+        assert_eq!(range.chapters(), 23..=24);
+        assert!(range.contains(24, 10));
+
+        assert!(PassageRange::parse("John").is_none());
+        assert!(PassageRange::parse("John 4-3").is_none());
+    }
+
+    #[test]
+    fn plain_text_and_markdown_honour_options() {
+        let passage = sample_passage();
+
+        let plain = Exporter::export(&passage, ExportFormat::PlainText, &ExportOptions::default());
+        assert_eq!(
+            plain,
+            "John 3:16-17 (KJV)\n\nGod's Love & the World\n\n16 For ‹God [so] loved,› 17 Tom&\n  Jerry\n"
+        );
+
+        let options = ExportOptions {
+            verse_numbers: false,
+            red_letter: false,
+        };
+        let markdown = Exporter::export(&passage, ExportFormat::Markdown, &options);
+        assert_eq!(
+            markdown,
+            "# John 3:16-17 (KJV)\n\n### God's Love & the World\n\n\
+             For God _so_ loved[^1], Tom&  \n&emsp;Jerry[^2]\n\n\
+             [^1]: Or, thus\n[^2]: Rom.5.8\n"
+        );
+    }
+
+    #[test]
+    fn html_keeps_styles() {
+        let html = Exporter::export(
+            &sample_passage(),
+            ExportFormat::Html,
+            &ExportOptions::default(),
+        );
+        assert!(html.contains(r#"<sup class="verse-number">16</sup>"#));
+        assert!(html.contains(r#"<span class="words-of-christ"><span class="w" data-strongs="G2316">God</span> <em class="added">so</em> loved"#));
+        assert!(html.contains("Love &amp; the World"));
+        assert!(html.contains(".words-of-christ { color: #c01c28; }"));
+    }
+
+    #[test]
+    fn osis_round_trips_through_the_osis_engine() {
+        let passage = sample_passage();
+        let osis = Exporter::export(&passage, ExportFormat::Osis, &ExportOptions::default());
+
+        let mut reader = Reader::from_str(&osis);
+        reader.config_mut().trim_text(true);
+        let verses = OsisEngine::parse_verses(&mut reader, "John.3");
+        assert_eq!(verses, passage.book.chapters[0].verses);
+
+        let mut reader = Reader::from_str(&osis);
+        let books = OsisEngine::parse_books(&mut reader);
+        assert_eq!(books[0].osis_id, "John");
+        assert_eq!(books[0].title, "John");
+    }
+
+    #[test]
+    fn json_is_the_serde_model() {
+        let passage = sample_passage();
+        let json = Exporter::export(&passage, ExportFormat::Json, &ExportOptions::default());
+        assert_eq!(serde_json::from_str::<Passage>(&json).unwrap(), passage);
+    }
+
+    #[test]
+    fn citations_merge_contiguous_verses() {
+        let verse = |id: &str, number: i32| {
+            Verse::builder(id, number)
+                .word(Word::builder(format!("v{number}")).build())
+                .build()
+        };
+        let (v16, v17, v18, v20) = (
+            verse("John 3:16", 16),
+            verse("John 3:17", 17),
+            verse("John 3:18", 18),
+            verse("John 3:20", 20),
+        );
+
+        let selection = [(15, &v16), (16, &v17), (17, &v18)];
+        assert_eq!(
+            Citation::format("KJV", &selection, &CitationOptions::default()),
+            "\"v16 v17 v18\" — John 3:16-18 (KJV)"
+        );
+
+        let options = CitationOptions {
+            template: "{ref} {text}".into(),
+            verse_numbers: true,
+        };
+        let selection = [(15, &v16), (16, &v17), (19, &v20)];
+        assert_eq!(
+            Citation::format("KJV", &selection, &options),
+            "John 3:16-17, 20 16 v16 17 v17 … 20 v20"
+        );
+
+        let (v24, v1) = (verse("1John.3.24", 24), verse("1John.4.1", 1));
+        assert_eq!(
+            Citation::reference(&[(23, &v24), (24, &v1)]),
+            "1 John 3:24-4:1"
+        );
+    }
+}
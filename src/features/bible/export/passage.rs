@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::features::bible::{
+    model::Book, transilation_engines::usfm_engine::books::resolve_book_name,
+};
+
+/// A span of verses within one book, read from a module or document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Passage {
+    pub reference: String, // "John 3:16-18"
+    pub module: String,
+    pub book: Book,
+}
+
+/// A reference range such as "John 3", "John 3-4", "John 3:16-18" or
+/// "John 3:16-4:2". A missing verse means the whole chapter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassageRange {
+    pub book: String,
+    pub start: (i32, Option<i32>),
+    pub end: (i32, Option<i32>),
+}
+
+impl PassageRange {
+    pub fn parse(reference: &str) -> Option<Self> {
+        let (book, rest) = reference.trim().rsplit_once(' ')?;
+        let (from, to) = rest.split_once('-').unwrap_or((rest, rest));
+
+        let start = Self::parse_point(from)?;
+        let end = match to.split_once(':') {
+            Some(_) => Self::parse_point(to)?,
+            // "3:16-18" continues in the same chapter, "3-4" spans chapters
+            None if start.1.is_some() => (start.0, Some(to.trim().parse().ok()?)),
+            None => (to.trim().parse().ok()?, None),
+        };
+        if end.0 < start.0 {
+            return None;
+        }
+
+        Some(Self {
+            book: book.trim().to_string(),
+            start,
+            end,
+        })
+    }
+
+    fn parse_point(point: &str) -> Option<(i32, Option<i32>)> {
+        match point.trim().split_once(':') {
+            Some((chapter, verse)) => Some((chapter.parse().ok()?, Some(verse.parse().ok()?))),
+            None => Some((point.trim().parse().ok()?, None)),
+        }
+    }
+
+    pub fn chapters(&self) -> std::ops::RangeInclusive<i32> {
+        self.start.0..=self.end.0
+    }
+
+    pub fn contains(&self, chapter: i32, verse: i32) -> bool {
+        let after_start = chapter > self.start.0 || self.start.1.is_none_or(|v| verse >= v);
+        let before_end = chapter < self.end.0 || self.end.1.is_none_or(|v| verse <= v);
+        self.chapters().contains(&chapter) && after_start && before_end
+    }
+
+    /// Chapter reference both SWORD and the document readers accept ("1John 3")
+    pub fn chapter_reference(&self, chapter: i32) -> String {
+        format!("{} {}", self.osis_book().0, chapter)
+    }
+
+    /// OSIS id and display name of the book, falling back to the text as typed
+    pub fn osis_book(&self) -> (String, String) {
+        match resolve_book_name(&self.book) {
+            Some((osis, name)) => (osis.to_string(), name.to_string()),
+            None => (self.book.clone(), self.book.clone()),
+        }
+    }
+}
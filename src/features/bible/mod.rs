@@ -1 +1,6 @@
-pub mod components;
\ No newline at end of file
+pub mod components;
+pub mod export;
+pub mod model;
+pub mod morphology;
+pub mod transilation_engines;
+pub mod transliteration;
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use crate::features::bible::model::verse::Verse;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Book {
+    pub osis_id: String,
+    pub title: String,
+    pub chapters: Vec<Chapter>,
+    pub canonical: bool,
+}
+
+impl Book {
+    pub fn new(osis_id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            osis_id: osis_id.into(),
+            title: title.into(),
+            chapters: Vec::new(),
+            canonical: true,
+        }
+    }
+
+    pub fn chapter(mut self, chapter: Chapter) -> Self {
+        self.chapters.push(chapter);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chapter {
+    pub title: String,
+    pub osis_ref: String,
+    pub number: String,
+    pub verses: Vec<Verse>,
+}
+
+impl Chapter {
+    /// An untitled chapter "Gen.1" of book `book_osis_id`
+    pub fn new(book_osis_id: &str, number: i32) -> Self {
+        Self {
+            title: String::new(),
+            osis_ref: format!("{}.{}", book_osis_id, number),
+            number: number.to_string(),
+            verses: Vec::new(),
+        }
+    }
+
+    pub fn verse(mut self, verse: Verse) -> Self {
+        self.verses.push(verse);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BibleVersion {
+    pub osis_id: String,    // e.g., "KJV"
+    pub title: String,      // e.g., "King James Version (1769)"
+    pub identifier: String, // e.g., "Bible.KJV"
+    pub scope: String,      // e.g., "Gen-Rev"
+    pub ref_system: String, // e.g., "Bible.KJV"
+}
@@ -0,0 +1,88 @@
+//! UI-agnostic domain model shared by every text source (SWORD modules,
+//! OSIS documents, imported formats) and consumed by one renderer.
+
+mod book;
+mod verse;
+mod word;
+
+pub use book::{BibleVersion, Book, Chapter};
+pub use verse::{BlockKind, Note, NoteKind, ResolvedReference, Verse, VerseBlock, VerseBuilder};
+pub use word::{LexicalInfo, SegmentStyle, StrongsDefinition, Word, WordBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_verse() -> Verse {
+        Verse::builder("John.1.1", 1)
+            .paragraph_start(true)
+            .block(BlockKind::Heading("The Word Became Flesh".into()))
+            .word(Word::builder("In").build())
+            .word(Word::builder("the").build())
+            .word(
+                Word::builder("beginning")
+                    .lex(Some(
+                        LexicalInfo::default()
+                            .strong("G746")
+                            .lemma("ἀρχή")
+                            .morph("robinson:N-DSF"),
+                    ))
+                    .build(),
+            )
+            .note(Note::new(NoteKind::Footnote, "a", 0).text("Or In the origin"))
+            .word(Word::builder("was").added(true).red(true).build())
+            .word(Word::builder(",").detect_punctuation().build())
+            .note(Note::new(NoteKind::CrossReference, "b", 0).target("Gen.1.1"))
+            .block(BlockKind::LineBreak)
+            .build()
+    }
+
+    #[test]
+    fn builder_anchors_notes_and_blocks_at_the_current_word() {
+        let verse = sample_verse();
+
+        assert_eq!(verse.words.len(), 5);
+        assert_eq!(verse.notes[0].anchor, 3);
+        assert_eq!(verse.notes[1].anchor, 5);
+        assert_eq!(verse.notes[1].targets, vec!["Gen.1.1".to_string()]);
+        assert_eq!(verse.blocks[0].anchor, 0);
+        assert_eq!(verse.blocks[1].kind, BlockKind::LineBreak);
+        assert!(verse.words[4].is_punctuation);
+        assert_eq!(verse.words[3].style, SegmentStyle::Added);
+        assert_eq!(verse.plain_text(), "In the beginning was,");
+    }
+
+    #[test]
+    fn verse_round_trips_through_json() {
+        let verse = sample_verse();
+        let json = serde_json::to_string(&verse).unwrap();
+        let back: Verse = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, verse);
+    }
+
+    #[test]
+    fn book_round_trips_through_json() {
+        let book = Book::new("John", "John").chapter(Chapter::new("John", 1).verse(sample_verse()));
+        let json = serde_json::to_string(&book).unwrap();
+        let back: Book = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, book);
+        assert_eq!(back.chapters[0].osis_ref, "John.1");
+    }
+
+    #[test]
+    fn older_payloads_without_optional_fields_still_load() {
+        let json = r#"{
+            "osis_id": "Gen.1.1", "number": 1, "is_paragraph_start": false,
+            "notes": [],
+            "words": [{
+                "text": "God", "style": "Plain", "is_red": false, "is_italic": false,
+                "is_bold_text": false, "note": null, "is_first_in_group": false,
+                "is_last_in_group": false, "is_punctuation": false,
+                "lex": {"strongs": ["H430"], "lemma": null, "gloss": null, "morph": null}
+            }]
+        }"#;
+        let verse: Verse = serde_json::from_str(json).unwrap();
+        assert!(verse.blocks.is_empty());
+        assert!(verse.words[0].lex.as_ref().unwrap().definitions.is_empty());
+    }
+}
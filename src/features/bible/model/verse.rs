@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+
+use crate::features::bible::model::word::Word;
+
+/// What kind of annotation a note carries
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NoteKind {
+    Footnote,
+    CrossReference,
+}
+
+/// A referenced passage with its text looked up in the current module
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedReference {
+    pub osis_ref: String, // "Job.38.4"
+    pub text: String,
+}
+
+/// A footnote or cross-reference anchored inside the word stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    pub kind: NoteKind,
+    pub marker: String, // "a", "b", ... shown as superscript
+    pub text: String,
+
+    /// osisRef targets of `<reference>` children ("Job.38.4-Job.38.7")
+    pub targets: Vec<String>,
+    /// Verse texts of the targets, filled in by the page after parsing
+    #[serde(default)]
+    pub resolved: Vec<ResolvedReference>,
+
+    /// Number of words preceding the note; the marker is drawn after them
+    pub anchor: usize,
+}
+
+impl Note {
+    pub fn new(kind: NoteKind, marker: impl Into<String>, anchor: usize) -> Self {
+        Self {
+            kind,
+            marker: marker.into(),
+            text: String::new(),
+            targets: Vec::new(),
+            resolved: Vec::new(),
+            anchor,
+        }
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    pub fn target(mut self, osis_ref: impl Into<String>) -> Self {
+        self.targets.push(osis_ref.into());
+        self
+    }
+
+    /// Marker letter for the n-th note of a verse: "a".."z", then wrapping
+    pub fn default_marker(index: usize) -> String {
+        char::from(b'a' + (index % 26) as u8).to_string()
+    }
+}
+
+/// Structural markers that break up a verse the way a printed Bible does
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BlockKind {
+    Heading(String),        // Section heading
+    Superscription(String), // Canonical Psalm title
+    Colophon(String),       // Closing subscription of some epistles
+    Paragraph,              // Paragraph break
+    PoetryLine(u8),         // Start of a poetry line at an indent level
+    LineBreak,
+}
+
+/// A structural marker anchored inside the word stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerseBlock {
+    pub kind: BlockKind,
+
+    /// Number of words preceding the block
+    pub anchor: usize,
+}
+
+/// A full verse, UI-agnostic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Verse {
+    pub osis_id: String,
+    pub number: i32,
+
+    pub words: Vec<Word>,
+    pub notes: Vec<Note>,
+    #[serde(default)]
+    pub blocks: Vec<VerseBlock>,
+
+    /// Paragraph indentation hint
+    pub is_paragraph_start: bool,
+}
+
+impl Verse {
+    pub fn builder(osis_id: impl Into<String>, number: i32) -> VerseBuilder {
+        VerseBuilder {
+            verse: Verse {
+                osis_id: osis_id.into(),
+                number,
+                words: Vec::new(),
+                notes: Vec::new(),
+                blocks: Vec::new(),
+                is_paragraph_start: false,
+            },
+        }
+    }
+
+    /// The verse's words joined back into running text
+    pub fn plain_text(&self) -> String {
+        let mut text = String::new();
+        for word in &self.words {
+            if !text.is_empty() && !word.is_punctuation {
+                text.push(' ');
+            }
+            text.push_str(&word.text);
+        }
+        text
+    }
+}
+
+/// Chained construction for `Verse`. Notes and blocks pushed through the
+/// builder are anchored after the words added so far.
+#[derive(Debug, Clone)]
+pub struct VerseBuilder {
+    verse: Verse,
+}
+
+impl VerseBuilder {
+    pub fn word(mut self, word: Word) -> Self {
+        self.verse.words.push(word);
+        self
+    }
+
+    pub fn words(mut self, words: impl IntoIterator<Item = Word>) -> Self {
+        self.verse.words.extend(words);
+        self
+    }
+
+    /// Adds a note at the current word position
+    pub fn note(mut self, mut note: Note) -> Self {
+        note.anchor = self.verse.words.len();
+        self.verse.notes.push(note);
+        self
+    }
+
+    /// Adds a structural block at the current word position
+    pub fn block(mut self, kind: BlockKind) -> Self {
+        let anchor = self.verse.words.len();
+        if !self
+            .verse
+            .blocks
+            .iter()
+            .any(|b| b.anchor == anchor && b.kind == kind)
+        {
+            self.verse.blocks.push(VerseBlock { kind, anchor });
+        }
+        self
+    }
+
+    pub fn paragraph_start(mut self, is_paragraph_start: bool) -> Self {
+        self.verse.is_paragraph_start = is_paragraph_start;
+        self
+    }
+
+    /// Number of words added so far, i.e. the anchor of the next note/block
+    pub fn position(&self) -> usize {
+        self.verse.words.len()
+    }
+
+    pub fn build(self) -> Verse {
+        self.verse
+    }
+}
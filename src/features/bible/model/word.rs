@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+/// How a segment should be rendered or interpreted
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SegmentStyle {
+    Plain,
+    Added,     // Supplied words (italics / brackets)
+    RedLetter, // Words of Christ
+    Note,      // Footnotes or annotations
+}
+
+/// Lexical metadata attached to a word
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct LexicalInfo {
+    pub strongs: Vec<String>,  // "G3056"
+    pub lemma: Option<String>, // "λόγος"
+    pub gloss: Option<String>, // "word, speech"
+    /// Raw OSIS morph attribute ("robinson:V-PAI-3S"), decoded on demand
+    pub morph: Option<String>,
+    /// Lexicon entries for `strongs`, filled in by the page after parsing
+    #[serde(default)]
+    pub definitions: Vec<StrongsDefinition>,
+    /// Surface text of the words sharing a Strong's number in the paired
+    /// module of a reverse interlinear ("λόγος" under "Word")
+    #[serde(default)]
+    pub paired: Option<String>,
+}
+
+impl LexicalInfo {
+    pub fn strong(mut self, strong: impl Into<String>) -> Self {
+        self.strongs.push(strong.into());
+        self
+    }
+
+    pub fn lemma(mut self, lemma: impl Into<String>) -> Self {
+        self.lemma = Some(lemma.into());
+        self
+    }
+
+    pub fn gloss(mut self, gloss: impl Into<String>) -> Self {
+        self.gloss = Some(gloss.into());
+        self
+    }
+
+    pub fn morph(mut self, morph: impl Into<String>) -> Self {
+        self.morph = Some(morph.into());
+        self
+    }
+}
+
+/// A Strong's number looked up in an installed lexicon module
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrongsDefinition {
+    pub strong: String,  // "G3056"
+    pub lexicon: String, // "StrongsGreek"
+    pub text: String,
+}
+
+/// A single renderable word or punctuation mark
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Word {
+    pub text: String,
+
+    pub style: SegmentStyle,
+    pub is_red: bool,
+    pub is_italic: bool,
+    pub is_bold_text: bool,
+
+    /// Lexicon & dictionary hooks
+    pub lex: Option<LexicalInfo>,
+    pub note: Option<String>,
+
+    /// Grouping flags (for Added / RedLetter spans)
+    pub is_first_in_group: bool,
+    pub is_last_in_group: bool,
+
+    /// Layout hint
+    pub is_punctuation: bool,
+}
+
+impl Word {
+    pub fn builder(text: impl Into<String>) -> WordBuilder {
+        WordBuilder {
+            word: Word {
+                text: text.into(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl Default for Word {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            lex: None,
+
+            style: SegmentStyle::Plain,
+            is_red: false,
+            is_italic: false,
+            is_bold_text: false,
+            is_punctuation: false,
+
+            is_first_in_group: false,
+            is_last_in_group: false,
+            note: None,
+        }
+    }
+}
+
+/// Chained construction for `Word`, used by the format readers
+#[derive(Debug, Clone)]
+pub struct WordBuilder {
+    word: Word,
+}
+
+impl WordBuilder {
+    pub fn style(mut self, style: SegmentStyle) -> Self {
+        self.word.style = style;
+        self
+    }
+
+    pub fn added(self, added: bool) -> Self {
+        if added {
+            self.style(SegmentStyle::Added)
+        } else {
+            self
+        }
+    }
+
+    pub fn red(mut self, is_red: bool) -> Self {
+        self.word.is_red = is_red;
+        self
+    }
+
+    pub fn italic(mut self, is_italic: bool) -> Self {
+        self.word.is_italic = is_italic;
+        self
+    }
+
+    pub fn bold(mut self, is_bold: bool) -> Self {
+        self.word.is_bold_text = is_bold;
+        self
+    }
+
+    pub fn lex(mut self, lex: Option<LexicalInfo>) -> Self {
+        self.word.lex = lex;
+        self
+    }
+
+    pub fn note(mut self, note: impl Into<String>) -> Self {
+        self.word.note = Some(note.into());
+        self
+    }
+
+    /// Marks the word as punctuation when every character is ASCII punctuation
+    pub fn detect_punctuation(mut self) -> Self {
+        self.word.is_punctuation =
+            !self.word.text.is_empty() && self.word.text.chars().all(|c| c.is_ascii_punctuation());
+        self
+    }
+
+    pub fn punctuation(mut self, is_punctuation: bool) -> Self {
+        self.word.is_punctuation = is_punctuation;
+        self
+    }
+
+    pub fn build(self) -> Word {
+        self.word
+    }
+}
@@ -0,0 +1,542 @@
+//! Decoding of the morphology codes found in OSIS `morph` attributes.
+//!
+//! Each scheme lives in its own submodule and fills in one or more
+//! [`MorphSegment`]s; [`Morphology`] ties them together and produces a
+//! human-readable description.
+
+pub mod oshm;
+pub mod packard;
+pub mod robinson;
+pub mod strong_morph;
+
+use serde::{Deserialize, Serialize};
+
+/// Which tagging scheme a code belongs to, taken from its prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MorphScheme {
+    Robinson,    // robinson:V-PAI-3S
+    Packard,     // packard:V1 PAI3S
+    Oshm,        // oshm:HVqp3ms
+    StrongMorph, // strongMorph:TH8804 / TG5719
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    Greek,
+    Hebrew,
+    Aramaic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PartOfSpeech {
+    Noun,
+    Verb,
+    Adjective,
+    Adverb,
+    Article,
+    Pronoun,
+    Preposition,
+    Conjunction,
+    Particle,
+    Interjection,
+    Numeral,
+    Suffix,
+    Letter,
+    Foreign,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Tense {
+    Present,
+    Imperfect,
+    Future,
+    Aorist,
+    Perfect,
+    Pluperfect,
+    SequentialPerfect,   // Hebrew weqatal
+    SequentialImperfect, // Hebrew wayyiqtol
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Voice {
+    Active,
+    Middle,
+    Passive,
+    MiddleOrPassive,
+    MiddleDeponent,
+    PassiveDeponent,
+    MiddleOrPassiveDeponent,
+    ImpersonalActive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mood {
+    Indicative,
+    Subjunctive,
+    Optative,
+    Imperative,
+    Infinitive,
+    Participle,
+    Cohortative,
+    Jussive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Person {
+    First,
+    Second,
+    Third,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Case {
+    Nominative,
+    Genitive,
+    Dative,
+    Accusative,
+    Vocative,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Number {
+    Singular,
+    Plural,
+    Dual,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Gender {
+    Masculine,
+    Feminine,
+    Neuter,
+    Common,
+    Both,
+}
+
+/// Hebrew/Aramaic noun state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum State {
+    Absolute,
+    Construct,
+    Determined,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Degree {
+    Comparative,
+    Superlative,
+}
+
+/// The grammatical analysis of one morpheme. Greek codes yield a single
+/// segment; Hebrew codes may chain prefixes and suffixes (`HC/Vqw3ms`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MorphSegment {
+    pub part_of_speech: Option<PartOfSpeech>,
+    /// Refines the part of speech: "personal", "relative", "proper", ...
+    pub kind: Option<String>,
+    /// Hebrew/Aramaic verbal stem (binyan): "Qal", "Piel", ...
+    pub stem: Option<String>,
+    pub tense: Option<Tense>,
+    pub voice: Option<Voice>,
+    pub mood: Option<Mood>,
+    pub person: Option<Person>,
+    pub case: Option<Case>,
+    pub number: Option<Number>,
+    pub gender: Option<Gender>,
+    pub state: Option<State>,
+    pub degree: Option<Degree>,
+    /// Anything else the code carries: "second", "Attic", ...
+    pub notes: Vec<String>,
+}
+
+/// A decoded morphology code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Morphology {
+    pub scheme: MorphScheme,
+    /// The code without its scheme prefix
+    pub code: String,
+    pub language: Language,
+    pub segments: Vec<MorphSegment>,
+}
+
+impl Morphology {
+    /// Decodes a single prefixed code such as `robinson:N-NSM`.
+    pub fn parse(raw: &str) -> Option<Morphology> {
+        let (prefix, code) = raw.split_once(':')?;
+        let prefix = prefix.trim_start_matches("x-").to_ascii_lowercase();
+        let code = code.trim();
+        if code.is_empty() {
+            return None;
+        }
+
+        match prefix.as_str() {
+            "robinson" => robinson::parse(code),
+            "packard" => packard::parse(code),
+            "oshm" => oshm::parse(code),
+            "strongmorph" => strong_morph::parse(code),
+            // Bare Strong's tense/voice/mood codes ("TH:8804")
+            "th" | "tg" => strong_morph::parse(&format!("{}{}", prefix.to_uppercase(), code)),
+            _ => None,
+        }
+    }
+
+    /// Decodes a whole OSIS `morph` attribute, which may list several codes
+    /// (`robinson:V-PAI-3S strongMorph:TG5719`). The most descriptive scheme
+    /// wins; Strong's TVM numbers are only a fallback.
+    pub fn parse_attr(attr: &str) -> Option<Morphology> {
+        let mut best: Option<Morphology> = None;
+        for token in attr.split_whitespace() {
+            let Some(morph) = Self::parse(token) else {
+                continue;
+            };
+            let replaces = match &best {
+                None => true,
+                Some(current) => {
+                    current.scheme == MorphScheme::StrongMorph
+                        && morph.scheme != MorphScheme::StrongMorph
+                }
+            };
+            if replaces {
+                best = Some(morph);
+            }
+        }
+        best
+    }
+
+    /// "Verb, Present, Active, Indicative, Third person, Singular"
+    pub fn describe(&self) -> String {
+        let mut description = self
+            .segments
+            .iter()
+            .map(|s| s.describe())
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(" + ");
+
+        if self.language == Language::Aramaic {
+            description = format!("Aramaic: {}", description);
+        }
+        description
+    }
+}
+
+impl MorphSegment {
+    pub fn describe(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+
+        if let Some(pos) = self.part_of_speech {
+            match &self.kind {
+                Some(kind) => parts.push(capitalize(&format!("{} {}", kind, pos.label()))),
+                None => parts.push(capitalize(pos.label())),
+            }
+        }
+        if let Some(stem) = &self.stem {
+            parts.push(stem.clone());
+        }
+        parts.extend(self.tense.map(|t| capitalize(t.label())));
+        parts.extend(self.voice.map(|v| capitalize(v.label())));
+        parts.extend(self.mood.map(|m| capitalize(m.label())));
+        parts.extend(self.person.map(|p| capitalize(p.label())));
+        parts.extend(self.case.map(|c| capitalize(c.label())));
+        parts.extend(self.number.map(|n| capitalize(n.label())));
+        parts.extend(self.gender.map(|g| capitalize(g.label())));
+        parts.extend(self.state.map(|s| capitalize(s.label())));
+        parts.extend(self.degree.map(|d| capitalize(d.label())));
+        parts.extend(self.notes.iter().map(|n| capitalize(n)));
+
+        parts.join(", ")
+    }
+}
+
+impl PartOfSpeech {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Noun => "noun",
+            Self::Verb => "verb",
+            Self::Adjective => "adjective",
+            Self::Adverb => "adverb",
+            Self::Article => "article",
+            Self::Pronoun => "pronoun",
+            Self::Preposition => "preposition",
+            Self::Conjunction => "conjunction",
+            Self::Particle => "particle",
+            Self::Interjection => "interjection",
+            Self::Numeral => "numeral",
+            Self::Suffix => "suffix",
+            Self::Letter => "letter",
+            Self::Foreign => "foreign word",
+        }
+    }
+}
+
+impl Tense {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Present => "present",
+            Self::Imperfect => "imperfect",
+            Self::Future => "future",
+            Self::Aorist => "aorist",
+            Self::Perfect => "perfect",
+            Self::Pluperfect => "pluperfect",
+            Self::SequentialPerfect => "sequential perfect",
+            Self::SequentialImperfect => "sequential imperfect",
+        }
+    }
+}
+
+impl Voice {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Middle => "middle",
+            Self::Passive => "passive",
+            Self::MiddleOrPassive => "middle or passive",
+            Self::MiddleDeponent => "middle deponent",
+            Self::PassiveDeponent => "passive deponent",
+            Self::MiddleOrPassiveDeponent => "middle or passive deponent",
+            Self::ImpersonalActive => "impersonal active",
+        }
+    }
+}
+
+impl Mood {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Indicative => "indicative",
+            Self::Subjunctive => "subjunctive",
+            Self::Optative => "optative",
+            Self::Imperative => "imperative",
+            Self::Infinitive => "infinitive",
+            Self::Participle => "participle",
+            Self::Cohortative => "cohortative",
+            Self::Jussive => "jussive",
+        }
+    }
+}
+
+impl Person {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::First => "first person",
+            Self::Second => "second person",
+            Self::Third => "third person",
+        }
+    }
+
+    fn from_digit(c: char) -> Option<Self> {
+        match c {
+            '1' => Some(Self::First),
+            '2' => Some(Self::Second),
+            '3' => Some(Self::Third),
+            _ => None,
+        }
+    }
+}
+
+impl Case {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Nominative => "nominative",
+            Self::Genitive => "genitive",
+            Self::Dative => "dative",
+            Self::Accusative => "accusative",
+            Self::Vocative => "vocative",
+        }
+    }
+}
+
+impl Number {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Singular => "singular",
+            Self::Plural => "plural",
+            Self::Dual => "dual",
+        }
+    }
+}
+
+impl Gender {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Masculine => "masculine",
+            Self::Feminine => "feminine",
+            Self::Neuter => "neuter",
+            Self::Common => "common",
+            Self::Both => "both genders",
+        }
+    }
+}
+
+impl State {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Absolute => "absolute",
+            Self::Construct => "construct",
+            Self::Determined => "determined",
+        }
+    }
+}
+
+impl Degree {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Comparative => "comparative",
+            Self::Superlative => "superlative",
+        }
+    }
+}
+
+/// Greek case/number/gender triplets shared by Robinson and Packard
+/// (`NSM`, `GPF`, `DSN`). Position decides what each letter means, so the
+/// `N` of nominative and the `N` of neuter never get confused.
+fn parse_case_number_gender(code: &str, segment: &mut MorphSegment) {
+    let mut chars = code.chars();
+    segment.case = chars.next().and_then(|c| match c {
+        'N' => Some(Case::Nominative),
+        'G' => Some(Case::Genitive),
+        'D' => Some(Case::Dative),
+        'A' => Some(Case::Accusative),
+        'V' => Some(Case::Vocative),
+        _ => None,
+    });
+    segment.number = chars.next().and_then(greek_number);
+    segment.gender = chars.next().and_then(|c| match c {
+        'M' => Some(Gender::Masculine),
+        'F' => Some(Gender::Feminine),
+        'N' => Some(Gender::Neuter),
+        _ => None,
+    });
+}
+
+fn greek_number(c: char) -> Option<Number> {
+    match c {
+        'S' => Some(Number::Singular),
+        'P' => Some(Number::Plural),
+        _ => None,
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_codes_from_every_scheme() {
+        let cases = [
+            // Robinson
+            ("robinson:N-NSM", "Noun, Nominative, Singular, Masculine"),
+            ("robinson:N-ASN", "Noun, Accusative, Singular, Neuter"),
+            ("robinson:N-PRI", "Proper noun, Indeclinable"),
+            ("robinson:T-GPF", "Article, Genitive, Plural, Feminine"),
+            ("robinson:A-NSM-C", "Adjective, Nominative, Singular, Masculine, Comparative"),
+            ("robinson:P-1NS", "Personal pronoun, First person, Nominative, Singular"),
+            ("robinson:P-DSM", "Personal pronoun, Third person, Dative, Singular, Masculine"),
+            ("robinson:R-NSN", "Relative pronoun, Nominative, Singular, Neuter"),
+            (
+                "robinson:S-1SNSM",
+                "Possessive pronoun, First person, Nominative, Singular, Masculine, Singular possessor",
+            ),
+            (
+                "robinson:V-PAI-3S",
+                "Verb, Present, Active, Indicative, Third person, Singular",
+            ),
+            (
+                "robinson:V-2AAI-3S",
+                "Verb, Aorist, Active, Indicative, Third person, Singular, Second aorist",
+            ),
+            (
+                "robinson:V-AAP-NSM",
+                "Verb, Aorist, Active, Participle, Nominative, Singular, Masculine",
+            ),
+            ("robinson:V-PNN", "Verb, Present, Middle or passive deponent, Infinitive"),
+            ("robinson:PRT-N", "Negative particle"),
+            ("robinson:CONJ", "Conjunction"),
+            // Packard
+            ("packard:N1 NSF", "Noun, Nominative, Singular, Feminine, First declension"),
+            ("packard:V1 PAI3S", "Verb, Present, Active, Indicative, Third person, Singular"),
+            (
+                "packard:V1 XMPGPM",
+                "Verb, Perfect, Middle, Participle, Genitive, Plural, Masculine",
+            ),
+            ("packard:RA NSM", "Article, Nominative, Singular, Masculine"),
+            ("packard:RP GS", "Personal pronoun, Genitive, Singular"),
+            ("packard:P", "Preposition"),
+            // OSHM
+            (
+                "oshm:HVqp3ms",
+                "Verb, Qal, Perfect, Third person, Singular, Masculine",
+            ),
+            (
+                "oshm:HC/Vqw3ms",
+                "Conjunction + Verb, Qal, Sequential imperfect, Third person, Singular, Masculine",
+            ),
+            (
+                "oshm:HR/Ncfsa",
+                "Preposition + Common noun, Singular, Feminine, Absolute",
+            ),
+            ("oshm:HTd/Ncmpa", "Article + Common noun, Plural, Masculine, Absolute"),
+            ("oshm:HNpm", "Proper noun, Masculine"),
+            (
+                "oshm:HVhrmsa",
+                "Verb, Hiphil, Active, Participle, Singular, Masculine, Absolute",
+            ),
+            (
+                "oshm:HNcmsc/Sp3ms",
+                "Common noun, Singular, Masculine, Construct + Pronominal suffix, Third person, Singular, Masculine",
+            ),
+            ("oshm:AVqp3ms", "Aramaic: Verb, Peal, Perfect, Third person, Singular, Masculine"),
+            // Strong's tense/voice/mood
+            ("strongMorph:TH8804", "Verb, Hebrew tense/voice/mood code 8804"),
+            ("strongMorph:TG5719", "Verb, Greek tense/voice/mood code 5719"),
+        ];
+
+        for (code, expected) in cases {
+            let morph = Morphology::parse(code).unwrap_or_else(|| panic!("{code} did not parse"));
+            assert_eq!(morph.describe(), expected, "{code}");
+        }
+    }
+
+    #[test]
+    fn positions_disambiguate_shared_letters() {
+        let cases = [
+            ("robinson:N-NSN", Some(Case::Nominative), Some(Gender::Neuter)),
+            ("robinson:A-GPN", Some(Case::Genitive), Some(Gender::Neuter)),
+            ("robinson:T-NPF", Some(Case::Nominative), Some(Gender::Feminine)),
+        ];
+
+        for (code, case, gender) in cases {
+            let morph = Morphology::parse(code).unwrap();
+            assert_eq!(morph.segments[0].case, case, "{code}");
+            assert_eq!(morph.segments[0].gender, gender, "{code}");
+        }
+    }
+
+    #[test]
+    fn picks_the_richest_code_from_an_attribute() {
+        let cases = [
+            ("strongMorph:TG5719 robinson:V-PAI-3S", MorphScheme::Robinson),
+            ("robinson:V-PAI-3S strongMorph:TG5719", MorphScheme::Robinson),
+            ("strongMorph:TH8804", MorphScheme::StrongMorph),
+            ("x-Robinson:N-NSM", MorphScheme::Robinson),
+        ];
+
+        for (attr, scheme) in cases {
+            assert_eq!(Morphology::parse_attr(attr).map(|m| m.scheme), Some(scheme), "{attr}");
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_or_empty_codes() {
+        for attr in ["", "robinson:", "unknown:XYZ", "V-PAI-3S"] {
+            assert_eq!(Morphology::parse_attr(attr), None, "{attr}");
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! OpenScriptures Hebrew/Aramaic morphology (`HVqp3ms`, `HC/Vqw3ms`).
+//!
+//! A code starts with the language letter, then lists one segment per
+//! morpheme separated by `/`: prefixes, the main word, and suffixes.
+
+use super::{
+    Gender, Language, MorphScheme, MorphSegment, Morphology, Mood, Number, PartOfSpeech, Person,
+    State, Tense, Voice,
+};
+
+pub fn parse(code: &str) -> Option<Morphology> {
+    let mut chars = code.chars();
+    let language = match chars.next()? {
+        'H' => Language::Hebrew,
+        'A' => Language::Aramaic,
+        _ => return None,
+    };
+
+    let segments = chars
+        .as_str()
+        .split('/')
+        .map(|segment| parse_segment(segment, language))
+        .collect::<Option<Vec<_>>>()?;
+    if segments.is_empty() {
+        return None;
+    }
+
+    Some(Morphology {
+        scheme: MorphScheme::Oshm,
+        code: code.to_string(),
+        language,
+        segments,
+    })
+}
+
+fn parse_segment(code: &str, language: Language) -> Option<MorphSegment> {
+    let mut chars = code.chars();
+    let mut segment = MorphSegment::default();
+
+    match chars.next()? {
+        'A' => {
+            segment.part_of_speech = Some(PartOfSpeech::Adjective);
+            match chars.next() {
+                Some('c') => {
+                    segment.part_of_speech = Some(PartOfSpeech::Numeral);
+                    segment.kind = Some("cardinal".to_string());
+                }
+                Some('o') => {
+                    segment.part_of_speech = Some(PartOfSpeech::Numeral);
+                    segment.kind = Some("ordinal".to_string());
+                }
+                Some('g') => segment.kind = Some("gentilic".to_string()),
+                _ => {}
+            }
+            parse_gender_number_state(&mut chars, &mut segment);
+        }
+        'C' => segment.part_of_speech = Some(PartOfSpeech::Conjunction),
+        'D' => segment.part_of_speech = Some(PartOfSpeech::Adverb),
+        'N' => {
+            segment.part_of_speech = Some(PartOfSpeech::Noun);
+            match chars.next() {
+                Some('p') => {
+                    segment.kind = Some("proper".to_string());
+                    match chars.next() {
+                        Some('m') => segment.gender = Some(Gender::Masculine),
+                        Some('f') => segment.gender = Some(Gender::Feminine),
+                        Some('l') => segment.notes.push("location".to_string()),
+                        Some('t') => segment.notes.push("title".to_string()),
+                        _ => {}
+                    }
+                }
+                Some('c') => {
+                    segment.kind = Some("common".to_string());
+                    parse_gender_number_state(&mut chars, &mut segment);
+                }
+                Some('g') => {
+                    segment.kind = Some("gentilic".to_string());
+                    parse_gender_number_state(&mut chars, &mut segment);
+                }
+                _ => parse_gender_number_state(&mut chars, &mut segment),
+            }
+        }
+        'P' => {
+            segment.part_of_speech = Some(PartOfSpeech::Pronoun);
+            segment.kind = chars
+                .next()
+                .and_then(|c| match c {
+                    'd' => Some("demonstrative"),
+                    'f' => Some("indefinite"),
+                    'i' => Some("interrogative"),
+                    'p' => Some("personal"),
+                    'r' => Some("relative"),
+                    _ => None,
+                })
+                .map(|k| k.to_string());
+            parse_person_gender_number(&mut chars, &mut segment);
+        }
+        'R' => {
+            segment.part_of_speech = Some(PartOfSpeech::Preposition);
+            if chars.next() == Some('d') {
+                segment.notes.push("with definite article".to_string());
+            }
+        }
+        'S' => {
+            segment.part_of_speech = Some(PartOfSpeech::Suffix);
+            match chars.next() {
+                Some('d') => segment.kind = Some("directional he".to_string()),
+                Some('h') => segment.kind = Some("paragogic he".to_string()),
+                Some('n') => segment.kind = Some("paragogic nun".to_string()),
+                Some('p') => {
+                    segment.kind = Some("pronominal".to_string());
+                    parse_person_gender_number(&mut chars, &mut segment);
+                }
+                _ => {}
+            }
+        }
+        'T' => {
+            segment.part_of_speech = Some(PartOfSpeech::Particle);
+            match chars.next() {
+                Some('d') => segment.part_of_speech = Some(PartOfSpeech::Article),
+                Some('j') => segment.part_of_speech = Some(PartOfSpeech::Interjection),
+                Some(c) => {
+                    segment.kind = match c {
+                        'a' => Some("affirmation"),
+                        'e' => Some("exhortation"),
+                        'i' => Some("interrogative"),
+                        'm' => Some("demonstrative"),
+                        'n' => Some("negative"),
+                        'o' => Some("direct object marker"),
+                        'r' => Some("relative"),
+                        _ => None,
+                    }
+                    .map(|k| k.to_string());
+                }
+                None => {}
+            }
+        }
+        'V' => {
+            segment.part_of_speech = Some(PartOfSpeech::Verb);
+            segment.stem = chars
+                .next()
+                .and_then(|c| match language {
+                    Language::Aramaic => aramaic_stem(c),
+                    _ => hebrew_stem(c),
+                })
+                .map(|s| s.to_string());
+            parse_verb_form(&mut chars, &mut segment);
+        }
+        _ => return None,
+    }
+
+    Some(segment)
+}
+
+fn hebrew_stem(c: char) -> Option<&'static str> {
+    Some(match c {
+        'q' => "Qal",
+        'N' => "Niphal",
+        'p' => "Piel",
+        'P' => "Pual",
+        'h' => "Hiphil",
+        'H' => "Hophal",
+        't' => "Hithpael",
+        'o' => "Polel",
+        'O' => "Polal",
+        'r' => "Hithpolel",
+        'm' => "Poel",
+        'M' => "Poal",
+        'k' => "Palel",
+        'K' => "Pulal",
+        'Q' => "Qal passive",
+        'l' => "Pilpel",
+        'L' => "Polpal",
+        'f' => "Hithpalpel",
+        'D' => "Nithpael",
+        'j' => "Pealal",
+        'i' => "Pilel",
+        'u' => "Hothpaal",
+        'c' => "Tiphil",
+        'v' => "Hishtaphel",
+        'w' => "Nithpalel",
+        'y' => "Nithpoel",
+        'z' => "Hithpoel",
+        _ => return None,
+    })
+}
+
+fn aramaic_stem(c: char) -> Option<&'static str> {
+    Some(match c {
+        'q' => "Peal",
+        'Q' => "Peil",
+        'u' => "Hithpeel",
+        'p' => "Pael",
+        'P' => "Ithpaal",
+        'M' => "Hithpaal",
+        'a' => "Aphel",
+        'h' => "Haphel",
+        's' => "Saphel",
+        'e' => "Shaphel",
+        'H' => "Hophal",
+        'i' => "Ithpeel",
+        't' => "Hishtaphel",
+        'v' => "Ishtaphel",
+        'w' => "Hithaphel",
+        'o' => "Polel",
+        'z' => "Ithpoel",
+        'r' => "Hithpolel",
+        'f' => "Hithpalpel",
+        'b' => "Hephal",
+        'c' => "Tiphel",
+        'm' => "Poel",
+        'l' => "Palpel",
+        'L' => "Ithpalpel",
+        'O' => "Ithpolel",
+        'G' => "Ittaphal",
+        _ => return None,
+    })
+}
+
+/// Conjugation letter, then person/gender/number for finite forms or
+/// gender/number/state for participles.
+fn parse_verb_form(chars: &mut std::str::Chars, segment: &mut MorphSegment) {
+    match chars.next() {
+        Some('p') => segment.tense = Some(Tense::Perfect),
+        Some('q') => segment.tense = Some(Tense::SequentialPerfect),
+        Some('i') => segment.tense = Some(Tense::Imperfect),
+        Some('w') => segment.tense = Some(Tense::SequentialImperfect),
+        Some('h') => segment.mood = Some(Mood::Cohortative),
+        Some('j') => segment.mood = Some(Mood::Jussive),
+        Some('v') => segment.mood = Some(Mood::Imperative),
+        Some('r') => {
+            segment.voice = Some(Voice::Active);
+            segment.mood = Some(Mood::Participle);
+        }
+        Some('s') => {
+            segment.voice = Some(Voice::Passive);
+            segment.mood = Some(Mood::Participle);
+        }
+        Some('a') => {
+            segment.mood = Some(Mood::Infinitive);
+            segment.state = Some(State::Absolute);
+        }
+        Some('c') => {
+            segment.mood = Some(Mood::Infinitive);
+            segment.state = Some(State::Construct);
+        }
+        _ => return,
+    }
+
+    match segment.mood {
+        Some(Mood::Participle) => parse_gender_number_state(chars, segment),
+        Some(Mood::Infinitive) => {}
+        _ => parse_person_gender_number(chars, segment),
+    }
+}
+
+fn parse_person_gender_number(chars: &mut std::str::Chars, segment: &mut MorphSegment) {
+    segment.person = chars.next().and_then(Person::from_digit);
+    segment.gender = chars.next().and_then(gender);
+    segment.number = chars.next().and_then(number);
+}
+
+fn parse_gender_number_state(chars: &mut std::str::Chars, segment: &mut MorphSegment) {
+    segment.gender = chars.next().and_then(gender);
+    segment.number = chars.next().and_then(number);
+    segment.state = chars.next().and_then(|c| match c {
+        'a' => Some(State::Absolute),
+        'c' => Some(State::Construct),
+        'd' => Some(State::Determined),
+        _ => None,
+    });
+}
+
+fn gender(c: char) -> Option<Gender> {
+    match c {
+        'm' => Some(Gender::Masculine),
+        'f' => Some(Gender::Feminine),
+        'b' => Some(Gender::Both),
+        'c' => Some(Gender::Common),
+        _ => None,
+    }
+}
+
+fn number(c: char) -> Option<Number> {
+    match c {
+        's' => Some(Number::Singular),
+        'p' => Some(Number::Plural),
+        'd' => Some(Number::Dual),
+        _ => None,
+    }
+}
@@ -0,0 +1,127 @@
+//! CCAT/Packard Greek morphology codes, as used by the LXX modules
+//! (`N1 NSF`, `V1 PAI3S`, `RA NSM`).
+
+use super::{
+    Degree, Language, MorphScheme, MorphSegment, Morphology, Mood, PartOfSpeech, Person, Tense,
+    Voice, greek_number, parse_case_number_gender,
+};
+
+pub fn parse(code: &str) -> Option<Morphology> {
+    // The type and the parsing are separated by a space in the CCAT files;
+    // OSIS attributes cannot hold spaces, so modules also use '+' or '.'.
+    let (head, parsing) = match code.split_once([' ', '+', '.', '_']) {
+        Some((head, parsing)) => (head, parsing.trim()),
+        None => (code, ""),
+    };
+
+    let mut segment = MorphSegment::default();
+
+    match head.chars().next()? {
+        'N' => {
+            segment.part_of_speech = Some(PartOfSpeech::Noun);
+            if let Some(declension) = head.chars().nth(1).and_then(declension_label) {
+                segment.notes.push(format!("{} declension", declension));
+            }
+            parse_nominal(parsing, &mut segment);
+        }
+        'A' => {
+            segment.part_of_speech = Some(PartOfSpeech::Adjective);
+            parse_nominal(parsing, &mut segment);
+        }
+        'R' => {
+            let (part_of_speech, kind) = match head.chars().nth(1) {
+                Some('A') => (PartOfSpeech::Article, None),
+                Some('P') => (PartOfSpeech::Pronoun, Some("personal")),
+                Some('R') => (PartOfSpeech::Pronoun, Some("relative")),
+                Some('D') => (PartOfSpeech::Pronoun, Some("demonstrative")),
+                Some('I') => (PartOfSpeech::Pronoun, Some("interrogative or indefinite")),
+                Some('X') => (PartOfSpeech::Pronoun, Some("indefinite relative")),
+                _ => (PartOfSpeech::Pronoun, None),
+            };
+            segment.part_of_speech = Some(part_of_speech);
+            segment.kind = kind.map(|k| k.to_string());
+            parse_nominal(parsing, &mut segment);
+        }
+        'V' => {
+            segment.part_of_speech = Some(PartOfSpeech::Verb);
+            parse_verb(parsing, &mut segment);
+        }
+        'P' => segment.part_of_speech = Some(PartOfSpeech::Preposition),
+        'C' => segment.part_of_speech = Some(PartOfSpeech::Conjunction),
+        'D' => segment.part_of_speech = Some(PartOfSpeech::Adverb),
+        'X' => segment.part_of_speech = Some(PartOfSpeech::Particle),
+        'I' => segment.part_of_speech = Some(PartOfSpeech::Interjection),
+        'M' => {
+            segment.part_of_speech = Some(PartOfSpeech::Numeral);
+            segment.notes.push("indeclinable".to_string());
+        }
+        _ => return None,
+    }
+
+    Some(Morphology {
+        scheme: MorphScheme::Packard,
+        code: code.to_string(),
+        language: Language::Greek,
+        segments: vec![segment],
+    })
+}
+
+fn declension_label(c: char) -> Option<&'static str> {
+    match c {
+        '1' => Some("first"),
+        '2' => Some("second"),
+        '3' => Some("third"),
+        _ => None,
+    }
+}
+
+/// `NSF`, `NSMC` (comparative), `GS` (personal pronouns have no gender)
+fn parse_nominal(parsing: &str, segment: &mut MorphSegment) {
+    parse_case_number_gender(parsing, segment);
+    segment.degree = parsing.chars().nth(3).and_then(|c| match c {
+        'C' => Some(Degree::Comparative),
+        'S' => Some(Degree::Superlative),
+        _ => None,
+    });
+}
+
+/// `PAI3S`, `AAN`, `XMPGPM`: tense, voice and mood, then either person and
+/// number or, for participles, case/number/gender.
+fn parse_verb(parsing: &str, segment: &mut MorphSegment) {
+    let mut chars = parsing.chars();
+
+    segment.tense = chars.next().and_then(|c| match c {
+        'P' => Some(Tense::Present),
+        'I' => Some(Tense::Imperfect),
+        'F' => Some(Tense::Future),
+        'A' => Some(Tense::Aorist),
+        'X' => Some(Tense::Perfect),
+        'Y' => Some(Tense::Pluperfect),
+        _ => None,
+    });
+    segment.voice = chars.next().and_then(|c| match c {
+        'A' => Some(Voice::Active),
+        'M' => Some(Voice::Middle),
+        'P' => Some(Voice::Passive),
+        'E' => Some(Voice::MiddleOrPassive),
+        _ => None,
+    });
+    segment.mood = chars.next().and_then(|c| match c {
+        'I' => Some(Mood::Indicative),
+        'D' => Some(Mood::Imperative),
+        'S' => Some(Mood::Subjunctive),
+        'O' => Some(Mood::Optative),
+        'N' => Some(Mood::Infinitive),
+        'P' => Some(Mood::Participle),
+        _ => None,
+    });
+
+    let rest = chars.as_str();
+    if segment.mood == Some(Mood::Participle) {
+        parse_case_number_gender(rest, segment);
+    } else {
+        let mut chars = rest.chars();
+        segment.person = chars.next().and_then(Person::from_digit);
+        segment.number = chars.next().and_then(greek_number);
+    }
+}
@@ -0,0 +1,200 @@
+//! Maurice Robinson's Greek morphology codes (`V-PAI-3S`, `N-NSM`, `P-1GS`).
+
+use super::{
+    Degree, Language, MorphScheme, MorphSegment, Morphology, Mood, PartOfSpeech, Person,
+    Tense, Voice, greek_number, parse_case_number_gender,
+};
+
+pub fn parse(code: &str) -> Option<Morphology> {
+    let mut parts = code.split('-');
+    let head = parts.next()?;
+    let rest: Vec<&str> = parts.collect();
+
+    let mut segment = MorphSegment::default();
+    let mut suffixes = rest.as_slice();
+
+    match head {
+        "N" | "A" | "T" => {
+            segment.part_of_speech = Some(match head {
+                "N" => PartOfSpeech::Noun,
+                "A" => PartOfSpeech::Adjective,
+                _ => PartOfSpeech::Article,
+            });
+            let parsing = suffixes.first().copied().unwrap_or("");
+            suffixes = suffixes.get(1..).unwrap_or(&[]);
+            match parsing {
+                "PRI" => {
+                    segment.kind = Some("proper".to_string());
+                    segment.notes.push("indeclinable".to_string());
+                }
+                "LI" => {
+                    segment.part_of_speech = Some(PartOfSpeech::Letter);
+                    segment.notes.push("indeclinable".to_string());
+                }
+                "OI" | "NUI" => {
+                    segment.part_of_speech = Some(PartOfSpeech::Numeral);
+                    segment.notes.push("indeclinable".to_string());
+                }
+                _ => parse_case_number_gender(parsing, &mut segment),
+            }
+        }
+        "P" | "R" | "C" | "D" | "K" | "I" | "X" | "Q" | "F" | "S" => {
+            segment.part_of_speech = Some(PartOfSpeech::Pronoun);
+            segment.kind = Some(pronoun_kind(head).to_string());
+            let parsing = suffixes.first().copied().unwrap_or("");
+            suffixes = suffixes.get(1..).unwrap_or(&[]);
+            parse_pronoun(head, parsing, &mut segment);
+        }
+        "V" => {
+            segment.part_of_speech = Some(PartOfSpeech::Verb);
+            let tvm = suffixes.first().copied().unwrap_or("");
+            suffixes = suffixes.get(1..).unwrap_or(&[]);
+            parse_tense_voice_mood(tvm, &mut segment);
+
+            if let Some(parsing) = suffixes.first().copied() {
+                if segment.mood == Some(Mood::Participle) {
+                    parse_case_number_gender(parsing, &mut segment);
+                    suffixes = &suffixes[1..];
+                } else if parsing.starts_with(['1', '2', '3']) {
+                    let mut chars = parsing.chars();
+                    segment.person = chars.next().and_then(Person::from_digit);
+                    segment.number = chars.next().and_then(greek_number);
+                    suffixes = &suffixes[1..];
+                }
+            }
+        }
+        "ADV" => segment.part_of_speech = Some(PartOfSpeech::Adverb),
+        "CONJ" => segment.part_of_speech = Some(PartOfSpeech::Conjunction),
+        "COND" => {
+            segment.part_of_speech = Some(PartOfSpeech::Conjunction);
+            segment.kind = Some("conditional".to_string());
+        }
+        "PREP" => segment.part_of_speech = Some(PartOfSpeech::Preposition),
+        "PRT" => segment.part_of_speech = Some(PartOfSpeech::Particle),
+        "INJ" => segment.part_of_speech = Some(PartOfSpeech::Interjection),
+        "ARAM" => {
+            segment.part_of_speech = Some(PartOfSpeech::Foreign);
+            segment.kind = Some("Aramaic".to_string());
+        }
+        "HEB" => {
+            segment.part_of_speech = Some(PartOfSpeech::Foreign);
+            segment.kind = Some("Hebrew".to_string());
+        }
+        _ => return None,
+    }
+
+    for suffix in suffixes {
+        apply_suffix(suffix, &mut segment);
+    }
+
+    Some(Morphology {
+        scheme: MorphScheme::Robinson,
+        code: code.to_string(),
+        language: Language::Greek,
+        segments: vec![segment],
+    })
+}
+
+fn pronoun_kind(head: &str) -> &'static str {
+    match head {
+        "P" => "personal",
+        "R" => "relative",
+        "C" => "reciprocal",
+        "D" => "demonstrative",
+        "K" => "correlative",
+        "I" => "interrogative",
+        "X" => "indefinite",
+        "Q" => "correlative or interrogative",
+        "F" => "reflexive",
+        _ => "possessive",
+    }
+}
+
+/// `1NS` (first/second person, no gender), `NSM` (third person personal and
+/// the other pronouns), `3ASM` (reflexive), `1SNSM` (possessive: person and
+/// number of the possessor, then case/number/gender of the thing possessed).
+fn parse_pronoun(head: &str, parsing: &str, segment: &mut MorphSegment) {
+    let mut rest = parsing;
+
+    if let Some(person) = rest.chars().next().and_then(Person::from_digit) {
+        segment.person = Some(person);
+        rest = &rest[1..];
+
+        if head == "S"
+            && let Some(possessor) = rest.chars().next().and_then(greek_number)
+        {
+            segment
+                .notes
+                .push(format!("{} possessor", possessor.label()));
+            rest = &rest[1..];
+        }
+    } else if head == "P" {
+        segment.person = Some(Person::Third);
+    }
+
+    parse_case_number_gender(rest, segment);
+}
+
+/// `PAI`, `2AAI`, `AAP`, `PNN`: optional "second" marker, then tense,
+/// voice and mood letters.
+fn parse_tense_voice_mood(tvm: &str, segment: &mut MorphSegment) {
+    let mut chars = tvm.chars().peekable();
+
+    let second = chars.next_if_eq(&'2').is_some();
+
+    segment.tense = chars.next().and_then(|c| match c {
+        'P' => Some(Tense::Present),
+        'I' => Some(Tense::Imperfect),
+        'F' => Some(Tense::Future),
+        'A' => Some(Tense::Aorist),
+        'R' => Some(Tense::Perfect),
+        'L' => Some(Tense::Pluperfect),
+        _ => None,
+    });
+    if second && let Some(tense) = segment.tense {
+        segment.notes.push(format!("second {}", tense.label()));
+    }
+
+    segment.voice = chars.next().and_then(|c| match c {
+        'A' => Some(Voice::Active),
+        'M' => Some(Voice::Middle),
+        'P' => Some(Voice::Passive),
+        'E' => Some(Voice::MiddleOrPassive),
+        'D' => Some(Voice::MiddleDeponent),
+        'O' => Some(Voice::PassiveDeponent),
+        'N' => Some(Voice::MiddleOrPassiveDeponent),
+        'Q' => Some(Voice::ImpersonalActive),
+        _ => None,
+    });
+
+    segment.mood = chars.next().and_then(|c| match c {
+        'I' => Some(Mood::Indicative),
+        'S' => Some(Mood::Subjunctive),
+        'O' => Some(Mood::Optative),
+        'M' => Some(Mood::Imperative),
+        'N' => Some(Mood::Infinitive),
+        'P' => Some(Mood::Participle),
+        'R' => {
+            segment.notes.push("imperative sense".to_string());
+            Some(Mood::Participle)
+        }
+        _ => None,
+    });
+}
+
+fn apply_suffix(suffix: &str, segment: &mut MorphSegment) {
+    match suffix {
+        "C" => segment.degree = Some(Degree::Comparative),
+        "S" => segment.degree = Some(Degree::Superlative),
+        "N" => segment.kind = Some("negative".to_string()),
+        "I" => segment.kind = Some("interrogative".to_string()),
+        "ATT" => segment.notes.push("Attic form".to_string()),
+        "ABB" => segment.notes.push("abbreviated".to_string()),
+        "K" => segment.notes.push("contracted (crasis)".to_string()),
+        "M" => segment.notes.push("middle significance".to_string()),
+        "P" => segment.notes.push("particle attached".to_string()),
+        "L" => segment.notes.push("location".to_string()),
+        "T" => segment.notes.push("title".to_string()),
+        _ => {}
+    }
+}
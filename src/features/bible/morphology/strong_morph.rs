@@ -0,0 +1,36 @@
+//! Strong's tense/voice/mood numbers (`TH8804`, `TG5719`) used by the KJV.
+//!
+//! The numbers index a separate table of verb forms, so all we can say
+//! without that table is that the word is a verb and which code it carries.
+
+use super::{Language, MorphScheme, MorphSegment, Morphology, PartOfSpeech};
+
+pub fn parse(code: &str) -> Option<Morphology> {
+    let (language, number) = if let Some(number) = code.strip_prefix("TH") {
+        (Language::Hebrew, number)
+    } else if let Some(number) = code.strip_prefix("TG") {
+        (Language::Greek, number)
+    } else {
+        return None;
+    };
+
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let language_label = match language {
+        Language::Greek => "Greek",
+        _ => "Hebrew",
+    };
+
+    Some(Morphology {
+        scheme: MorphScheme::StrongMorph,
+        code: code.to_string(),
+        language,
+        segments: vec![MorphSegment {
+            part_of_speech: Some(PartOfSpeech::Verb),
+            notes: vec![format!("{} tense/voice/mood code {}", language_label, number)],
+            ..Default::default()
+        }],
+    })
+}
@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::features::{
+    bible::{
+        model::{BibleVersion, Book, Verse},
+        transilation_engines::{
+            osis_engine::document::OsisDocument,
+            usfm_engine::{usfm::UsfmParser, usx::UsxParser},
+            vpl_engine::core::VplEngine,
+            zefania_engine::core::ZefaniaEngine,
+        },
+    },
+    core::module_engine::sword_module::{ModuleBook, ModuleChapter},
+};
+
+/// A Bible read from a local file rather than an installed SWORD module.
+/// The Study page lists these after the SWORD modules and renders them with
+/// the same `BiblePage`.
+pub trait DocumentBible: std::fmt::Debug + Send + Sync {
+    /// Name shown in the module dropdown
+    fn name(&self) -> String;
+
+    /// Book/chapter outline in the same shape `SwordEngine::get_bible_structure` returns
+    fn structure(&self) -> Vec<ModuleBook>;
+
+    /// Reads one chapter. Accepts "Gen 1", "Gen.1", "Gen 1:3" or "Gen.1.3".
+    fn read_chapter(&self, reference: &str) -> Vec<Verse>;
+
+    /// Every book with its verses, for exporting or compiling the document
+    fn books(&self) -> Vec<Book>;
+
+    fn version(&self) -> Option<&BibleVersion>;
+}
+
+/// Opens any supported file format, choosing the reader from the file
+/// extension and, for XML, the root element.
+pub fn open_document(path: &Path) -> Result<Arc<dyn DocumentBible>, String> {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "usfm" | "sfm" | "ptx" => Ok(Arc::new(UsfmParser::parse_file(path)?)),
+        "usx" => Ok(Arc::new(UsxParser::parse_file(path)?)),
+        "vpl" | "txt" => Ok(Arc::new(VplEngine::parse_file(path)?)),
+        "xml" => {
+            let head = read_head(path)?;
+            if head.contains("<usx") {
+                Ok(Arc::new(UsxParser::parse_file(path)?))
+            } else if head.contains("<XMLBIBLE") || head.contains("<xmlbible") {
+                Ok(Arc::new(ZefaniaEngine::parse_file(path)?))
+            } else {
+                Ok(Arc::new(OsisDocument::open(path)?))
+            }
+        }
+        _ => Err(format!("Unsupported file type: {}", path.display())),
+    }
+}
+
+/// First few kilobytes of a file, enough to sniff an XML root element
+fn read_head(path: &Path) -> Result<String, String> {
+    use std::io::Read;
+
+    let mut buf = vec![0; 4096];
+    let mut file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let read = file.read(&mut buf).map_err(|e| e.to_string())?;
+    Ok(String::from_utf8_lossy(&buf[..read]).into_owned())
+}
+
+/// A document whose books are fully parsed into memory (USFM, USX, ...).
+#[derive(Debug)]
+pub struct InMemoryBible {
+    pub path: PathBuf,
+    pub version: BibleVersion,
+    pub books: Vec<Book>,
+}
+
+impl InMemoryBible {
+    pub fn new(path: &Path, books: Vec<Book>) -> Result<Self, String> {
+        if books.is_empty() {
+            return Err(format!("{} contains no books", path.display()));
+        }
+
+        let name = path
+            .file_stem()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let scope = match (books.first(), books.last()) {
+            (Some(first), Some(last)) if books.len() > 1 => {
+                format!("{}-{}", first.osis_id, last.osis_id)
+            }
+            (Some(first), _) => first.osis_id.clone(),
+            _ => String::new(),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            version: BibleVersion {
+                osis_id: name.clone(),
+                title: name.clone(),
+                identifier: format!("Bible.{}", name),
+                scope,
+                ref_system: "Bible".to_string(),
+            },
+            books,
+        })
+    }
+}
+
+impl DocumentBible for InMemoryBible {
+    fn name(&self) -> String {
+        self.version.title.clone()
+    }
+
+    fn structure(&self) -> Vec<ModuleBook> {
+        book_structure(&self.books)
+    }
+
+    fn read_chapter(&self, reference: &str) -> Vec<Verse> {
+        let Some(osis_ref) = chapter_osis_ref(&self.books, reference) else {
+            return Vec::new();
+        };
+        self.books
+            .iter()
+            .flat_map(|b| b.chapters.iter())
+            .find(|c| c.osis_ref == osis_ref)
+            .map(|c| c.verses.clone())
+            .unwrap_or_default()
+    }
+
+    fn books(&self) -> Vec<Book> {
+        self.books.clone()
+    }
+
+    fn version(&self) -> Option<&BibleVersion> {
+        Some(&self.version)
+    }
+}
+
+pub(crate) fn book_structure(books: &[Book]) -> Vec<ModuleBook> {
+    books
+        .iter()
+        .map(|book| ModuleBook {
+            name: book.osis_id.clone(),
+            chapters: book
+                .chapters
+                .iter()
+                .enumerate()
+                .map(|(i, chapter)| ModuleChapter {
+                    number: chapter.number.parse().unwrap_or(i as i32 + 1),
+                    verse_count: chapter.verses.len() as i32,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// "Gen 1", "Gen.1.3" or "Genesis 1:3" -> "Gen.1", matched against the
+/// books' OSIS ids and titles.
+pub(crate) fn chapter_osis_ref(books: &[Book], reference: &str) -> Option<String> {
+    let reference = reference.split('-').next()?.trim();
+    let (book, rest) = reference
+        .rsplit_once(' ')
+        .or_else(|| reference.split_once('.'))?;
+    let chapter = rest.split([':', '.']).next()?;
+
+    let book = books
+        .iter()
+        .find(|b| b.osis_id == book || b.title == book)?;
+    Some(format!("{}.{}", book.osis_id, chapter))
+}
@@ -0,0 +1,200 @@
+use crate::features::bible::model::{
+    BlockKind, Book, Chapter, LexicalInfo, Note, NoteKind, Verse, VerseBuilder, Word,
+};
+
+/// Character-level styling active while text is being read
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TextStyle {
+    pub added: bool,
+    pub red: bool,
+    pub italic: bool,
+    pub bold: bool,
+}
+
+/// Accumulates books, chapters and verses from a streaming reader.
+///
+/// Format readers (USFM, USX, Zefania, VPL) call it in document order.
+/// Headings and paragraph/poetry markers are held back until the next word
+/// or verse so that a break between two verses opens the later one instead
+/// of trailing the earlier one.
+#[derive(Debug, Default)]
+pub struct BibleBuilder {
+    books: Vec<Book>,
+    verse: Option<VerseBuilder>,
+    notes_in_verse: usize,
+    pending_blocks: Vec<BlockKind>,
+    note: Option<Note>,
+}
+
+impl BibleBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start_book(&mut self, osis_id: &str, title: &str) {
+        self.finish_verse();
+        self.pending_blocks.clear();
+        self.books.push(Book::new(osis_id, title));
+    }
+
+    /// Replaces the current book's title, e.g. from a running header
+    pub fn set_book_title(&mut self, title: &str) {
+        if let Some(book) = self.books.last_mut()
+            && !title.trim().is_empty()
+        {
+            book.title = title.trim().to_string();
+        }
+    }
+
+    pub fn start_chapter(&mut self, number: i32) {
+        self.finish_verse();
+        if let Some(book) = self.books.last_mut() {
+            let chapter = Chapter::new(&book.osis_id, number);
+            book.chapters.push(chapter);
+        }
+    }
+
+    pub fn start_verse(&mut self, number: i32) {
+        self.finish_verse();
+        let Some(chapter) = self.books.last().and_then(|b| b.chapters.last()) else {
+            return;
+        };
+
+        let osis_id = format!("{}.{}", chapter.osis_ref, number);
+        let is_paragraph_start = self.pending_blocks.contains(&BlockKind::Paragraph);
+        let mut verse = Verse::builder(osis_id, number).paragraph_start(is_paragraph_start);
+        for kind in self.pending_blocks.drain(..) {
+            verse = verse.block(kind);
+        }
+        self.verse = Some(verse);
+    }
+
+    /// Paragraph, poetry line, line break, heading or psalm title
+    pub fn block(&mut self, kind: BlockKind) {
+        self.pending_blocks.push(kind);
+    }
+
+    pub fn has_verse(&self) -> bool {
+        self.verse.is_some()
+    }
+
+    /// Splits running text into words; inside an open note it becomes note text.
+    pub fn text(&mut self, text: &str, style: TextStyle, lex: Option<&LexicalInfo>) {
+        if let Some(note) = self.note.as_mut() {
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !text.is_empty() {
+                if !note.text.is_empty() {
+                    note.text.push(' ');
+                }
+                note.text.push_str(&text);
+            }
+            return;
+        }
+
+        for part in text.split_whitespace() {
+            let mut word = part.to_string();
+            let mut punctuation = String::new();
+            while let Some(c) = word.chars().last() {
+                if c.is_ascii_punctuation() && c != '\'' {
+                    punctuation.insert(0, word.pop().unwrap());
+                } else {
+                    break;
+                }
+            }
+
+            if !word.is_empty() {
+                self.word(
+                    Word::builder(word)
+                        .added(style.added)
+                        .red(style.red)
+                        .italic(style.italic)
+                        .bold(style.bold)
+                        .lex(lex.cloned())
+                        .build(),
+                );
+            }
+            if !punctuation.is_empty() {
+                self.word(
+                    Word::builder(punctuation)
+                        .red(style.red)
+                        .punctuation(true)
+                        .build(),
+                );
+            }
+        }
+    }
+
+    pub fn word(&mut self, word: Word) {
+        let Some(mut verse) = self.verse.take() else {
+            // Introductions and other text outside a verse are not kept
+            return;
+        };
+        for kind in self.pending_blocks.drain(..) {
+            verse = verse.block(kind);
+        }
+        self.verse = Some(verse.word(word));
+    }
+
+    /// Opens a footnote or cross-reference. Callers of "+" (automatic) or
+    /// "-" (none) get the next letter as marker.
+    pub fn open_note(&mut self, kind: NoteKind, caller: Option<&str>) {
+        self.note = Some(Note::new(
+            kind,
+            Note::default_marker(self.notes_in_verse),
+            0,
+        ));
+        if let Some(caller) = caller {
+            self.set_note_marker(caller);
+        }
+    }
+
+    /// Uses an explicit note caller as marker; "+" and "-" keep the default
+    pub fn set_note_marker(&mut self, caller: &str) {
+        let caller = caller.trim();
+        if let Some(note) = self.note.as_mut()
+            && !caller.is_empty()
+            && caller != "+"
+            && caller != "-"
+        {
+            note.marker = caller.to_string();
+        }
+    }
+
+    pub fn note_target(&mut self, osis_ref: &str) {
+        if let Some(note) = self.note.as_mut()
+            && !osis_ref.trim().is_empty()
+        {
+            note.targets.push(osis_ref.trim().to_string());
+        }
+    }
+
+    pub fn in_note(&self) -> bool {
+        self.note.is_some()
+    }
+
+    pub fn close_note(&mut self) {
+        let Some(note) = self.note.take() else {
+            return;
+        };
+        if let Some(verse) = self.verse.take() {
+            self.notes_in_verse += 1;
+            self.verse = Some(verse.note(note));
+        }
+    }
+
+    pub fn finish(mut self) -> Vec<Book> {
+        self.finish_verse();
+        self.books
+    }
+
+    fn finish_verse(&mut self) {
+        self.close_note();
+        self.notes_in_verse = 0;
+        let Some(verse) = self.verse.take() else {
+            return;
+        };
+        if let Some(chapter) = self.books.last_mut().and_then(|b| b.chapters.last_mut()) {
+            chapter.verses.push(verse.build());
+        }
+    }
+}
@@ -1 +1,6 @@
-pub mod  osis_engine;
\ No newline at end of file
+pub mod document;
+pub mod import;
+pub mod osis_engine;
+pub mod usfm_engine;
+pub mod vpl_engine;
+pub mod zefania_engine;
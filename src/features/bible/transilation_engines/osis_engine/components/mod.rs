@@ -1,3 +0,0 @@
-pub mod page;
-pub mod verse;
-pub mod word;
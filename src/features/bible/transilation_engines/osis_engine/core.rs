@@ -1,8 +1,12 @@
-use crate::features::bible::transilation_engines::osis_engine::helpers::{
-    BibleVersion, Book, Chapter, LexicalInfo, SegmentStyle, Verse, Word,
+use crate::features::bible::model::{
+    BibleVersion, BlockKind, Book, Chapter, LexicalInfo, Note, NoteKind, SegmentStyle, Verse,
+    VerseBlock, Word,
 };
 use quick_xml::{Reader, events::Event};
 
+/// A `<title>` or colophon being collected: the block it becomes and its text so far
+type PendingBlock = Option<(fn(String) -> BlockKind, String)>;
+
 pub struct OsisEngine;
 
 impl OsisEngine {
@@ -20,6 +24,7 @@ impl OsisEngine {
 
         let mut inside_target_chapter = false;
         let mut inside_paragraph = false;
+        let mut block_text: PendingBlock = None;
 
         loop {
             match reader.read_event_into(&mut buf) {
@@ -39,6 +44,36 @@ impl OsisEngine {
                             }
                         }
                         b"p" => inside_paragraph = true,
+                        b"milestone"
+                            if Self::get_attr(reader, e, b"type").as_deref() == Some("x-p") =>
+                        {
+                            match current_verse.as_mut() {
+                                Some(v) if !v.words.is_empty() || !v.blocks.is_empty() => {
+                                    v.blocks.push(VerseBlock {
+                                        kind: BlockKind::Paragraph,
+                                        anchor: v.words.len(),
+                                    });
+                                }
+                                Some(v) => v.is_paragraph_start = true,
+                                None => inside_paragraph = true,
+                            }
+                        }
+                        b"title" if inside_target_chapter && current_verse.is_some() => {
+                            let kind: fn(String) -> BlockKind =
+                                match Self::get_attr(reader, e, b"type").as_deref() {
+                                    Some("psalm") => BlockKind::Superscription,
+                                    _ => BlockKind::Heading,
+                                };
+                            block_text = Some((kind, String::new()));
+                        }
+                        b"div"
+                            if inside_target_chapter
+                                && current_verse.is_some()
+                                && Self::get_attr(reader, e, b"type").as_deref()
+                                    == Some("colophon") =>
+                        {
+                            block_text = Some((BlockKind::Colophon, String::new()));
+                        }
                         b"verse" if inside_target_chapter => {
                             let sid = Self::get_attr(reader, e, b"sID");
                             let eid = Self::get_attr(reader, e, b"eID");
@@ -46,37 +81,83 @@ impl OsisEngine {
 
                             if let Some(id) = sid.or(osis.clone()) {
                                 let number = Self::get_attr(reader, e, b"n")
+                                    .and_then(|n| n.parse().ok())
                                     .unwrap_or_else(|| Self::verse_number_from_osis(&id));
                                 current_verse = Some(Verse {
                                     osis_id: id,
-                                    number: number,
+                                    number,
                                     words: Vec::new(),
                                     notes: Vec::new(),
+                                    blocks: Vec::new(),
                                     is_paragraph_start: inside_paragraph,
                                 });
                                 inside_paragraph = false;
-                            } else if eid.is_some() {
-                                if let Some(v) = current_verse.take() {
-                                    verses.push(v);
-                                }
+                            } else if eid.is_some()
+                                && let Some(v) = current_verse.take()
+                            {
+                                verses.push(v);
                             }
                         }
                         b"q" if inside_target_chapter => {
-                            if Self::get_attr(reader, e, b"who").as_deref() == Some("Jesus") {
-                                style_stack.push(SegmentStyle::RedLetter);
-                            }
+                            // Every <q> is pushed so its end tag pops the right entry
+                            let style =
+                                if Self::get_attr(reader, e, b"who").as_deref() == Some("Jesus") {
+                                    SegmentStyle::RedLetter
+                                } else {
+                                    *style_stack.last().unwrap_or(&SegmentStyle::Plain)
+                                };
+                            style_stack.push(style);
                         }
                         b"transChange" if inside_target_chapter => {
                             style_stack.push(SegmentStyle::Added);
                         }
                         b"note" if inside_target_chapter => {
                             style_stack.push(SegmentStyle::Note);
+                            if let Some(v) = current_verse.as_mut() {
+                                let kind = match Self::get_attr(reader, e, b"type").as_deref() {
+                                    Some("crossReference") => NoteKind::CrossReference,
+                                    _ => NoteKind::Footnote,
+                                };
+                                let marker = Self::get_attr(reader, e, b"n")
+                                    .unwrap_or_else(|| Note::default_marker(v.notes.len()));
+                                v.notes.push(Note::new(kind, marker, v.words.len()));
+                            }
+                        }
+                        b"reference" if style_stack.contains(&SegmentStyle::Note) => {
+                            if let (Some(v), Some(target)) = (
+                                current_verse.as_mut(),
+                                Self::get_attr(reader, e, b"osisRef"),
+                            ) && let Some(note) = v.notes.last_mut()
+                            {
+                                note.targets.push(target);
+                            }
+                        }
+                        b"l" | b"lb" if inside_target_chapter => {
+                            if let Some(v) = current_verse.as_mut() {
+                                let kind = if name.as_ref() == b"l" {
+                                    let level = Self::get_attr(reader, e, b"level")
+                                        .and_then(|l| l.parse().ok())
+                                        .unwrap_or(1);
+                                    BlockKind::PoetryLine(level)
+                                } else {
+                                    BlockKind::LineBreak
+                                };
+                                // Closing milestones (eID) end a line rather than start one
+                                if Self::get_attr(reader, e, b"eID").is_none() {
+                                    v.blocks.push(VerseBlock {
+                                        kind,
+                                        anchor: v.words.len(),
+                                    });
+                                }
+                            }
                         }
                         b"w" if inside_target_chapter => {
                             current_lex = Some(LexicalInfo {
                                 strongs: Self::parse_strongs(Self::get_attr(reader, e, b"lemma")),
-                                morph: Self::parse_morph(Self::get_attr(reader, e, b"morph")),
-                                lemma: None,
+                                lemma: Self::parse_lemma(Self::get_attr(reader, e, b"lemma")),
+                                gloss: Self::get_attr(reader, e, b"gloss"),
+                                morph: Self::get_attr(reader, e, b"morph"),
+                                ..Default::default()
                             });
                         }
                         _ => {}
@@ -85,18 +166,35 @@ impl OsisEngine {
                 Ok(Event::Text(e)) if inside_target_chapter => {
                     if let Some(v) = current_verse.as_mut() {
                         let text = reader.decoder().decode(e.as_ref()).unwrap_or_default();
-                        if style_stack.contains(&SegmentStyle::Note) {
-                            v.notes.push(text.clone().to_string());
-                        } else {
-                            Self::process_text_into_words(v, &text, &style_stack, &current_lex);
-                            current_lex = None;
-                        }
+                        Self::push_text(v, &text, &style_stack, &mut current_lex, &mut block_text);
+                    }
+                }
+                Ok(Event::GeneralRef(e)) if inside_target_chapter => {
+                    // Entities arrive separately from the surrounding text
+                    let entity = reader.decoder().decode(e.as_ref()).unwrap_or_default();
+                    let resolved = match e.resolve_char_ref() {
+                        Ok(Some(c)) => Some(c.to_string()),
+                        _ => quick_xml::escape::resolve_xml_entity(&entity).map(str::to_string),
+                    };
+                    if let (Some(v), Some(text)) = (current_verse.as_mut(), resolved) {
+                        Self::push_text(v, &text, &style_stack, &mut current_lex, &mut block_text);
                     }
                 }
                 Ok(Event::End(ref e)) if inside_target_chapter => match e.name().as_ref() {
                     b"q" | b"transChange" | b"note" => {
                         style_stack.pop();
                     }
+                    b"title" | b"div" => {
+                        if let (Some((kind, text)), Some(v)) =
+                            (block_text.take(), current_verse.as_mut())
+                            && !text.is_empty()
+                        {
+                            v.blocks.push(VerseBlock {
+                                kind: kind(text),
+                                anchor: v.words.len(),
+                            });
+                        }
+                    }
                     _ => {}
                 },
                 Ok(Event::Eof) => break,
@@ -151,21 +249,20 @@ impl OsisEngine {
                     }
                 }
                 Ok(Event::Text(e)) => {
-                    if let (Some(tag), Some(book)) = (current_tag.as_ref(), current_book.as_mut()) {
-                        if tag == "title" && book.title.is_empty() {
-                            // FIX: Consistent decoder use
-                            book.title = reader
-                                .decoder()
-                                .decode(e.as_ref())
-                                .unwrap_or_default()
-                                .into_owned();
-                        }
+                    if let (Some(tag), Some(book)) = (current_tag.as_ref(), current_book.as_mut())
+                        && tag == "title"
+                        && book.title.is_empty()
+                    {
+                        // FIX: Consistent decoder use
+                        book.title = reader
+                            .decoder()
+                            .decode(e.as_ref())
+                            .unwrap_or_default()
+                            .into_owned();
                     }
                 }
-                Ok(Event::End(ref e)) => {
-                    if e.name().as_ref() == b"title" {
-                        current_tag = None;
-                    }
+                Ok(Event::End(ref e)) if e.name().as_ref() == b"title" => {
+                    current_tag = None;
                 }
                 Ok(Event::Eof) => break,
                 _ => {}
@@ -292,6 +389,32 @@ impl OsisEngine {
             })
     }
 
+    /// Routes text to the open title or note, or splits it into words
+    fn push_text(
+        v: &mut Verse,
+        text: &str,
+        styles: &[SegmentStyle],
+        lex: &mut Option<LexicalInfo>,
+        block_text: &mut PendingBlock,
+    ) {
+        if let Some((_, block)) = block_text.as_mut() {
+            if !block.is_empty() {
+                block.push(' ');
+            }
+            block.push_str(text.trim());
+        } else if styles.contains(&SegmentStyle::Note) {
+            if let Some(note) = v.notes.last_mut() {
+                if !note.text.is_empty() {
+                    note.text.push(' ');
+                }
+                note.text.push_str(text.trim());
+            }
+        } else {
+            Self::process_text_into_words(v, text, styles, lex);
+            *lex = None;
+        }
+    }
+
     fn process_text_into_words(
         v: &mut Verse,
         raw_text: &str,
@@ -313,26 +436,19 @@ impl OsisEngine {
                 }
             }
 
-            v.words.push(Word {
-                text: word_str,
-                style,
-                is_red,
-                lex: lex.clone(),
-                is_first_in_group: false,
-                is_last_in_group: false,
-                is_punctuation: false,
-            });
+            if !word_str.is_empty() {
+                v.words.push(
+                    Word::builder(word_str)
+                        .style(style)
+                        .red(is_red)
+                        .lex(lex.clone())
+                        .build(),
+                );
+            }
 
             if !punc.is_empty() {
-                v.words.push(Word {
-                    text: punc,
-                    style: SegmentStyle::Plain,
-                    is_red,
-                    lex: None,
-                    is_first_in_group: false,
-                    is_last_in_group: false,
-                    is_punctuation: true,
-                });
+                v.words
+                    .push(Word::builder(punc).red(is_red).punctuation(true).build());
             }
         }
     }
@@ -345,11 +461,85 @@ impl OsisEngine {
             .collect()
     }
 
-    fn parse_morph(attr: Option<String>) -> Option<String> {
-        attr.and_then(|s| s.strip_prefix("strongMorph:").map(|m| m.to_string()))
+    fn parse_lemma(attr: Option<String>) -> Option<String> {
+        attr.unwrap_or_default()
+            .split_whitespace()
+            .find_map(|s| {
+                s.strip_prefix("lemma.TR:")
+                    .or_else(|| s.strip_prefix("lemma:"))
+            })
+            .map(|s| s.to_string())
+    }
+
+    fn verse_number_from_osis(osis_id: &str) -> i32 {
+        osis_id
+            .rsplit('.')
+            .next()
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PSALMS: &str = r#"<osis><osisText osisIDWork="Sample">
+<header><work osisWork="Sample"><title>Sample Psalter</title><refSystem>Bible.KJV</refSystem></work></header>
+<div type="book" osisID="Ps"><title>Psalms</title>
+<chapter osisID="Ps.22" n="22"><verse sID="Ps.22.1" osisID="Ps.22.1" n="1"/>My God, my God.<verse eID="Ps.22.1"/></chapter>
+<chapter osisID="Ps.23" n="23">
+<verse sID="Ps.23.1" osisID="Ps.23.1" n="1"/><title type="psalm">A Psalm of David.</title><l level="1">The LORD is my shepherd;</l><l level="2">I shall not want.<note n="a">Or lack nothing</note></l><verse eID="Ps.23.1"/>
+<verse sID="Ps.23.2" osisID="Ps.23.2" n="2"/><milestone type="x-p"/>He maketh me to lie down.<verse eID="Ps.23.2"/>
+</chapter></div></osisText></osis>"#;
+
+    fn reader() -> Reader<&'static [u8]> {
+        let mut reader = Reader::from_str(PSALMS);
+        reader.config_mut().trim_text(true);
+        reader
+    }
+
+    #[test]
+    fn reads_the_header_and_outline() {
+        let version = OsisEngine::parse_version(&mut reader()).unwrap();
+        assert_eq!(version.osis_id, "Sample");
+        assert_eq!(version.title, "Sample Psalter");
+
+        let books = OsisEngine::parse_books(&mut reader());
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].title, "Psalms");
+        let chapters: Vec<&str> = books[0]
+            .chapters
+            .iter()
+            .map(|c| c.osis_ref.as_str())
+            .collect();
+        assert_eq!(chapters, ["Ps.22", "Ps.23"]);
     }
 
-    fn verse_number_from_osis(osis_id: &str) -> String {
-        osis_id.rsplit('.').next().unwrap_or_default().to_string()
+    #[test]
+    fn reads_blocks_and_notes_of_one_chapter() {
+        let verses = OsisEngine::parse_verses(&mut reader(), "Ps.23");
+        assert_eq!(verses.len(), 2);
+
+        let first = &verses[0];
+        assert_eq!(first.words.len(), 11);
+        let blocks: Vec<(&BlockKind, usize)> =
+            first.blocks.iter().map(|b| (&b.kind, b.anchor)).collect();
+        assert_eq!(
+            blocks,
+            [
+                (&BlockKind::Superscription("A Psalm of David.".into()), 0),
+                (&BlockKind::PoetryLine(1), 0),
+                (&BlockKind::PoetryLine(2), 6),
+            ]
+        );
+        assert_eq!(first.notes.len(), 1);
+        assert_eq!(first.notes[0].kind, NoteKind::Footnote);
+        assert_eq!(first.notes[0].marker, "a");
+        assert_eq!(first.notes[0].anchor, 11);
+        assert_eq!(first.notes[0].text, "Or lack nothing");
+
+        assert!(verses[1].is_paragraph_start);
+        assert_eq!(verses[1].osis_id, "Ps.23.2");
     }
 }
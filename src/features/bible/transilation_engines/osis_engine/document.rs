@@ -0,0 +1,90 @@
+use quick_xml::Reader;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use crate::features::{
+    bible::{
+        model::{BibleVersion, Book, Verse},
+        transilation_engines::{
+            document::{DocumentBible, book_structure, chapter_osis_ref},
+            osis_engine::core::OsisEngine,
+        },
+    },
+    core::module_engine::sword_module::ModuleBook,
+};
+
+/// A standalone OSIS XML file opened read-only as a "document Bible".
+///
+/// Only the header and book/chapter outline are kept in memory; chapters
+/// are streamed from disk through `OsisEngine::parse_verses` when read.
+#[derive(Debug)]
+pub struct OsisDocument {
+    pub path: PathBuf,
+    pub version: Option<BibleVersion>,
+    pub books: Vec<Book>,
+}
+
+impl OsisDocument {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let version = OsisEngine::parse_version(&mut Self::reader(path)?);
+        let books = OsisEngine::parse_books(&mut Self::reader(path)?);
+        if books.is_empty() {
+            return Err(format!("{} contains no OSIS books", path.display()));
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            version,
+            books,
+        })
+    }
+
+    fn reader(path: &Path) -> Result<Reader<BufReader<std::fs::File>>, String> {
+        let mut reader = Reader::from_file(path).map_err(|e| e.to_string())?;
+        reader.config_mut().trim_text(true);
+        Ok(reader)
+    }
+}
+
+impl DocumentBible for OsisDocument {
+    fn name(&self) -> String {
+        match self.version.as_ref() {
+            Some(version) if !version.title.is_empty() => version.title.clone(),
+            Some(version) if !version.osis_id.is_empty() => version.osis_id.clone(),
+            _ => self
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn structure(&self) -> Vec<ModuleBook> {
+        book_structure(&self.books)
+    }
+
+    fn read_chapter(&self, reference: &str) -> Vec<Verse> {
+        let Some(osis_ref) = chapter_osis_ref(&self.books, reference) else {
+            return Vec::new();
+        };
+        match Self::reader(&self.path) {
+            Ok(mut reader) => OsisEngine::parse_verses(&mut reader, &osis_ref),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Streams every chapter in turn, since only the outline is kept in memory
+    fn books(&self) -> Vec<Book> {
+        let mut books = self.books.clone();
+        for book in books.iter_mut() {
+            for chapter in book.chapters.iter_mut() {
+                chapter.verses = self.read_chapter(&chapter.osis_ref);
+            }
+        }
+        books
+    }
+
+    fn version(&self) -> Option<&BibleVersion> {
+        self.version.as_ref()
+    }
+}
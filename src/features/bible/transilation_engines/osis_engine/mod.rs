@@ -1,3 +1,3 @@
-pub mod components;
 pub mod core;
-pub mod helpers;
+pub mod document;
+pub mod writer;
@@ -0,0 +1,319 @@
+use crate::features::bible::{
+    model::{BlockKind, Book, LexicalInfo, NoteKind, SegmentStyle, Verse, Word},
+    transilation_engines::usfm_engine::books::resolve_book_name,
+};
+
+/// Inline spans a word can sit in, outermost first
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Span {
+    Red,
+    Added,
+    Italic,
+    Bold,
+}
+
+impl Span {
+    fn open_tag(&self) -> &'static str {
+        match self {
+            Span::Red => r#"<q who="Jesus" marker="">"#,
+            Span::Added => r#"<transChange type="added">"#,
+            Span::Italic => r#"<hi type="italic">"#,
+            Span::Bold => r#"<hi type="bold">"#,
+        }
+    }
+
+    fn close_tag(&self) -> &'static str {
+        match self {
+            Span::Red => "</q>",
+            Span::Added => "</transChange>",
+            Span::Italic | Span::Bold => "</hi>",
+        }
+    }
+}
+
+/// Serializes the shared model back into OSIS markup.
+pub struct OsisWriter;
+
+impl OsisWriter {
+    /// A complete OSIS document for `books`, laid out the way
+    /// `OsisEngine` reads it: book divs, chapter containers and verse milestones.
+    pub fn document(work: &str, title: &str, books: &[Book]) -> String {
+        let work = Self::escape(work);
+        let mut out = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <osis xmlns=\"http://www.bibletechnologies.net/2003/OSIS/namespace\">\n\
+             <osisText osisIDWork=\"{work}\" osisRefWork=\"Bible\">\n\
+             <header>\n<work osisWork=\"{work}\">\n<title>{}</title>\n\
+             <refSystem>Bible.KJV</refSystem>\n</work>\n</header>\n",
+            Self::escape(title)
+        );
+
+        for book in books {
+            let book_id = Self::escape(&book.osis_id);
+            out.push_str(&format!(
+                "<div type=\"book\" osisID=\"{book_id}\" canonical=\"{}\">\n",
+                book.canonical
+            ));
+            if !book.title.is_empty() {
+                out.push_str(&format!(
+                    "<title type=\"main\">{}</title>\n",
+                    Self::escape(&book.title)
+                ));
+            }
+
+            for chapter in &book.chapters {
+                let chapter_id = if chapter.osis_ref.is_empty() {
+                    format!("{}.{}", book.osis_id, chapter.number)
+                } else {
+                    Self::osis_id(&chapter.osis_ref)
+                };
+                out.push_str(&format!(
+                    "<chapter osisID=\"{}\" n=\"{}\">\n",
+                    Self::escape(&chapter_id),
+                    Self::escape(&chapter.number)
+                ));
+                for verse in &chapter.verses {
+                    let verse_id = Self::escape(&Self::osis_id(&verse.osis_id));
+                    out.push_str(&format!(
+                        "<verse sID=\"{verse_id}\" osisID=\"{verse_id}\" n=\"{}\"/>{}<verse eID=\"{verse_id}\"/>\n",
+                        verse.number,
+                        Self::verse_text(verse)
+                    ));
+                }
+                out.push_str("</chapter>\n");
+            }
+            out.push_str("</div>\n");
+        }
+
+        out.push_str("</osisText>\n</osis>\n");
+        out
+    }
+
+    /// OSIS form of a SWORD key: "1 John 3:16" -> "1John.3.16", "John 3" -> "John.3".
+    /// Ids already in OSIS form are returned unchanged.
+    pub fn osis_id(key: &str) -> String {
+        let Some((book, position)) = key.rsplit_once(' ') else {
+            return key.to_string();
+        };
+        let book = resolve_book_name(book)
+            .map(|(osis, _)| osis.to_string())
+            .unwrap_or_else(|| book.replace(' ', ""));
+        format!("{}.{}", book, position.replace(':', "."))
+    }
+
+    /// OSIS body of a single verse, as stored in a SWORD module entry.
+    /// Notes and blocks are written at their anchors; runs of red-letter,
+    /// added and highlighted words share one enclosing element.
+    pub fn verse_text(verse: &Verse) -> String {
+        let mut out = String::new();
+        let mut open: Vec<Span> = Vec::new();
+
+        if verse.is_paragraph_start
+            && !verse
+                .blocks
+                .iter()
+                .any(|b| b.anchor == 0 && b.kind == BlockKind::Paragraph)
+        {
+            out.push_str(r#"<milestone type="x-p" marker="¶"/>"#);
+        }
+
+        for (position, word) in verse.words.iter().enumerate() {
+            Self::write_anchored(&mut out, verse, position, &mut open);
+
+            let wanted = Self::spans_of(word);
+            let shared = open
+                .iter()
+                .zip(wanted.iter())
+                .take_while(|(a, b)| a == b)
+                .count();
+            while open.len() > shared {
+                out.push_str(open.pop().unwrap().close_tag());
+            }
+            if !out.is_empty() && !word.is_punctuation {
+                out.push(' ');
+            }
+            for span in &wanted[shared..] {
+                out.push_str(span.open_tag());
+                open.push(*span);
+            }
+
+            Self::write_word(&mut out, word);
+        }
+
+        Self::write_anchored(&mut out, verse, verse.words.len(), &mut open);
+        while let Some(span) = open.pop() {
+            out.push_str(span.close_tag());
+        }
+        out
+    }
+
+    /// Escapes text for use in element content and attribute values
+    pub fn escape(text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                _ => escaped.push(c),
+            }
+        }
+        escaped
+    }
+
+    /// Notes drawn after the preceding word, then blocks opening the next one
+    fn write_anchored(out: &mut String, verse: &Verse, position: usize, open: &mut Vec<Span>) {
+        for note in verse.notes.iter().filter(|n| n.anchor == position) {
+            let kind = match note.kind {
+                NoteKind::CrossReference => r#" type="crossReference""#,
+                NoteKind::Footnote => "",
+            };
+            out.push_str(&format!(
+                r#"<note{kind} n="{}">{}"#,
+                Self::escape(&note.marker),
+                Self::escape(&note.text)
+            ));
+            for target in &note.targets {
+                out.push_str(&format!(
+                    r#"<reference osisRef="{}"/>"#,
+                    Self::escape(target)
+                ));
+            }
+            out.push_str("</note>");
+        }
+
+        for block in verse.blocks.iter().filter(|b| b.anchor == position) {
+            // Structural elements never nest inside inline spans
+            while let Some(span) = open.pop() {
+                out.push_str(span.close_tag());
+            }
+            match &block.kind {
+                BlockKind::Heading(text) => {
+                    out.push_str(&format!("<title>{}</title>", Self::escape(text)));
+                }
+                BlockKind::Superscription(text) => out.push_str(&format!(
+                    r#"<title type="psalm" canonical="true">{}</title>"#,
+                    Self::escape(text)
+                )),
+                BlockKind::Colophon(text) => out.push_str(&format!(
+                    r#"<div type="colophon">{}</div>"#,
+                    Self::escape(text)
+                )),
+                BlockKind::Paragraph => out.push_str(r#"<milestone type="x-p" marker="¶"/>"#),
+                BlockKind::PoetryLine(level) => {
+                    out.push_str(&format!(r#"<l level="{level}"/>"#));
+                }
+                BlockKind::LineBreak => out.push_str("<lb/>"),
+            }
+        }
+    }
+
+    fn write_word(out: &mut String, word: &Word) {
+        let text = Self::escape(&word.text);
+        match word.lex.as_ref().and_then(Self::word_attributes) {
+            Some(attributes) => out.push_str(&format!("<w{attributes}>{text}</w>")),
+            None => out.push_str(&text),
+        }
+    }
+
+    /// `lemma`, `morph` and `gloss` attributes of a tagged word
+    fn word_attributes(lex: &LexicalInfo) -> Option<String> {
+        let mut lemma: Vec<String> = lex.strongs.iter().map(|s| format!("strong:{s}")).collect();
+        if let Some(word) = lex.lemma.as_deref() {
+            lemma.push(format!("lemma.TR:{word}"));
+        }
+
+        let mut attributes = String::new();
+        if !lemma.is_empty() {
+            attributes.push_str(&format!(r#" lemma="{}""#, Self::escape(&lemma.join(" "))));
+        }
+        if let Some(morph) = lex.morph.as_deref() {
+            attributes.push_str(&format!(r#" morph="{}""#, Self::escape(morph)));
+        }
+        if let Some(gloss) = lex.gloss.as_deref() {
+            attributes.push_str(&format!(r#" gloss="{}""#, Self::escape(gloss)));
+        }
+        (!attributes.is_empty()).then_some(attributes)
+    }
+
+    fn spans_of(word: &Word) -> Vec<Span> {
+        [
+            (word.is_red, Span::Red),
+            (word.style == SegmentStyle::Added, Span::Added),
+            (word.is_italic, Span::Italic),
+            (word.is_bold_text, Span::Bold),
+        ]
+        .into_iter()
+        .filter_map(|(on, span)| on.then_some(span))
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::bible::{
+        model::{LexicalInfo, Note},
+        transilation_engines::osis_engine::core::OsisEngine,
+    };
+    use quick_xml::Reader;
+
+    #[test]
+    fn verse_text_reads_back_through_the_osis_engine() {
+        let verse = Verse::builder("John.3.16", 16)
+            .word(Word::builder("For").build())
+            .word(
+                Word::builder("God")
+                    .red(true)
+                    .lex(Some(LexicalInfo::default().strong("G2316").lemma("θεός")))
+                    .build(),
+            )
+            .word(
+                Word::builder("so")
+                    .red(true)
+                    .style(SegmentStyle::Added)
+                    .build(),
+            )
+            .word(Word::builder("loved").red(true).build())
+            .note(Note::new(NoteKind::CrossReference, "a", 0).target("Rom.5.8"))
+            .word(Word::builder(",").red(true).punctuation(true).build())
+            .build();
+
+        let text = OsisWriter::verse_text(&verse);
+        assert_eq!(
+            text,
+            r#"For <q who="Jesus" marker=""><w lemma="strong:G2316 lemma.TR:θεός">God</w> <transChange type="added">so</transChange> loved<note type="crossReference" n="a"><reference osisRef="Rom.5.8"/></note>,</q>"#
+        );
+
+        let xml = format!(
+            r#"<chapter osisID="John.3"><verse sID="John.3.16" osisID="John.3.16" n="16"/>{text}<verse eID="John.3.16"/></chapter>"#
+        );
+        let mut reader = Reader::from_str(&xml);
+        reader.config_mut().trim_text(true);
+        let parsed = OsisEngine::parse_verses(&mut reader, "John.3");
+
+        assert_eq!(parsed.len(), 1);
+        let words: Vec<_> = parsed[0]
+            .words
+            .iter()
+            .map(|w| (w.text.as_str(), w.is_red, w.style))
+            .collect();
+        assert_eq!(
+            words,
+            vec![
+                ("For", false, SegmentStyle::Plain),
+                ("God", true, SegmentStyle::RedLetter),
+                ("so", true, SegmentStyle::Added),
+                ("loved", true, SegmentStyle::RedLetter),
+                (",", true, SegmentStyle::Plain),
+            ]
+        );
+        assert_eq!(
+            parsed[0].words[1].lex.as_ref().unwrap().strongs,
+            vec!["G2316"]
+        );
+        assert_eq!(parsed[0].notes[0].anchor, 4);
+        assert_eq!(parsed[0].notes[0].targets, vec!["Rom.5.8"]);
+    }
+}
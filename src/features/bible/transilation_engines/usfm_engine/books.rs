@@ -0,0 +1,134 @@
+/// USFM book code, OSIS book id and English name
+const BOOKS: &[(&str, &str, &str)] = &[
+    ("GEN", "Gen", "Genesis"),
+    ("EXO", "Exod", "Exodus"),
+    ("LEV", "Lev", "Leviticus"),
+    ("NUM", "Num", "Numbers"),
+    ("DEU", "Deut", "Deuteronomy"),
+    ("JOS", "Josh", "Joshua"),
+    ("JDG", "Judg", "Judges"),
+    ("RUT", "Ruth", "Ruth"),
+    ("1SA", "1Sam", "1 Samuel"),
+    ("2SA", "2Sam", "2 Samuel"),
+    ("1KI", "1Kgs", "1 Kings"),
+    ("2KI", "2Kgs", "2 Kings"),
+    ("1CH", "1Chr", "1 Chronicles"),
+    ("2CH", "2Chr", "2 Chronicles"),
+    ("EZR", "Ezra", "Ezra"),
+    ("NEH", "Neh", "Nehemiah"),
+    ("EST", "Esth", "Esther"),
+    ("JOB", "Job", "Job"),
+    ("PSA", "Ps", "Psalms"),
+    ("PRO", "Prov", "Proverbs"),
+    ("ECC", "Eccl", "Ecclesiastes"),
+    ("SNG", "Song", "Song of Solomon"),
+    ("ISA", "Isa", "Isaiah"),
+    ("JER", "Jer", "Jeremiah"),
+    ("LAM", "Lam", "Lamentations"),
+    ("EZK", "Ezek", "Ezekiel"),
+    ("DAN", "Dan", "Daniel"),
+    ("HOS", "Hos", "Hosea"),
+    ("JOL", "Joel", "Joel"),
+    ("AMO", "Amos", "Amos"),
+    ("OBA", "Obad", "Obadiah"),
+    ("JON", "Jonah", "Jonah"),
+    ("MIC", "Mic", "Micah"),
+    ("NAM", "Nah", "Nahum"),
+    ("HAB", "Hab", "Habakkuk"),
+    ("ZEP", "Zeph", "Zephaniah"),
+    ("HAG", "Hag", "Haggai"),
+    ("ZEC", "Zech", "Zechariah"),
+    ("MAL", "Mal", "Malachi"),
+    ("MAT", "Matt", "Matthew"),
+    ("MRK", "Mark", "Mark"),
+    ("LUK", "Luke", "Luke"),
+    ("JHN", "John", "John"),
+    ("ACT", "Acts", "Acts"),
+    ("ROM", "Rom", "Romans"),
+    ("1CO", "1Cor", "1 Corinthians"),
+    ("2CO", "2Cor", "2 Corinthians"),
+    ("GAL", "Gal", "Galatians"),
+    ("EPH", "Eph", "Ephesians"),
+    ("PHP", "Phil", "Philippians"),
+    ("COL", "Col", "Colossians"),
+    ("1TH", "1Thess", "1 Thessalonians"),
+    ("2TH", "2Thess", "2 Thessalonians"),
+    ("1TI", "1Tim", "1 Timothy"),
+    ("2TI", "2Tim", "2 Timothy"),
+    ("TIT", "Titus", "Titus"),
+    ("PHM", "Phlm", "Philemon"),
+    ("HEB", "Heb", "Hebrews"),
+    ("JAS", "Jas", "James"),
+    ("1PE", "1Pet", "1 Peter"),
+    ("2PE", "2Pet", "2 Peter"),
+    ("1JN", "1John", "1 John"),
+    ("2JN", "2John", "2 John"),
+    ("3JN", "3John", "3 John"),
+    ("JUD", "Jude", "Jude"),
+    ("REV", "Rev", "Revelation"),
+    ("TOB", "Tob", "Tobit"),
+    ("JDT", "Jdt", "Judith"),
+    ("ESG", "AddEsth", "Esther (Greek)"),
+    ("WIS", "Wis", "Wisdom of Solomon"),
+    ("SIR", "Sir", "Sirach"),
+    ("BAR", "Bar", "Baruch"),
+    ("1MA", "1Macc", "1 Maccabees"),
+    ("2MA", "2Macc", "2 Maccabees"),
+];
+
+/// OSIS id and English name for a USFM book code ("JHN" -> ("John", "John"))
+pub fn from_usfm_code(code: &str) -> Option<(&'static str, &'static str)> {
+    let code = code.trim().to_ascii_uppercase();
+    BOOKS
+        .iter()
+        .find(|(usfm, _, _)| *usfm == code)
+        .map(|(_, osis, name)| (*osis, *name))
+}
+
+/// USFM code for an OSIS book id ("John" -> "JHN")
+pub fn to_usfm_code(osis_id: &str) -> Option<&'static str> {
+    BOOKS
+        .iter()
+        .find(|(_, osis, _)| *osis == osis_id)
+        .map(|(usfm, _, _)| *usfm)
+}
+
+/// OSIS id and English name for a canonical book number, 1 (Genesis) to 66 (Revelation)
+pub fn from_canonical_number(number: usize) -> Option<(&'static str, &'static str)> {
+    BOOKS
+        .get(number.checked_sub(1)?)
+        .filter(|_| number <= 66)
+        .map(|(_, osis, name)| (*osis, *name))
+}
+
+/// Resolves a book written as a USFM code, OSIS id or English name, as
+/// found in verse-per-line files and cross-reference text ("1 John", "1Jn", "JHN").
+pub fn resolve_book_name(name: &str) -> Option<(&'static str, &'static str)> {
+    let normalized: String = name
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '.')
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if normalized.is_empty() {
+        return None;
+    }
+
+    BOOKS
+        .iter()
+        .find(|(usfm, osis, english)| {
+            usfm.eq_ignore_ascii_case(&normalized)
+                || osis.eq_ignore_ascii_case(&normalized)
+                || english.replace(' ', "").eq_ignore_ascii_case(&normalized)
+        })
+        .or_else(|| {
+            // Abbreviations such as "Gn", "Matt", "Phil" by prefix of the English name
+            BOOKS.iter().find(|(_, _, english)| {
+                english
+                    .replace(' ', "")
+                    .to_ascii_lowercase()
+                    .starts_with(&normalized)
+                    && normalized.len() >= 3
+            })
+        })
+        .map(|(_, osis, english)| (*osis, *english))
+}
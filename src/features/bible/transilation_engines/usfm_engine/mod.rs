@@ -0,0 +1,3 @@
+pub mod books;
+pub mod usfm;
+pub mod usx;
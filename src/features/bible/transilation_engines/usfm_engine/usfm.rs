@@ -0,0 +1,493 @@
+use std::path::Path;
+
+use crate::features::bible::{
+    model::{BlockKind, LexicalInfo, NoteKind},
+    transilation_engines::{
+        document::InMemoryBible,
+        import::{BibleBuilder, TextStyle},
+        usfm_engine::books,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Marker { name: &'a str, closing: bool },
+    Text(&'a str),
+}
+
+/// What the rest of a line-oriented marker's line is used for
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LineTarget {
+    BookId,
+    BookTitle,
+    Heading,
+    Superscription,
+    Ignored,
+}
+
+/// What the next whitespace-delimited token is used for
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Argument {
+    Chapter,
+    Verse,
+    NoteCaller,
+}
+
+/// Character markers that bracket text and may change its styling
+const CHAR_MARKERS: &[&str] = &[
+    "add", "wj", "it", "bd", "bdit", "em", "nd", "sc", "qs", "tl", "k", "pn", "png", "addpn",
+    "ord", "sig", "sls", "bk", "dc", "no", "sup", "qt", "lit", "ior", "iqt", "rb", "jmp",
+];
+
+/// Character markers whose content is not Bible text
+const SKIPPED_MARKERS: &[&str] = &[
+    "fig", "rq", "va", "vp", "ca", "cat", "fv", "xop", "xot", "xnt", "xdc",
+];
+
+/// Streaming parser for Unified Standard Format Markers text.
+pub struct UsfmParser {
+    builder: BibleBuilder,
+    char_stack: Vec<String>,
+    line: Option<(LineTarget, String)>,
+    argument: Option<Argument>,
+    /// Raw `\w ...|attributes` content collected until `\w*`
+    word: Option<String>,
+    skip_depth: usize,
+    /// Inside `\fr` / `\xo`: origin references are not note text
+    skip_note_text: bool,
+    in_cross_reference_target: bool,
+    last_reference_book: Option<&'static str>,
+}
+
+impl UsfmParser {
+    pub fn parse_file(path: &Path) -> Result<InMemoryBible, String> {
+        let source = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        InMemoryBible::new(path, Self::parse(&source))
+    }
+
+    /// Parses one or more books (each starting with `\id`)
+    pub fn parse(source: &str) -> Vec<crate::features::bible::model::Book> {
+        let mut parser = Self {
+            builder: BibleBuilder::new(),
+            char_stack: Vec::new(),
+            line: None,
+            argument: None,
+            word: None,
+            skip_depth: 0,
+            skip_note_text: false,
+            in_cross_reference_target: false,
+            last_reference_book: None,
+        };
+
+        for token in Self::tokenize(source) {
+            match token {
+                Token::Text(text) => parser.text(text),
+                Token::Marker { name, closing } => parser.marker(name, closing),
+            }
+        }
+        parser.finish_line();
+        parser.builder.finish()
+    }
+
+    fn tokenize(source: &str) -> Vec<Token<'_>> {
+        let mut tokens = Vec::new();
+        let bytes = source.as_bytes();
+        let mut text_start = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if bytes[i] != b'\\' {
+                i += 1;
+                continue;
+            }
+            if text_start < i {
+                tokens.push(Token::Text(&source[text_start..i]));
+            }
+
+            let name_start = i + 1;
+            let mut end = name_start;
+            while end < bytes.len()
+                && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'+' || bytes[end] == b'-')
+            {
+                end += 1;
+            }
+            let name = source[name_start..end].trim_start_matches('+');
+            let closing = end < bytes.len() && bytes[end] == b'*';
+            if closing {
+                end += 1;
+            } else if end < bytes.len() && bytes[end] == b' ' {
+                // The single space after an opening marker belongs to the marker
+                end += 1;
+            }
+
+            tokens.push(Token::Marker { name, closing });
+            i = end;
+            text_start = end;
+        }
+        if text_start < bytes.len() {
+            tokens.push(Token::Text(&source[text_start..]));
+        }
+        tokens
+    }
+
+    fn text(&mut self, mut text: &str) {
+        if self.skip_depth > 0 {
+            return;
+        }
+
+        if let Some((_, buffer)) = self.line.as_mut() {
+            match text.split_once('\n') {
+                Some((line, rest)) => {
+                    buffer.push_str(line);
+                    self.finish_line();
+                    text = rest;
+                }
+                None => {
+                    buffer.push_str(text);
+                    return;
+                }
+            }
+        }
+
+        if let Some(argument) = self.argument.take() {
+            let trimmed = text.trim_start();
+            let (value, rest) = trimmed
+                .split_once(char::is_whitespace)
+                .unwrap_or((trimmed, ""));
+            match argument {
+                Argument::Chapter => self.builder.start_chapter(Self::leading_number(value)),
+                Argument::Verse => self.builder.start_verse(Self::leading_number(value)),
+                Argument::NoteCaller => self.builder.set_note_marker(value),
+            }
+            text = rest;
+        }
+
+        if let Some(word) = self.word.as_mut() {
+            word.push_str(text);
+            return;
+        }
+
+        if self.builder.in_note() {
+            if self.skip_note_text {
+                return;
+            }
+            if self.in_cross_reference_target {
+                for reference in text.split([';', ',']) {
+                    if let Some(osis_ref) = self.osis_reference(reference) {
+                        self.builder.note_target(&osis_ref);
+                    }
+                }
+            }
+        }
+
+        self.builder.text(text, self.style(), None);
+    }
+
+    fn marker(&mut self, name: &str, closing: bool) {
+        if SKIPPED_MARKERS.contains(&name) {
+            if closing {
+                self.skip_depth = self.skip_depth.saturating_sub(1);
+            } else {
+                self.skip_depth += 1;
+            }
+            return;
+        }
+        if self.skip_depth > 0 {
+            return;
+        }
+
+        if closing {
+            self.close_marker(name);
+            return;
+        }
+
+        // Inline markers continue a heading line; anything else ends it
+        if self.line.is_some() && !CHAR_MARKERS.contains(&name) {
+            self.finish_line();
+        }
+        if self.line.is_some() {
+            return;
+        }
+
+        let (base, level) = Self::split_level(name);
+        match base {
+            "id" => self.line = Some((LineTarget::BookId, String::new())),
+            "h" => self.line = Some((LineTarget::BookTitle, String::new())),
+            "c" => self.argument = Some(Argument::Chapter),
+            "v" => self.argument = Some(Argument::Verse),
+            "p" | "m" | "pi" | "pm" | "pmo" | "pmc" | "pmr" | "po" | "pr" | "pc" | "cls" | "li"
+            | "lim" | "ph" | "mi" => self.builder.block(BlockKind::Paragraph),
+            "q" | "qr" | "qc" | "qm" => self
+                .builder
+                .block(BlockKind::PoetryLine(level.unwrap_or(1).clamp(1, 4))),
+            "b" => self.builder.block(BlockKind::LineBreak),
+            "s" | "ms" | "mr" | "sr" | "sd" => {
+                self.line = Some((LineTarget::Heading, String::new()))
+            }
+            "d" => self.line = Some((LineTarget::Superscription, String::new())),
+            "r" | "rem" | "toc" | "toca" | "mt" | "mte" | "ide" | "sts" | "usfm" | "is" | "ip"
+            | "ipi" | "im" | "imi" | "ipq" | "imq" | "ipr" | "iq" | "ib" | "ili" | "imt"
+            | "imte" | "io" | "iot" | "ior" | "iex" | "ie" | "cl" | "cp" | "cd" | "restore"
+            | "lit" => self.line = Some((LineTarget::Ignored, String::new())),
+            "f" | "fe" | "ef" => self.start_note(NoteKind::Footnote),
+            "x" | "ex" => self.start_note(NoteKind::CrossReference),
+            "fr" | "xo" => {
+                self.skip_note_text = true;
+                self.in_cross_reference_target = false;
+            }
+            "xt" => {
+                self.skip_note_text = false;
+                self.in_cross_reference_target = true;
+            }
+            "ft" | "fq" | "fqa" | "fk" | "fl" | "fw" | "fp" | "xk" | "xq" | "xta" => {
+                self.skip_note_text = false;
+                self.in_cross_reference_target = false;
+            }
+            "w" => self.word = Some(String::new()),
+            _ if CHAR_MARKERS.contains(&base) => self.char_stack.push(base.to_string()),
+            _ => {}
+        }
+    }
+
+    fn close_marker(&mut self, name: &str) {
+        match name {
+            "f" | "fe" | "ef" | "x" | "ex" => {
+                self.builder.close_note();
+                self.skip_note_text = false;
+                self.in_cross_reference_target = false;
+            }
+            "w" => {
+                if let Some(raw) = self.word.take() {
+                    let (surface, lex) = Self::parse_word_attributes(&raw);
+                    self.builder.text(surface, self.style(), lex.as_ref());
+                }
+            }
+            "xt" => self.in_cross_reference_target = false,
+            _ => {
+                if let Some(position) = self.char_stack.iter().rposition(|m| m == name) {
+                    self.char_stack.remove(position);
+                }
+            }
+        }
+    }
+
+    fn start_note(&mut self, kind: NoteKind) {
+        self.builder.open_note(kind, None);
+        self.skip_note_text = false;
+        self.in_cross_reference_target = false;
+        self.argument = Some(Argument::NoteCaller);
+    }
+
+    fn finish_line(&mut self) {
+        let Some((target, text)) = self.line.take() else {
+            return;
+        };
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        match target {
+            LineTarget::BookId => {
+                let code = text.split_whitespace().next().unwrap_or_default();
+                let (osis_id, name) = books::from_usfm_code(code).unwrap_or((code, code));
+                let (osis_id, name) = (osis_id.to_string(), name.to_string());
+                self.builder.start_book(&osis_id, &name);
+                self.last_reference_book = None;
+            }
+            LineTarget::BookTitle => self.builder.set_book_title(&text),
+            LineTarget::Heading if !text.is_empty() => self.builder.block(BlockKind::Heading(text)),
+            LineTarget::Superscription if !text.is_empty() => {
+                self.builder.block(BlockKind::Superscription(text))
+            }
+            _ => {}
+        }
+    }
+
+    fn style(&self) -> TextStyle {
+        let open = |marker: &str| self.char_stack.iter().any(|m| m == marker);
+        TextStyle {
+            added: open("add"),
+            red: open("wj"),
+            italic: open("it") || open("em") || open("bdit"),
+            bold: open("bd") || open("bdit"),
+        }
+    }
+
+    /// `\w grace|lemma="charis" strong="G5485"\w*` -> ("grace", lexical info).
+    /// A bare value after the bar is the lemma.
+    fn parse_word_attributes(raw: &str) -> (&str, Option<LexicalInfo>) {
+        let Some((surface, attributes)) = raw.split_once('|') else {
+            return (raw, None);
+        };
+
+        let mut lex = LexicalInfo::default();
+        let attributes = attributes.trim();
+        if !attributes.contains('=') {
+            lex.lemma = Some(attributes.to_string()).filter(|l| !l.is_empty());
+        }
+
+        let mut rest = attributes;
+        while let Some((key, after)) = rest.split_once("=\"") {
+            let key = key.trim();
+            let Some((value, remaining)) = after.split_once('"') else {
+                break;
+            };
+            match key {
+                "strong" => {
+                    lex.strongs.extend(
+                        value
+                            .split(',')
+                            .map(|s| s.trim().to_string())
+                            .filter(|s| !s.is_empty()),
+                    );
+                }
+                "lemma" => lex.lemma = Some(value.to_string()),
+                "gloss" | "x-gloss" => lex.gloss = Some(value.to_string()),
+                "x-morph" | "morph" => lex.morph = Some(value.to_string()),
+                _ => {}
+            }
+            rest = remaining;
+        }
+
+        (surface, Some(lex))
+    }
+
+    /// "Mk 1:2", "Jn 3.16" or a bare "3:4" (same book as the previous
+    /// reference) -> "Mark.1.2". Unrecognised text yields `None`.
+    fn osis_reference(&mut self, raw: &str) -> Option<String> {
+        let raw = raw.trim().trim_end_matches('.');
+        let (book, location) = match raw.rfind(char::is_whitespace) {
+            Some(split) => (raw[..split].trim(), raw[split..].trim()),
+            None => ("", raw),
+        };
+        let book = if book.is_empty() {
+            self.last_reference_book?
+        } else {
+            books::resolve_book_name(book)?.0
+        };
+        self.last_reference_book = Some(book);
+
+        let (chapter, verse) = location.split_once([':', '.']).unwrap_or((location, ""));
+        let chapter = Self::leading_number(chapter);
+        let verse = Self::leading_number(verse);
+        if chapter == 0 {
+            return None;
+        }
+        Some(if verse == 0 {
+            format!("{}.{}", book, chapter)
+        } else {
+            format!("{}.{}.{}", book, chapter, verse)
+        })
+    }
+
+    /// "q2" -> ("q", Some(2)), "toc1" -> ("toc", Some(1)), "p" -> ("p", None)
+    fn split_level(name: &str) -> (&str, Option<u8>) {
+        let digits = name.len() - name.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        if digits == 0 || digits == name.len() {
+            return (name, None);
+        }
+        let (base, level) = name.split_at(name.len() - digits);
+        (base, level.parse().ok())
+    }
+
+    fn leading_number(value: &str) -> i32 {
+        value
+            .trim()
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect::<String>()
+            .parse()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::bible::model::SegmentStyle;
+
+    const JOHN: &str = r#"\id JHN Sample
+\h John
+\mt1 The Gospel of John
+\c 1
+\s1 The Word Became Flesh
+\p
+\v 1 In the beginning was the \w Word|strong="G3056" lemma="λόγος"\w*, and the Word was with God.\f + \fr 1:1 \ft Or \fq the Word\f*
+\v 2 He was in the beginning \add with\add* God.\x - \xo 1:2 \xt Gen 1:1; 1 Jn 1:1; 3:4\x*
+\c 3
+\q1
+\v 16 \wj For God so loved the world,\wj*
+\q2 that he gave his only Son.
+"#;
+
+    #[test]
+    fn parses_books_chapters_and_verses() {
+        let books = UsfmParser::parse(JOHN);
+        assert_eq!(books.len(), 1);
+        let book = &books[0];
+        assert_eq!(book.osis_id, "John");
+        assert_eq!(book.title, "John");
+        assert_eq!(book.chapters.len(), 2);
+        assert_eq!(book.chapters[0].verses.len(), 2);
+        assert_eq!(book.chapters[1].verses[0].osis_id, "John.3.16");
+    }
+
+    #[test]
+    fn keeps_headings_paragraphs_and_poetry() {
+        let books = UsfmParser::parse(JOHN);
+        let first = &books[0].chapters[0].verses[0];
+        assert!(first.is_paragraph_start);
+        assert_eq!(
+            first.blocks[0].kind,
+            BlockKind::Heading("The Word Became Flesh".into())
+        );
+
+        let poetry = &books[0].chapters[1].verses[0];
+        assert_eq!(poetry.blocks[0].kind, BlockKind::PoetryLine(1));
+        assert!(
+            poetry
+                .blocks
+                .iter()
+                .any(|b| b.kind == BlockKind::PoetryLine(2) && b.anchor > 0)
+        );
+    }
+
+    #[test]
+    fn reads_word_attributes_styles_and_notes() {
+        let books = UsfmParser::parse(JOHN);
+        let verse = &books[0].chapters[0].verses[0];
+
+        let word = verse.words.iter().find(|w| w.text == "Word").unwrap();
+        let lex = word.lex.as_ref().unwrap();
+        assert_eq!(lex.strongs, vec!["G3056".to_string()]);
+        assert_eq!(lex.lemma.as_deref(), Some("λόγος"));
+
+        assert_eq!(verse.notes.len(), 1);
+        assert_eq!(verse.notes[0].text, "Or the Word");
+        assert_eq!(verse.notes[0].marker, "a");
+
+        let second = &books[0].chapters[0].verses[1];
+        let added = second.words.iter().find(|w| w.text == "with").unwrap();
+        assert_eq!(added.style, SegmentStyle::Added);
+        assert_eq!(second.notes[0].kind, NoteKind::CrossReference);
+        assert_eq!(
+            second.notes[0].targets,
+            vec![
+                "Gen.1.1".to_string(),
+                "1John.1.1".to_string(),
+                "1John.3.4".to_string()
+            ]
+        );
+
+        let red = &books[0].chapters[1].verses[0];
+        assert!(
+            red.words
+                .iter()
+                .filter(|w| w.text == "loved")
+                .all(|w| w.is_red)
+        );
+        assert!(
+            red.words
+                .iter()
+                .filter(|w| w.text == "Son")
+                .all(|w| !w.is_red)
+        );
+    }
+}
@@ -0,0 +1,349 @@
+use quick_xml::{
+    Reader,
+    events::{BytesStart, Event},
+};
+use std::path::Path;
+
+use crate::features::bible::{
+    model::{BlockKind, Book, LexicalInfo, NoteKind},
+    transilation_engines::{
+        document::InMemoryBible,
+        import::{BibleBuilder, TextStyle},
+        usfm_engine::books,
+    },
+};
+
+/// What the text of the innermost open element is used for
+#[derive(Debug, Clone, PartialEq)]
+enum Capture {
+    Text,
+    BookTitle,
+    Heading(String),
+    Superscription(String),
+    Skip,
+}
+
+/// Streaming parser for USX, the XML form of USFM.
+pub struct UsxParser;
+
+impl UsxParser {
+    pub fn parse_file(path: &Path) -> Result<InMemoryBible, String> {
+        let mut reader = Reader::from_file(path).map_err(|e| e.to_string())?;
+        InMemoryBible::new(path, Self::parse(&mut reader))
+    }
+
+    pub fn parse<R: std::io::BufRead>(reader: &mut Reader<R>) -> Vec<Book> {
+        let mut builder = BibleBuilder::new();
+        let mut buf = Vec::new();
+
+        // One entry per open element, popped on End
+        let mut captures: Vec<Capture> = Vec::new();
+        let mut char_styles: Vec<String> = Vec::new();
+        let mut word_lex: Vec<Option<LexicalInfo>> = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => {
+                    let capture =
+                        Self::start(reader, e, &mut builder, &mut char_styles, &mut word_lex);
+                    captures.push(capture);
+                }
+                Ok(Event::Empty(ref e)) => {
+                    // Milestones (<chapter/>, <verse/>, <optbreak/>) and empty elements
+                    Self::start(reader, e, &mut builder, &mut char_styles, &mut word_lex);
+                    Self::end(
+                        e.name().as_ref(),
+                        &mut builder,
+                        &mut char_styles,
+                        &mut word_lex,
+                    );
+                }
+                Ok(Event::Text(e)) => {
+                    let text = reader.decoder().decode(e.as_ref()).unwrap_or_default();
+                    Self::text(&text, &mut captures, &mut builder, &char_styles, &word_lex);
+                }
+                Ok(Event::GeneralRef(e)) => {
+                    // Entities arrive separately from the surrounding text
+                    let entity = reader.decoder().decode(e.as_ref()).unwrap_or_default();
+                    let resolved = match e.resolve_char_ref() {
+                        Ok(Some(c)) => Some(c.to_string()),
+                        _ => quick_xml::escape::resolve_xml_entity(&entity).map(str::to_string),
+                    };
+                    if let Some(text) = resolved {
+                        Self::text(&text, &mut captures, &mut builder, &char_styles, &word_lex);
+                    }
+                }
+                Ok(Event::End(ref e)) => {
+                    match captures.pop() {
+                        Some(Capture::Heading(text)) if !text.trim().is_empty() => {
+                            builder.block(BlockKind::Heading(Self::collapse(&text)))
+                        }
+                        Some(Capture::Superscription(text)) if !text.trim().is_empty() => {
+                            builder.block(BlockKind::Superscription(Self::collapse(&text)))
+                        }
+                        _ => {}
+                    }
+                    Self::end(
+                        e.name().as_ref(),
+                        &mut builder,
+                        &mut char_styles,
+                        &mut word_lex,
+                    );
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        builder.finish()
+    }
+
+    fn start<R: std::io::BufRead>(
+        reader: &Reader<R>,
+        e: &BytesStart,
+        builder: &mut BibleBuilder,
+        char_styles: &mut Vec<String>,
+        word_lex: &mut Vec<Option<LexicalInfo>>,
+    ) -> Capture {
+        let attr = |name: &[u8]| Self::get_attr(reader, e, name);
+        let style = attr(b"style").unwrap_or_default();
+
+        match e.name().as_ref() {
+            b"book" => {
+                let code = attr(b"code").unwrap_or_default();
+                let (osis_id, name) = books::from_usfm_code(&code).unwrap_or((&code, &code));
+                let (osis_id, name) = (osis_id.to_string(), name.to_string());
+                builder.start_book(&osis_id, &name);
+                Capture::Skip
+            }
+            b"chapter" => {
+                if attr(b"eid").is_none()
+                    && let Some(number) = attr(b"number")
+                {
+                    builder.start_chapter(number.trim().parse().unwrap_or(0));
+                }
+                Capture::Skip
+            }
+            b"verse" => {
+                if attr(b"eid").is_none()
+                    && let Some(number) = attr(b"number")
+                {
+                    let number: String =
+                        number.chars().take_while(|c| c.is_ascii_digit()).collect();
+                    builder.start_verse(number.parse().unwrap_or(0));
+                }
+                Capture::Skip
+            }
+            b"para" => Self::para(&style, builder),
+            b"note" => {
+                let kind = if style.starts_with('x') {
+                    NoteKind::CrossReference
+                } else {
+                    NoteKind::Footnote
+                };
+                builder.open_note(kind, attr(b"caller").as_deref());
+                Capture::Text
+            }
+            b"char" => {
+                let capture = match style.as_str() {
+                    "fr" | "xo" | "fv" | "rq" | "va" | "vp" | "ca" => Capture::Skip,
+                    "xt" => {
+                        if let Some(link) = attr(b"link-href") {
+                            builder.note_target(&Self::usx_reference(&link));
+                        }
+                        Capture::Text
+                    }
+                    _ => Capture::Text,
+                };
+                let lex = (style == "w").then(|| LexicalInfo {
+                    strongs: attr(b"strong")
+                        .map(|s| {
+                            s.split(',')
+                                .map(|s| s.trim().to_string())
+                                .filter(|s| !s.is_empty())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    lemma: attr(b"lemma"),
+                    gloss: attr(b"x-gloss").or_else(|| attr(b"gloss")),
+                    morph: attr(b"x-morph").or_else(|| attr(b"morph")),
+                    ..Default::default()
+                });
+                word_lex.push(lex);
+                char_styles.push(style);
+                capture
+            }
+            b"ref" => {
+                if let Some(loc) = attr(b"loc") {
+                    builder.note_target(&Self::usx_reference(&loc));
+                }
+                Capture::Text
+            }
+            b"figure" | b"sidebar" => Capture::Skip,
+            b"optbreak" => {
+                builder.block(BlockKind::LineBreak);
+                Capture::Skip
+            }
+            _ => Capture::Text,
+        }
+    }
+
+    fn para(style: &str, builder: &mut BibleBuilder) -> Capture {
+        let base = style.trim_end_matches(|c: char| c.is_ascii_digit());
+        let level: u8 = style[base.len()..].parse().unwrap_or(1);
+        match base {
+            "h" => Capture::BookTitle,
+            "s" | "ms" | "mr" | "sr" | "sd" => Capture::Heading(String::new()),
+            "d" => Capture::Superscription(String::new()),
+            "q" | "qr" | "qc" | "qm" => {
+                builder.block(BlockKind::PoetryLine(level.clamp(1, 4)));
+                Capture::Text
+            }
+            "b" => {
+                builder.block(BlockKind::LineBreak);
+                Capture::Skip
+            }
+            "p" | "m" | "pi" | "pm" | "pmo" | "pmc" | "pmr" | "po" | "pr" | "pc" | "cls" | "li"
+            | "lim" | "ph" | "mi" | "nb" => {
+                if base != "nb" {
+                    builder.block(BlockKind::Paragraph);
+                }
+                Capture::Text
+            }
+            // Identification, titles, introductions and remarks
+            _ => Capture::Skip,
+        }
+    }
+
+    fn end(
+        name: &[u8],
+        builder: &mut BibleBuilder,
+        char_styles: &mut Vec<String>,
+        word_lex: &mut Vec<Option<LexicalInfo>>,
+    ) {
+        match name {
+            b"note" => builder.close_note(),
+            b"char" => {
+                char_styles.pop();
+                word_lex.pop();
+            }
+            _ => {}
+        }
+    }
+
+    fn text(
+        text: &str,
+        captures: &mut [Capture],
+        builder: &mut BibleBuilder,
+        char_styles: &[String],
+        word_lex: &[Option<LexicalInfo>],
+    ) {
+        if captures.contains(&Capture::Skip) {
+            return;
+        }
+        // Character styles inside a heading still belong to the heading
+        match captures.iter_mut().rev().find(|c| **c != Capture::Text) {
+            Some(Capture::BookTitle) => builder.set_book_title(text),
+            Some(Capture::Heading(buffer)) | Some(Capture::Superscription(buffer)) => {
+                buffer.push_str(text)
+            }
+            _ => {
+                let open = |style: &str| char_styles.iter().any(|s| s == style);
+                let style = TextStyle {
+                    added: open("add"),
+                    red: open("wj"),
+                    italic: open("it") || open("em") || open("bdit"),
+                    bold: open("bd") || open("bdit"),
+                };
+                let lex = word_lex.iter().rev().find_map(|l| l.as_ref());
+                builder.text(text, style, lex);
+            }
+        }
+    }
+
+    /// "GEN 1:1" or "GEN 1:1-3" -> "Gen.1.1"
+    fn usx_reference(loc: &str) -> String {
+        let loc = loc.split('-').next().unwrap_or(loc).trim();
+        let Some((code, location)) = loc.split_once(' ') else {
+            return loc.to_string();
+        };
+        let book = books::from_usfm_code(code).map_or(code, |(osis, _)| osis);
+        let location = location.replace(':', ".");
+        format!("{}.{}", book, location)
+    }
+
+    fn collapse(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn get_attr<R: std::io::BufRead>(
+        reader: &Reader<R>,
+        e: &BytesStart,
+        name: &[u8],
+    ) -> Option<String> {
+        e.attributes()
+            .flatten()
+            .find(|a| a.key.as_ref() == name)
+            .map(|a| {
+                reader
+                    .decoder()
+                    .decode(&a.value)
+                    .unwrap_or_default()
+                    .into_owned()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::bible::model::SegmentStyle;
+
+    const GENESIS: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<usx version="3.0">
+  <book code="GEN" style="id">Sample</book>
+  <para style="h">Genesis</para>
+  <chapter number="1" style="c" sid="GEN 1" />
+  <para style="s1">The Creation</para>
+  <para style="p">
+    <verse number="1" style="v" sid="GEN 1:1" />In the <char style="w" strong="H7225">beginning</char> God created<note caller="+" style="f"><char style="fr">1:1 </char><char style="ft">Or When God began</char></note> the heavens.<verse eid="GEN 1:1" />
+    <verse number="2" style="v" sid="GEN 1:2" />And <char style="add">the</char> earth was void.<note caller="-" style="x"><char style="xo">1:2 </char><char style="xt" link-href="JER 4:23">Jer 4:23</char></note><verse eid="GEN 1:2" />
+  </para>
+  <chapter eid="GEN 1" />
+</usx>"#;
+
+    #[test]
+    fn parses_usx_into_the_shared_model() {
+        let mut reader = Reader::from_str(GENESIS);
+        let books = UsxParser::parse(&mut reader);
+
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].osis_id, "Gen");
+        assert_eq!(books[0].title, "Genesis");
+
+        let verses = &books[0].chapters[0].verses;
+        assert_eq!(verses.len(), 2);
+        assert_eq!(
+            verses[0].blocks[0].kind,
+            BlockKind::Heading("The Creation".into())
+        );
+        assert!(verses[0].is_paragraph_start);
+
+        let beginning = verses[0]
+            .words
+            .iter()
+            .find(|w| w.text == "beginning")
+            .unwrap();
+        assert_eq!(
+            beginning.lex.as_ref().unwrap().strongs,
+            vec!["H7225".to_string()]
+        );
+        assert_eq!(verses[0].notes[0].text, "Or When God began");
+
+        let added = verses[1].words.iter().find(|w| w.text == "the").unwrap();
+        assert_eq!(added.style, SegmentStyle::Added);
+        assert_eq!(verses[1].notes[0].kind, NoteKind::CrossReference);
+        assert_eq!(verses[1].notes[0].targets, vec!["Jer.4.23".to_string()]);
+    }
+}
@@ -0,0 +1,135 @@
+use regex::Regex;
+use std::path::Path;
+
+use crate::features::bible::{
+    model::{BlockKind, Book},
+    transilation_engines::{
+        document::InMemoryBible,
+        import::{BibleBuilder, TextStyle},
+        usfm_engine::books,
+    },
+};
+
+/// Reader for verse-per-line text ("Gen 1:1 In the beginning...").
+///
+/// Books may be written as OSIS ids, USFM codes or English names. A leading
+/// pilcrow (¶) opens a paragraph; blank lines and `#` comments are skipped.
+pub struct VplEngine;
+
+impl VplEngine {
+    pub fn parse_file(path: &Path) -> Result<InMemoryBible, String> {
+        let source = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        InMemoryBible::new(path, Self::parse(&source))
+    }
+
+    pub fn parse(source: &str) -> Vec<Book> {
+        let line_pattern = Regex::new(r"^\s*(.+?)\s+(\d+)[:.](\d+)\s*(.*)$").unwrap();
+        let mut builder = BibleBuilder::new();
+
+        let mut current_book: Option<&'static str> = None;
+        let mut current_chapter = 0;
+
+        for line in source.lines() {
+            let line = line.trim_start_matches('\u{feff}');
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            let Some(captures) = line_pattern.captures(line) else {
+                continue;
+            };
+            let Some((osis_id, name)) = books::resolve_book_name(&captures[1]) else {
+                continue;
+            };
+            let chapter: i32 = captures[2].parse().unwrap_or(0);
+            let verse: i32 = captures[3].parse().unwrap_or(0);
+
+            if current_book != Some(osis_id) {
+                builder.start_book(osis_id, name);
+                current_book = Some(osis_id);
+                current_chapter = 0;
+            }
+            if current_chapter != chapter {
+                builder.start_chapter(chapter);
+                current_chapter = chapter;
+            }
+
+            let mut text = captures[4].trim();
+            if let Some(rest) = text.strip_prefix('¶') {
+                builder.block(BlockKind::Paragraph);
+                text = rest.trim_start();
+            }
+            builder.start_verse(verse);
+            builder.text(text, TextStyle::default(), None);
+        }
+
+        Self::merge_repeated_books(builder.finish())
+    }
+
+    /// Lines of a book that come back after another book are added to the
+    /// first run of it, so each book and chapter is listed once
+    fn merge_repeated_books(books: Vec<Book>) -> Vec<Book> {
+        let mut merged: Vec<Book> = Vec::with_capacity(books.len());
+        for book in books {
+            let Some(existing) = merged.iter_mut().find(|b| b.osis_id == book.osis_id) else {
+                merged.push(book);
+                continue;
+            };
+            for chapter in book.chapters {
+                match existing
+                    .chapters
+                    .iter_mut()
+                    .find(|c| c.number == chapter.number)
+                {
+                    Some(known) => known.verses.extend(chapter.verses),
+                    None => existing.chapters.push(chapter),
+                }
+            }
+        }
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_verse_per_line_text() {
+        let source = "# sample\nGen 1:1 In the beginning God created the heaven and the earth.\n\
+                      Gen 1:2 And the earth was without form.\n\
+                      Genesis 2:1 ¶ Thus the heavens were finished.\n\
+                      1 John 1:1 That which was from the beginning.\n";
+        let books = VplEngine::parse(source);
+
+        assert_eq!(books.len(), 2);
+        assert_eq!(books[0].osis_id, "Gen");
+        assert_eq!(books[0].chapters.len(), 2);
+        assert_eq!(books[0].chapters[0].verses.len(), 2);
+        assert_eq!(books[0].chapters[0].verses[1].osis_id, "Gen.1.2");
+        assert!(books[0].chapters[1].verses[0].is_paragraph_start);
+        assert_eq!(books[1].osis_id, "1John");
+        assert_eq!(
+            books[1].chapters[0].verses[0].plain_text(),
+            "That which was from the beginning."
+        );
+    }
+
+    #[test]
+    fn merges_a_book_that_comes_back() {
+        let source = "Gen 1:1 In the beginning.\n\
+                      Exod 1:1 Now these are the names.\n\
+                      Gen 1:2 And the earth was without form.\n\
+                      Gen 2:1 Thus the heavens were finished.\n";
+        let books = VplEngine::parse(source);
+        assert_eq!(books.len(), 2);
+        assert_eq!(books[0].osis_id, "Gen");
+        assert_eq!(books[0].chapters.len(), 2);
+        let first: Vec<&str> = books[0].chapters[0]
+            .verses
+            .iter()
+            .map(|v| v.osis_id.as_str())
+            .collect();
+        assert_eq!(first, ["Gen.1.1", "Gen.1.2"]);
+        assert_eq!(books[1].osis_id, "Exod");
+    }
+}
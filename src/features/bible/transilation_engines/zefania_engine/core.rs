@@ -0,0 +1,254 @@
+use quick_xml::{
+    Reader,
+    events::{BytesStart, Event},
+};
+use std::path::Path;
+
+use crate::features::bible::{
+    model::{BlockKind, Book, LexicalInfo, NoteKind},
+    transilation_engines::{
+        document::InMemoryBible,
+        import::{BibleBuilder, TextStyle},
+        usfm_engine::books,
+    },
+};
+
+/// Last book of the Old Testament in Zefania's canonical numbering, used to
+/// prefix bare Strong's numbers with H or G.
+const LAST_OLD_TESTAMENT_BOOK: usize = 39;
+
+/// Streaming reader for Zefania XML (`<XMLBIBLE><BIBLEBOOK><CHAPTER><VERS>`).
+pub struct ZefaniaEngine;
+
+impl ZefaniaEngine {
+    pub fn parse_file(path: &Path) -> Result<InMemoryBible, String> {
+        let mut reader = Reader::from_file(path).map_err(|e| e.to_string())?;
+        let (title, books) = Self::parse(&mut reader);
+        let mut bible = InMemoryBible::new(path, books)?;
+        if let Some(title) = title {
+            bible.version.title = title;
+        }
+        Ok(bible)
+    }
+
+    /// Returns the Bible's title, if the file names one, and its books.
+    pub fn parse<R: std::io::BufRead>(reader: &mut Reader<R>) -> (Option<String>, Vec<Book>) {
+        let mut builder = BibleBuilder::new();
+        let mut buf = Vec::new();
+
+        let mut title: Option<String> = None;
+        let mut in_info_title = false;
+        let mut book_number = 0;
+
+        let mut in_verse = false;
+        let mut caption: Option<String> = None;
+        let mut styles: Vec<TextStyle> = Vec::new();
+        let mut lex: Option<LexicalInfo> = None;
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                // Line and paragraph breaks are the only meaningful empty elements
+                Ok(Event::Empty(ref e)) if in_verse && e.name().as_ref() == b"BR" => {
+                    let kind = match Self::get_attr(reader, e, b"art").as_deref() {
+                        Some("x-p") => BlockKind::Paragraph,
+                        _ => BlockKind::LineBreak,
+                    };
+                    builder.block(kind);
+                }
+                Ok(Event::Start(ref e)) => match e.name().as_ref() {
+                    b"XMLBIBLE" => {
+                        title = Self::get_attr(reader, e, b"biblename").or(title);
+                    }
+                    b"title" => in_info_title = true,
+                    b"BIBLEBOOK" => {
+                        book_number = Self::get_attr(reader, e, b"bnumber")
+                            .and_then(|n| n.parse().ok())
+                            .unwrap_or(book_number + 1);
+                        let short = Self::get_attr(reader, e, b"bsname");
+                        let long = Self::get_attr(reader, e, b"bname");
+                        let (osis_id, name) = books::from_canonical_number(book_number)
+                            .or_else(|| short.as_deref().and_then(books::resolve_book_name))
+                            .or_else(|| long.as_deref().and_then(books::resolve_book_name))
+                            .map(|(osis, name)| (osis.to_string(), name.to_string()))
+                            .unwrap_or_else(|| {
+                                let name = long.clone().unwrap_or_default();
+                                (short.unwrap_or_else(|| name.clone()), name)
+                            });
+                        builder.start_book(&osis_id, long.as_deref().unwrap_or(&name));
+                    }
+                    b"CHAPTER" => {
+                        let number = Self::get_attr(reader, e, b"cnumber")
+                            .and_then(|n| n.parse().ok())
+                            .unwrap_or(0);
+                        builder.start_chapter(number);
+                    }
+                    b"CAPTION" => caption = Some(String::new()),
+                    b"VERS" => {
+                        let number = Self::get_attr(reader, e, b"vnumber")
+                            .and_then(|n| n.parse().ok())
+                            .unwrap_or(0);
+                        builder.start_verse(number);
+                        in_verse = true;
+                    }
+                    b"gr" => {
+                        let prefix = if book_number <= LAST_OLD_TESTAMENT_BOOK {
+                            'H'
+                        } else {
+                            'G'
+                        };
+                        let strongs = Self::get_attr(reader, e, b"str")
+                            .map(|s| {
+                                s.split_whitespace()
+                                    .map(|n| match n.chars().next() {
+                                        Some(c) if c.is_ascii_digit() => format!("{prefix}{n}"),
+                                        _ => n.to_string(),
+                                    })
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        lex = Some(LexicalInfo {
+                            strongs,
+                            morph: Self::get_attr(reader, e, b"rmac")
+                                .map(|m| format!("robinson:{}", m)),
+                            ..Default::default()
+                        });
+                    }
+                    b"STYLE" => {
+                        let mut style = styles.last().copied().unwrap_or_default();
+                        let fs = Self::get_attr(reader, e, b"fs").unwrap_or_default();
+                        let css = Self::get_attr(reader, e, b"css")
+                            .unwrap_or_default()
+                            .to_ascii_lowercase();
+                        style.italic |= fs == "italic" || css.contains("italic");
+                        style.bold |= fs == "bold" || css.contains("bold");
+                        style.added |= fs == "added";
+                        style.red |= fs == "jesus"
+                            || css.contains("red")
+                            || css.contains("#ff0000")
+                            || css.contains("#f00");
+                        styles.push(style);
+                    }
+                    b"NOTE" if in_verse => builder.open_note(NoteKind::Footnote, None),
+                    _ => {}
+                },
+                Ok(Event::Text(e)) => {
+                    let text = reader.decoder().decode(e.as_ref()).unwrap_or_default();
+                    if in_info_title {
+                        if title.is_none() && !text.trim().is_empty() {
+                            title = Some(text.trim().to_string());
+                        }
+                    } else if let Some(caption) = caption.as_mut() {
+                        caption.push_str(&text);
+                    } else if in_verse {
+                        let style = styles.last().copied().unwrap_or_default();
+                        builder.text(&text, style, lex.as_ref());
+                    }
+                }
+                Ok(Event::GeneralRef(e)) => {
+                    let entity = reader.decoder().decode(e.as_ref()).unwrap_or_default();
+                    let resolved = match e.resolve_char_ref() {
+                        Ok(Some(c)) => Some(c.to_string()),
+                        _ => quick_xml::escape::resolve_xml_entity(&entity).map(str::to_string),
+                    };
+                    if let (Some(text), true) = (resolved, in_verse) {
+                        let style = styles.last().copied().unwrap_or_default();
+                        builder.text(&text, style, lex.as_ref());
+                    }
+                }
+                Ok(Event::End(ref e)) => match e.name().as_ref() {
+                    b"title" => in_info_title = false,
+                    b"CAPTION" => {
+                        // Captions precede the verse they head
+                        if let Some(text) = caption.take() {
+                            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                            if !text.is_empty() {
+                                builder.block(BlockKind::Heading(text));
+                            }
+                        }
+                    }
+                    b"VERS" => in_verse = false,
+                    b"gr" => lex = None,
+                    b"STYLE" => {
+                        styles.pop();
+                    }
+                    b"NOTE" => builder.close_note(),
+                    _ => {}
+                },
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        (title, builder.finish())
+    }
+
+    fn get_attr<R: std::io::BufRead>(
+        reader: &Reader<R>,
+        e: &BytesStart,
+        name: &[u8],
+    ) -> Option<String> {
+        e.attributes()
+            .flatten()
+            .find(|a| a.key.as_ref() == name)
+            .map(|a| {
+                reader
+                    .decoder()
+                    .decode(&a.value)
+                    .unwrap_or_default()
+                    .into_owned()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::bible::model::SegmentStyle;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<XMLBIBLE biblename="Sample Bible">
+  <INFORMATION><title>Ignored Title</title></INFORMATION>
+  <BIBLEBOOK bnumber="43" bname="John" bsname="Joh">
+    <CHAPTER cnumber="1">
+      <CAPTION vref="1">The Word</CAPTION>
+      <VERS vnumber="1">In the beginning was the <gr str="3056" rmac="N-NSM">Word</gr>.<NOTE>Or reason</NOTE></VERS>
+      <VERS vnumber="2">He was <STYLE fs="italic">with</STYLE> God.<BR art="x-p"/>And more.</VERS>
+    </CHAPTER>
+  </BIBLEBOOK>
+</XMLBIBLE>"#;
+
+    #[test]
+    fn parses_zefania_books_and_verses() {
+        let mut reader = Reader::from_str(SAMPLE);
+        let (title, books) = ZefaniaEngine::parse(&mut reader);
+
+        assert_eq!(title.as_deref(), Some("Sample Bible"));
+        assert_eq!(books[0].osis_id, "John");
+        assert_eq!(books[0].title, "John");
+
+        let verses = &books[0].chapters[0].verses;
+        assert_eq!(verses.len(), 2);
+        assert_eq!(verses[0].osis_id, "John.1.1");
+        assert_eq!(
+            verses[0].blocks[0].kind,
+            BlockKind::Heading("The Word".into())
+        );
+
+        let word = verses[0].words.iter().find(|w| w.text == "Word").unwrap();
+        let lex = word.lex.as_ref().unwrap();
+        assert_eq!(lex.strongs, vec!["G3056".to_string()]);
+        assert_eq!(lex.morph.as_deref(), Some("robinson:N-NSM"));
+        assert_eq!(verses[0].notes[0].text, "Or reason");
+
+        let with = verses[1].words.iter().find(|w| w.text == "with").unwrap();
+        assert!(with.is_italic);
+        assert_eq!(with.style, SegmentStyle::Plain);
+        assert!(
+            verses[1]
+                .blocks
+                .iter()
+                .any(|b| b.kind == BlockKind::Paragraph && b.anchor == 5)
+        );
+    }
+}
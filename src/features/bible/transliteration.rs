@@ -0,0 +1,308 @@
+//! Romanization of Greek and Hebrew lemmas for the word popover.
+//!
+//! Greek follows the SBL academic style (η → ē, χ → ch, rough breathing → h).
+//! Hebrew uses SBL consonants and vowels with the common matres lectionis
+//! (hiriq-yod → î, holam-vav → ô, shureq → û); cantillation is dropped.
+
+/// Returns `None` when the text has no Greek or Hebrew letters to convert.
+pub fn transliterate(text: &str) -> Option<String> {
+    if text.chars().any(is_hebrew) {
+        Some(hebrew(text))
+    } else if text.chars().any(|c| greek_base(c).is_some()) {
+        Some(greek(text))
+    } else {
+        None
+    }
+}
+
+fn is_hebrew(c: char) -> bool {
+    ('\u{05D0}'..='\u{05EA}').contains(&c)
+}
+
+/// Base lowercase letter of a (possibly accented) Greek character, whether it
+/// was uppercase, and whether it carries a rough breathing.
+fn greek_base(c: char) -> Option<(char, bool, bool)> {
+    let code = c as u32;
+    match c {
+        'ς' => Some(('σ', false, false)),
+        'α'..='ω' => Some((c, false, false)),
+        'Α'..='Ω' => Some((c.to_lowercase().next()?, true, false)),
+        'ά' => Some(('α', false, false)),
+        'έ' => Some(('ε', false, false)),
+        'ή' => Some(('η', false, false)),
+        'ί' | 'ϊ' | 'ΐ' => Some(('ι', false, false)),
+        'ό' => Some(('ο', false, false)),
+        'ύ' | 'ϋ' | 'ΰ' => Some(('υ', false, false)),
+        'ώ' => Some(('ω', false, false)),
+        'Ά' => Some(('α', true, false)),
+        'Έ' => Some(('ε', true, false)),
+        'Ή' => Some(('η', true, false)),
+        'Ί' => Some(('ι', true, false)),
+        'Ό' => Some(('ο', true, false)),
+        'Ύ' => Some(('υ', true, false)),
+        'Ώ' => Some(('ω', true, false)),
+        '\u{1F00}'..='\u{1FFF}' => greek_extended(code),
+        _ => None,
+    }
+}
+
+/// Greek Extended block: letters with breathings, accents and iota subscript.
+fn greek_extended(code: u32) -> Option<(char, bool, bool)> {
+    // Rows of eight share a base letter; within each row the odd positions
+    // carry the rough breathing and the upper half is uppercase.
+    let breathing_rows: [(u32, char); 10] = [
+        (0x1F00, 'α'),
+        (0x1F10, 'ε'),
+        (0x1F20, 'η'),
+        (0x1F30, 'ι'),
+        (0x1F40, 'ο'),
+        (0x1F50, 'υ'),
+        (0x1F60, 'ω'),
+        (0x1F80, 'α'),
+        (0x1F90, 'η'),
+        (0x1FA0, 'ω'),
+    ];
+    for (start, base) in breathing_rows {
+        if (start..start + 16).contains(&code) {
+            let offset = code - start;
+            return Some((base, offset >= 8, offset % 2 == 1));
+        }
+    }
+
+    let base = match code {
+        0x1F70 | 0x1F71 | 0x1FB0..=0x1FB7 => ('α', false),
+        0x1FB8..=0x1FBC => ('α', true),
+        0x1F72 | 0x1F73 => ('ε', false),
+        0x1FC8 | 0x1FC9 => ('ε', true),
+        0x1F74 | 0x1F75 | 0x1FC2..=0x1FC7 => ('η', false),
+        0x1FCA..=0x1FCC => ('η', true),
+        0x1F76 | 0x1F77 | 0x1FD0..=0x1FD7 => ('ι', false),
+        0x1FD8..=0x1FDB => ('ι', true),
+        0x1F78 | 0x1F79 => ('ο', false),
+        0x1FF8 | 0x1FF9 => ('ο', true),
+        0x1F7A | 0x1F7B | 0x1FE0..=0x1FE3 | 0x1FE6 | 0x1FE7 => ('υ', false),
+        0x1FE8..=0x1FEB => ('υ', true),
+        0x1FE4 => ('ρ', false),
+        0x1FE5 => return Some(('ρ', false, true)),
+        0x1FEC => return Some(('ρ', true, true)),
+        0x1F7C | 0x1F7D | 0x1FF2..=0x1FF7 => ('ω', false),
+        0x1FFA..=0x1FFC => ('ω', true),
+        _ => return None,
+    };
+    Some((base.0, base.1, false))
+}
+
+fn greek(text: &str) -> String {
+    let letters: Vec<(char, bool, bool)> = text
+        .chars()
+        .map(|c| greek_base(c).unwrap_or((c, false, false)))
+        .collect();
+
+    let mut out = String::new();
+    for (i, &(base, upper, rough)) in letters.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|p| letters[p].0);
+        let next = letters.get(i + 1).map(|l| l.0);
+
+        // In a diphthong the breathing is written on the second vowel but
+        // pronounced before the first
+        let opens_diphthong = next.is_some_and(|n| is_diphthong(base, n));
+        let closes_diphthong = prev.is_some_and(|p| is_diphthong(p, base));
+        let rough = (rough && !closes_diphthong) || (opens_diphthong && letters[i + 1].2);
+
+        let latin = match base {
+            'α' => "a",
+            'β' => "b",
+            'γ' if matches!(next, Some('γ' | 'κ' | 'ξ' | 'χ')) => "n",
+            'γ' => "g",
+            'δ' => "d",
+            'ε' => "e",
+            'ζ' => "z",
+            'η' => "ē",
+            'θ' => "th",
+            'ι' => "i",
+            'κ' => "k",
+            'λ' => "l",
+            'μ' => "m",
+            'ν' => "n",
+            'ξ' => "x",
+            'ο' => "o",
+            'π' => "p",
+            'ρ' if rough => "rh",
+            'ρ' => "r",
+            'σ' => "s",
+            'τ' => "t",
+            'υ' if opens_diphthong || closes_diphthong => "u",
+            'υ' => "y",
+            'φ' => "ph",
+            'χ' => "ch",
+            'ψ' => "ps",
+            'ω' => "ō",
+            other => {
+                out.push(other);
+                continue;
+            }
+        };
+
+        let mut latin = latin.to_string();
+        if rough && base != 'ρ' {
+            latin.insert(0, 'h');
+        }
+        if upper {
+            latin = capitalize(&latin);
+        }
+        out.push_str(&latin);
+    }
+    out
+}
+
+fn is_diphthong(first: char, second: char) -> bool {
+    matches!(
+        (first, second),
+        ('α' | 'ε' | 'η' | 'ο', 'υ') | ('α' | 'ε' | 'ο' | 'υ', 'ι')
+    )
+}
+
+fn hebrew(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        // Gather the points written on this letter
+        let mut marks = Vec::new();
+        let mut j = i + 1;
+        while j < chars.len() && ('\u{0591}'..='\u{05C7}').contains(&chars[j]) && chars[j] != '\u{05BE}' {
+            marks.push(chars[j]);
+            j += 1;
+        }
+        let has = |m: char| marks.contains(&m);
+        let vowel = marks.iter().find_map(|m| hebrew_vowel(*m));
+
+        let previous = out.chars().last();
+        let consonant = match c {
+            '\u{05D0}' => "ʾ",
+            '\u{05D1}' => "b",
+            '\u{05D2}' => "g",
+            '\u{05D3}' => "d",
+            '\u{05D4}' => "h",
+            '\u{05D5}' if has('\u{05B9}') && vowel == Some("ō") => {
+                // Holam-vav
+                out.push('ô');
+                i = j;
+                continue;
+            }
+            '\u{05D5}' if has('\u{05BC}') && vowel.is_none() => {
+                // Shureq
+                out.push('û');
+                i = j;
+                continue;
+            }
+            '\u{05D5}' => "w",
+            '\u{05D6}' => "z",
+            '\u{05D7}' => "ḥ",
+            '\u{05D8}' => "ṭ",
+            '\u{05D9}' if marks.is_empty() && matches!(previous, Some('i' | 'ē' | 'e')) => {
+                // Yod as a vowel letter lengthens the preceding vowel
+                let lengthened = match out.pop() {
+                    Some('i') => 'î',
+                    Some('ē') => 'ê',
+                    _ => 'ê',
+                };
+                out.push(lengthened);
+                i = j;
+                continue;
+            }
+            '\u{05D9}' => "y",
+            '\u{05DA}' | '\u{05DB}' => "k",
+            '\u{05DC}' => "l",
+            '\u{05DD}' | '\u{05DE}' => "m",
+            '\u{05DF}' | '\u{05E0}' => "n",
+            '\u{05E1}' => "s",
+            '\u{05E2}' => "ʿ",
+            '\u{05E3}' | '\u{05E4}' => "p",
+            '\u{05E5}' | '\u{05E6}' => "ṣ",
+            '\u{05E7}' => "q",
+            '\u{05E8}' => "r",
+            '\u{05E9}' if has('\u{05C2}') => "ś",
+            '\u{05E9}' => "š",
+            '\u{05EA}' => "t",
+            '\u{05BE}' => "-",
+            _ if ('\u{0591}'..='\u{05C7}').contains(&c) => {
+                // Stray point without a letter
+                i += 1;
+                continue;
+            }
+            other => {
+                out.push(other);
+                i += 1;
+                continue;
+            }
+        };
+
+        out.push_str(consonant);
+        if let Some(vowel) = vowel {
+            out.push_str(vowel);
+        }
+        i = j;
+    }
+    out
+}
+
+fn hebrew_vowel(mark: char) -> Option<&'static str> {
+    match mark {
+        '\u{05B0}' => Some("ə"),
+        '\u{05B1}' => Some("ĕ"),
+        '\u{05B2}' => Some("ă"),
+        '\u{05B3}' => Some("ŏ"),
+        '\u{05B4}' => Some("i"),
+        '\u{05B5}' => Some("ē"),
+        '\u{05B6}' => Some("e"),
+        '\u{05B7}' => Some("a"),
+        '\u{05B8}' => Some("ā"),
+        '\u{05B9}' | '\u{05BA}' => Some("ō"),
+        '\u{05BB}' => Some("u"),
+        _ => None,
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transliterates_greek_and_hebrew_lemmas() {
+        let cases = [
+            ("λόγος", "logos"),
+            ("Ἰησοῦς", "Iēsous"),
+            ("ἁμαρτία", "hamartia"),
+            ("ἄγγελος", "angelos"),
+            ("Χριστός", "Christos"),
+            ("εὐαγγέλιον", "euangelion"),
+            ("ῥῆμα", "rhēma"),
+            ("υἱός", "huios"),
+            ("אֱלֹהִים", "ʾĕlōhîm"),
+            ("בְּרֵאשִׁית", "bərēʾšît"),
+            ("שָׂרָה", "śārāh"),
+            ("תּוֹרָה", "tôrāh"),
+        ];
+
+        for (lemma, expected) in cases {
+            assert_eq!(transliterate(lemma).as_deref(), Some(expected), "{lemma}");
+        }
+    }
+
+    #[test]
+    fn leaves_latin_text_alone() {
+        assert_eq!(transliterate("Word"), None);
+    }
+}
@@ -1 +1,3 @@
-pub mod  sidebar;
\ No newline at end of file
+pub mod preferences;
+pub mod  sidebar;
+pub mod toast;
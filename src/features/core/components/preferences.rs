@@ -0,0 +1,240 @@
+use adw::prelude::*;
+use relm4::prelude::*;
+
+use crate::features::core::settings::{
+    AddedWordStyle, LogLevel, ReadingSettings, VerseNumberStyle,
+};
+
+/// Reading appearance and the log level, applied as each row changes
+pub struct PreferencesDialog {
+    reading: ReadingSettings,
+    log_level: LogLevel,
+}
+
+#[derive(Debug)]
+pub enum PreferencesInput {
+    /// `None` returns to the theme's font
+    SetFontFamily(Option<String>),
+    SetFontSize(f64),
+    SetLineSpacing(f64),
+    SetAddedStyle(AddedWordStyle),
+    SetRedLetter(bool),
+    SetVerseNumbers(VerseNumberStyle),
+    SetInterlinear(bool),
+    SetLogLevel(LogLevel),
+}
+
+#[derive(Debug)]
+pub enum PreferencesOutput {
+    /// The whole reading appearance after a change, to apply and keep
+    Changed(ReadingSettings),
+    LogLevelChanged(LogLevel),
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for PreferencesDialog {
+    type Init = (ReadingSettings, LogLevel);
+    type Input = PreferencesInput;
+    type Output = PreferencesOutput;
+
+    view! {
+        adw::PreferencesDialog {
+            set_title: "Preferences",
+            set_search_enabled: false,
+
+            add = &adw::PreferencesPage {
+                set_title: "Reading",
+                set_icon_name: Some("bible-read-symbolic"),
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Text",
+
+                    // Off keeps the theme's font
+                    #[name = "font_row"]
+                    add = &adw::ExpanderRow {
+                        set_title: "Custom Font",
+                        set_show_enable_switch: true,
+                        set_enable_expansion: model.reading.font_family.is_some(),
+
+                        add_row = &adw::ActionRow {
+                            set_title: "Font",
+                            add_suffix: font_button,
+                        },
+                    },
+
+                    add = &adw::SpinRow {
+                        set_title: "Font Size",
+                        set_subtitle: "In points",
+                        set_adjustment: Some(&gtk::Adjustment::new(
+                            model.reading.font_size, 8.0, 36.0, 1.0, 4.0, 0.0,
+                        )),
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesInput::SetFontSize(row.value()));
+                        },
+                    },
+
+                    add = &adw::SpinRow {
+                        set_title: "Line Spacing",
+                        set_digits: 1,
+                        set_adjustment: Some(&gtk::Adjustment::new(
+                            model.reading.line_spacing, 1.0, 2.5, 0.1, 0.5, 0.0,
+                        )),
+                        connect_value_notify[sender] => move |row| {
+                            sender.input(PreferencesInput::SetLineSpacing(row.value()));
+                        },
+                    },
+
+                    add = &adw::ComboRow {
+                        set_title: "Added Words",
+                        set_subtitle: "Words the translators supplied",
+                        set_model: Some(&gtk::StringList::new(
+                            &AddedWordStyle::ALL.map(AddedWordStyle::label),
+                        )),
+                        set_selected: AddedWordStyle::ALL
+                            .iter()
+                            .position(|style| *style == model.reading.added_style)
+                            .unwrap_or_default() as u32,
+                        connect_selected_notify[sender] => move |row| {
+                            let style = AddedWordStyle::ALL.get(row.selected() as usize);
+                            if let Some(style) = style {
+                                sender.input(PreferencesInput::SetAddedStyle(*style));
+                            }
+                        },
+                    },
+
+                    add = &adw::SwitchRow {
+                        set_title: "Words of Christ in Red",
+                        set_active: model.reading.red_letter,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesInput::SetRedLetter(row.is_active()));
+                        },
+                    },
+
+                    add = &adw::ComboRow {
+                        set_title: "Verse Numbers",
+                        set_model: Some(&gtk::StringList::new(
+                            &VerseNumberStyle::ALL.map(VerseNumberStyle::label),
+                        )),
+                        set_selected: VerseNumberStyle::ALL
+                            .iter()
+                            .position(|style| *style == model.reading.verse_numbers)
+                            .unwrap_or_default() as u32,
+                        connect_selected_notify[sender] => move |row| {
+                            let style = VerseNumberStyle::ALL.get(row.selected() as usize);
+                            if let Some(style) = style {
+                                sender.input(PreferencesInput::SetVerseNumbers(*style));
+                            }
+                        },
+                    },
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Study",
+
+                    add = &adw::SwitchRow {
+                        set_title: "Interlinear",
+                        set_subtitle: "Show Strong's-tagged modules word by word",
+                        set_active: model.reading.interlinear,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PreferencesInput::SetInterlinear(row.is_active()));
+                        },
+                    },
+                },
+            },
+
+            add = &adw::PreferencesPage {
+                set_title: "Advanced",
+                set_icon_name: Some("applications-engineering-symbolic"),
+
+                add = &adw::PreferencesGroup {
+                    set_title: "Diagnostics",
+
+                    add = &adw::ComboRow {
+                        set_title: "Log Level",
+                        set_subtitle: "Detail kept in the log file; XBIBLE_LOG overrides it",
+                        set_model: Some(&gtk::StringList::new(&LogLevel::ALL.map(LogLevel::label))),
+                        set_selected: LogLevel::ALL
+                            .iter()
+                            .position(|level| *level == model.log_level)
+                            .unwrap_or_default() as u32,
+                        connect_selected_notify[sender] => move |row| {
+                            if let Some(level) = LogLevel::ALL.get(row.selected() as usize) {
+                                sender.input(PreferencesInput::SetLogLevel(*level));
+                            }
+                        },
+                    },
+                },
+            },
+        }
+    }
+
+    fn init(
+        (reading, log_level): Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let model = PreferencesDialog { reading, log_level };
+
+        let font_button = &gtk::FontDialogButton::builder()
+            .dialog(&gtk::FontDialog::builder().title("Reading Font").build())
+            .level(gtk::FontLevel::Family)
+            .use_font(true)
+            .valign(gtk::Align::Center)
+            .build();
+        if let Some(family) = &model.reading.font_family {
+            font_button.set_font_desc(&gtk::pango::FontDescription::from_string(family));
+        }
+
+        let widgets = view_output!();
+
+        font_button.connect_font_desc_notify({
+            let sender = sender.clone();
+            let font_row = widgets.font_row.clone();
+            move |button| {
+                if font_row.enables_expansion() {
+                    sender.input(PreferencesInput::SetFontFamily(Self::family_of(button)));
+                }
+            }
+        });
+        widgets.font_row.connect_enable_expansion_notify({
+            let font_button = font_button.clone();
+            move |row| {
+                let family = row
+                    .enables_expansion()
+                    .then(|| Self::family_of(&font_button))
+                    .flatten();
+                sender.input(PreferencesInput::SetFontFamily(family));
+            }
+        });
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>) {
+        let reading = &mut self.reading;
+        match message {
+            PreferencesInput::SetFontFamily(family) => reading.font_family = family,
+            PreferencesInput::SetFontSize(size) => reading.font_size = size,
+            PreferencesInput::SetLineSpacing(spacing) => reading.line_spacing = spacing,
+            PreferencesInput::SetAddedStyle(style) => reading.added_style = style,
+            PreferencesInput::SetRedLetter(red_letter) => reading.red_letter = red_letter,
+            PreferencesInput::SetVerseNumbers(style) => reading.verse_numbers = style,
+            PreferencesInput::SetInterlinear(interlinear) => reading.interlinear = interlinear,
+            PreferencesInput::SetLogLevel(level) => {
+                self.log_level = level;
+                let _ = sender.output(PreferencesOutput::LogLevelChanged(level));
+                return;
+            }
+        }
+        let _ = sender.output(PreferencesOutput::Changed(self.reading.clone()));
+    }
+}
+
+impl PreferencesDialog {
+    fn family_of(button: &gtk::FontDialogButton) -> Option<String> {
+        button
+            .font_desc()
+            .and_then(|font| font.family())
+            .map(|family| family.to_string())
+    }
+}
@@ -5,6 +5,7 @@ use relm4::{Component, ComponentParts, prelude::*};
 pub enum NavigationPage {
     Bible,
     Library(String),
+    Plans,
     Store,
 }
 
@@ -20,6 +21,7 @@ pub struct SideBar {}
 pub enum SidebarMessage {
     ToggleSidebar,
     SelectPage(NavigationPage),
+    OpenPreferences,
 }
 
 #[relm4::component(pub)]
@@ -38,6 +40,14 @@ impl Component for SideBar {
             set_child = &adw::ToolbarView{
                 add_top_bar=&adw::HeaderBar {
                     set_show_title: false,
+                    pack_start = &gtk::Button {
+                        set_icon_name: "emblem-system-symbolic",
+                        set_tooltip_text: Some("Preferences"),
+                        add_css_class: "flat",
+                        connect_clicked[sender] => move |_| {
+                            let _ = sender.output(SidebarMessage::OpenPreferences);
+                        }
+                    },
                     pack_end = &gtk::Button {
                         set_icon_name: "sidebar-show-symbolic",
                         set_tooltip_text: Some("Hide Sidebar"),
@@ -156,6 +166,7 @@ impl SideBar {
         let listbox = &widgets.pages;
         let items = [
             ("bible-read-symbolic", "Study"),
+            ("x-office-calendar-symbolic", "Reading Plan"),
             ("my-store-symbolic", "Store"),
         ];
 
@@ -199,6 +210,11 @@ impl SideBar {
                     .output_sender()
                     .send(SidebarMessage::SelectPage(NavigationPage::Bible));
             }
+            if row.widget_name().as_str() == "Reading Plan" {
+                let _ = sender_clone
+                    .output_sender()
+                    .send(SidebarMessage::SelectPage(NavigationPage::Plans));
+            }
             if row.widget_name().as_str() == "Store" {
                 let _ = sender_clone
                     .output_sender()
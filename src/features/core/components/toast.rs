@@ -0,0 +1,12 @@
+use std::fmt::Display;
+
+/// Shows a failure at the bottom of a page. Toast titles are Pango markup,
+/// so the message is escaped first.
+pub fn show_error(overlay: &adw::ToastOverlay, error: &dyn Display) {
+    let message = error.to_string();
+    log::warn!("{}", message);
+
+    let toast = adw::Toast::new(&gtk::glib::markup_escape_text(&message));
+    toast.set_timeout(5);
+    overlay.add_toast(toast);
+}
@@ -0,0 +1,107 @@
+//! Application log.
+//!
+//! Records from the `log` macros, and from SWORD's own `SWLog` once it is
+//! routed here, go to stderr and to a rolling file under the data directory.
+//! The level is the one picked in the preferences, unless `XBIBLE_LOG`
+//! (`error`, `warn`, `info`, `debug`, `trace` or `off`) names another, and
+//! can be changed at runtime with `set_level`.
+
+pub mod rolling_file;
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+use crate::features::core::{logging::rolling_file::RollingFile, module_engine::sword};
+
+pub const LEVEL_VAR: &str = "XBIBLE_LOG";
+pub const FILE_NAME: &str = "xbible.log";
+const MAX_FILE_LEN: u64 = 1024 * 1024;
+const KEEP_FILES: usize = 3;
+
+struct Logger {
+    file: Mutex<Option<RollingFile>>,
+}
+
+static LOGGER: Logger = Logger {
+    file: Mutex::new(None),
+};
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        // Module paths are long; the last segment names the component
+        let target = record.target().rsplit("::").next().unwrap_or_default();
+        let line = format!(
+            "{} {:<5} {}: {}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            record.level(),
+            target,
+            record.args()
+        );
+        eprintln!("{}", line);
+
+        if let Ok(mut file) = self.file.lock()
+            && let Some(file) = file.as_mut()
+            && let Err(e) = file.write_line(&line)
+        {
+            eprintln!("Could not write to {}: {}", file.path().display(), e);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// The level requested through `XBIBLE_LOG`, which overrides preferences
+pub fn env_level() -> Option<LevelFilter> {
+    let value = std::env::var(LEVEL_VAR).ok()?;
+    match value.parse() {
+        Ok(level) => Some(level),
+        Err(_) => {
+            eprintln!("Ignoring {}={}: not a log level", LEVEL_VAR, value);
+            None
+        }
+    }
+}
+
+/// Installs the logger and routes SWORD's log into it. `default` applies
+/// unless `XBIBLE_LOG` is set; without a `log_dir` only stderr is used.
+pub fn init(log_dir: Option<&Path>, default: LevelFilter) {
+    if let Some(dir) = log_dir {
+        let path = dir.join(FILE_NAME);
+        match RollingFile::open(&path, MAX_FILE_LEN, KEEP_FILES) {
+            Ok(file) => *LOGGER.file.lock().unwrap_or_else(|e| e.into_inner()) = Some(file),
+            Err(e) => eprintln!("Could not open {}: {}", path.display(), e),
+        }
+    }
+
+    if log::set_logger(&LOGGER).is_err() {
+        return;
+    }
+    let level = env_level().unwrap_or(default);
+    log::set_max_level(level);
+    sword::route_log(level);
+    match log_dir {
+        Some(dir) => log::info!("Logging at {} to {}", level, dir.join(FILE_NAME).display()),
+        None => log::info!("Logging at {} to stderr only", level),
+    }
+}
+
+/// Switches to `level`, unless `XBIBLE_LOG` asked for another
+pub fn set_level(level: LevelFilter) {
+    if let Some(env) = env_level() {
+        log::info!("Keeping log level {} from {}", env, LEVEL_VAR);
+        return;
+    }
+    log::set_max_level(level);
+    sword::set_log_level(level);
+    log::info!("Log level set to {}", level);
+}
@@ -0,0 +1,107 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// An append-only log file that is renamed to `name.1`, `name.2`, ...
+/// once it grows past `max_len`, keeping at most `keep` old files.
+#[derive(Debug)]
+pub struct RollingFile {
+    path: PathBuf,
+    file: File,
+    len: u64,
+    max_len: u64,
+    keep: usize,
+}
+
+impl RollingFile {
+    pub fn open(path: &Path, max_len: u64, keep: usize) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = Self::append(path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            len,
+            max_len,
+            keep,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let size = line.len() as u64 + 1;
+        if self.len > 0 && self.len + size > self.max_len {
+            self.roll()?;
+        }
+        writeln!(self.file, "{line}")?;
+        self.len += size;
+        Ok(())
+    }
+
+    fn roll(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.keep == 0 {
+            self.file = File::create(&self.path)?;
+        } else {
+            // The oldest file is overwritten by the rename below it
+            for n in (1..self.keep).rev() {
+                let from = self.numbered(n);
+                if from.exists() {
+                    fs::rename(from, self.numbered(n + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.numbered(1))?;
+            self.file = Self::append(&self.path)?;
+        }
+        self.len = 0;
+        Ok(())
+    }
+
+    fn numbered(&self, n: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_os_string();
+        name.push(format!(".{n}"));
+        PathBuf::from(name)
+    }
+
+    fn append(path: &Path) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolls_over_and_keeps_the_newest_files() {
+        let dir = std::env::temp_dir().join(format!("xbible-log-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("xbible.log");
+
+        let mut log = RollingFile::open(&path, 10, 2).unwrap();
+        for line in ["one", "two", "three", "four", "five"] {
+            log.write_line(line).unwrap();
+        }
+
+        let read = |name: &str| fs::read_to_string(dir.join(name)).unwrap();
+        assert_eq!(read("xbible.log"), "four\nfive\n");
+        assert_eq!(read("xbible.log.1"), "three\n");
+        assert_eq!(read("xbible.log.2"), "one\ntwo\n");
+
+        // Reopening continues from the current size, and the oldest file is dropped
+        drop(log);
+        let mut log = RollingFile::open(&path, 10, 2).unwrap();
+        log.write_line("six").unwrap();
+        assert_eq!(read("xbible.log"), "six\n");
+        assert_eq!(read("xbible.log.1"), "four\nfive\n");
+        assert_eq!(read("xbible.log.2"), "three\n");
+        assert!(!dir.join("xbible.log.3").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
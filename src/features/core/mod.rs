@@ -1,3 +1,8 @@
+#[cfg(feature = "gui")]
 pub mod components;
-pub mod pages;
+pub mod logging;
 pub mod module_engine;
+#[cfg(feature = "gui")]
+pub mod pages;
+pub mod reading_plan;
+pub mod settings;
@@ -0,0 +1,167 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::features::core::module_engine::sword::{self, SwordResult};
+
+/// Name other BibleSync apps show for this one
+const APP_NAME: &str = "xBible";
+
+/// How often the group is checked for navigation
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How a session shares navigation with the rest of its group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BibleSyncMode {
+    /// The protocol's speaker: others follow along, and references
+    /// received are ignored
+    Leader,
+    /// The protocol's audience: follows speakers, and nothing is sent
+    Follower,
+    /// The protocol's personal mode: sends and follows
+    Peer,
+}
+
+impl BibleSyncMode {
+    pub const ALL: [Self; 3] = [Self::Leader, Self::Follower, Self::Peer];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Leader => "Leader",
+            Self::Follower => "Follower",
+            Self::Peer => "Peer",
+        }
+    }
+
+    pub fn transmits(self) -> bool {
+        matches!(self, Self::Leader | Self::Peer)
+    }
+
+    pub fn receives(self) -> bool {
+        matches!(self, Self::Follower | Self::Peer)
+    }
+}
+
+/// Membership of a BibleSync group, left when dropped.
+/// There is one session per process, so drop the previous session before
+/// starting another.
+#[derive(Debug)]
+pub struct BibleSyncSession {
+    mode: BibleSyncMode,
+    /// The chapter last sent or received, so following a reference does
+    /// not echo it back to the group
+    last_ref: Option<String>,
+    /// Tells the polling thread to finish
+    stopping: Arc<AtomicBool>,
+    poller: Option<JoinHandle<()>>,
+}
+
+impl BibleSyncSession {
+    /// Joins the group for `passphrase`; `on_navigate` gets OSIS references
+    /// ("John.3.16", or several separated by ';') on a polling thread
+    pub fn start(
+        mode: BibleSyncMode,
+        passphrase: &str,
+        on_navigate: impl Fn(String) + Send + 'static,
+    ) -> SwordResult<Self> {
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| APP_NAME.to_string());
+        sword::start_bible_sync(APP_NAME, &user, passphrase, mode)?;
+        log::info!("BibleSync joined as {}", mode.label());
+
+        let stopping = Arc::new(AtomicBool::new(false));
+        let poller = thread::spawn({
+            let stopping = stopping.clone();
+            move || {
+                while !stopping.load(Ordering::Acquire) {
+                    for osis_ref in sword::receive_bible_sync() {
+                        if mode.receives() {
+                            on_navigate(osis_ref);
+                        }
+                    }
+                    thread::park_timeout(POLL_INTERVAL);
+                }
+            }
+        });
+
+        Ok(Self {
+            mode,
+            last_ref: None,
+            stopping,
+            poller: Some(poller),
+        })
+    }
+
+    pub fn mode(&self) -> BibleSyncMode {
+        self.mode
+    }
+
+    /// Tells the group the reader moved to `osis_ref` in `module`
+    pub fn navigate(&mut self, module: &str, osis_ref: &str) {
+        if !self.should_send(osis_ref) {
+            return;
+        }
+        match sword::send_bible_sync(module, osis_ref) {
+            Ok(()) => self.last_ref = Some(osis_ref.to_string()),
+            Err(e) => log::warn!("BibleSync could not send {}: {}", osis_ref, e),
+        }
+    }
+
+    /// Records the chapter of a reference followed from the group
+    /// ("John.3.16" or "John.3.16-John.3.18" -> "John.3")
+    pub fn received(&mut self, osis_ref: &str) {
+        let start = osis_ref.split(['-', ';']).next().unwrap_or_default().trim();
+        let chapter: Vec<&str> = start.split('.').take(2).collect();
+        self.last_ref = Some(chapter.join("."));
+    }
+
+    fn should_send(&self, osis_ref: &str) -> bool {
+        self.mode.transmits() && self.last_ref.as_deref() != Some(osis_ref)
+    }
+}
+
+impl Drop for BibleSyncSession {
+    fn drop(&mut self) {
+        // The poller reads the session, so it finishes before the session goes
+        self.stopping.store(true, Ordering::Release);
+        if let Some(poller) = self.poller.take() {
+            poller.thread().unpark();
+            if poller.join().is_err() {
+                log::warn!("BibleSync polling thread panicked");
+            }
+        }
+        sword::stop_bible_sync();
+        log::info!("BibleSync left");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(mode: BibleSyncMode) -> BibleSyncSession {
+        BibleSyncSession {
+            mode,
+            last_ref: None,
+            stopping: Arc::default(),
+            poller: None,
+        }
+    }
+
+    #[test]
+    fn only_leaders_and_peers_send() {
+        assert!(session(BibleSyncMode::Leader).should_send("John.3"));
+        assert!(session(BibleSyncMode::Peer).should_send("John.3"));
+        assert!(!session(BibleSyncMode::Follower).should_send("John.3"));
+    }
+
+    #[test]
+    fn does_not_echo_a_followed_chapter() {
+        let mut peer = session(BibleSyncMode::Peer);
+        peer.received("John.3.16-John.3.18");
+        assert!(!peer.should_send("John.3"));
+        assert!(peer.should_send("John.4"));
+    }
+}
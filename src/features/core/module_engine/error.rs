@@ -0,0 +1,137 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::features::core::module_engine::sword::SwordError;
+
+/// Why `InstallMgr::remoteInstallModule` gave up, decoded from its return code
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InstallFailure {
+    /// -1: the disclaimer was not accepted or a file failed to download
+    Transfer,
+    /// -3: the repository is not configured
+    UnknownSource,
+    /// -4: the repository does not offer the module
+    NotInSource,
+    /// -9: SWORD was built without a transport for the repository's protocol
+    NoTransport,
+    Other(i32),
+}
+
+impl InstallFailure {
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            -1 => Self::Transfer,
+            -3 => Self::UnknownSource,
+            -4 => Self::NotInSource,
+            -9 => Self::NoTransport,
+            code => Self::Other(code),
+        }
+    }
+}
+
+impl fmt::Display for InstallFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transfer => write!(f, "the download did not complete"),
+            Self::UnknownSource => write!(f, "the repository is not configured"),
+            Self::NotInSource => write!(f, "the repository does not offer it"),
+            Self::NoTransport => write!(f, "no transport is available for the repository"),
+            Self::Other(code) => write!(f, "SWORD returned error code {code}"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EngineError {
+    #[error("Module {0} is not installed")]
+    ModuleNotFound(String),
+    #[error("{module} has no entry at “{key}”")]
+    InvalidKey { module: String, key: String },
+    #[error("“{0}” contains a NUL character")]
+    InvalidText(String),
+    #[error("Could not install {module} from {repository}: {failure}")]
+    Install {
+        repository: String,
+        module: String,
+        failure: InstallFailure,
+    },
+    #[error("Could not remove {module}: SWORD returned error code {code}")]
+    Uninstall { module: String, code: i32 },
+    #[error("Could not open the SWORD library at {}", .0.display())]
+    Library(PathBuf),
+    #[error("No data directory could be found for the SWORD library")]
+    NoDataDirectory,
+    #[error("Could not access {}: {source}", .path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("A module named {0} is already installed")]
+    ModuleExists(String),
+    #[error("Module name must contain letters or digits")]
+    InvalidModuleName,
+    #[error("Could not create module files in {}", .0.display())]
+    ModuleFiles(PathBuf),
+    #[error("None of the verses fit the {0} versification")]
+    NoVersesFit(String),
+    #[error("The SWORD library is unavailable after an earlier failure")]
+    LockPoisoned,
+    #[error("BibleSync is unavailable or could not use the network")]
+    BibleSync,
+}
+
+impl EngineError {
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Self::Io {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+impl From<SwordError> for EngineError {
+    fn from(error: SwordError) -> Self {
+        match error {
+            SwordError::Nul(e) => {
+                let text = String::from_utf8_lossy(&e.into_vec()).into_owned();
+                Self::InvalidText(text)
+            }
+            SwordError::Manager(path) | SwordError::InstallManager(path) => Self::Library(path),
+            SwordError::ModuleNotFound(module) => Self::ModuleNotFound(module),
+            SwordError::KeyNotFound { module, key } => Self::InvalidKey { module, key },
+            SwordError::Install {
+                repository,
+                module,
+                code,
+            } => Self::Install {
+                repository,
+                module,
+                failure: InstallFailure::from_code(code),
+            },
+            SwordError::Uninstall { module, code } => Self::Uninstall { module, code },
+            SwordError::BibleSync => Self::BibleSync,
+        }
+    }
+}
+
+pub type EngineResult<T> = Result<T, EngineError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_install_codes() {
+        let error = EngineError::from(SwordError::Install {
+            repository: "CrossWire".into(),
+            module: "KJV".into(),
+            code: -4,
+        });
+        assert_eq!(
+            error.to_string(),
+            "Could not install KJV from CrossWire: the repository does not offer it"
+        );
+        assert_eq!(InstallFailure::from_code(-9), InstallFailure::NoTransport);
+        assert_eq!(InstallFailure::from_code(7), InstallFailure::Other(7));
+    }
+}
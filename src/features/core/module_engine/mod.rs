@@ -1,2 +1,7 @@
+pub mod bible_sync;
+pub mod error;
+pub mod module_writer;
+pub mod sword;
 pub mod sword_engine;
-pub mod sword_module;
\ No newline at end of file
+pub mod sword_module;
+pub mod text_options;
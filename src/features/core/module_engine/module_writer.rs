@@ -0,0 +1,60 @@
+use std::ffi::CString;
+use std::path::Path;
+
+use crate::features::core::module_engine::error::{EngineError, EngineResult};
+use crate::sword_sys::*;
+
+/// Writes verse entries into a new compressed (zText) SWORD module.
+///
+/// Entries are buffered per book by SWORD; the final block is compressed
+/// and flushed when the writer is dropped.
+#[derive(Debug)]
+pub struct ModuleWriter {
+    handle: isize,
+}
+
+impl ModuleWriter {
+    /// Creates the module's data files under `path` and opens them for writing
+    pub fn create(path: &Path, versification: &str) -> EngineResult<Self> {
+        std::fs::create_dir_all(path).map_err(|e| EngineError::io(path, e))?;
+
+        let path_str = path.to_string_lossy().replace("\\", "/");
+        let path_str = if path_str.ends_with('/') {
+            path_str
+        } else {
+            format!("{path_str}/")
+        };
+        let c_path = CString::new(path_str.as_str())
+            .map_err(|_| EngineError::InvalidText(path_str.clone()))?;
+        let c_v11n = CString::new(versification)
+            .map_err(|_| EngineError::InvalidText(versification.to_string()))?;
+
+        unsafe {
+            if xbible_ModuleWriter_create(c_path.as_ptr(), c_v11n.as_ptr()) != 0 {
+                return Err(EngineError::ModuleFiles(path.to_path_buf()));
+            }
+            let handle = xbible_ModuleWriter_open(c_path.as_ptr(), c_v11n.as_ptr());
+            if handle == 0 {
+                return Err(EngineError::ModuleFiles(path.to_path_buf()));
+            }
+            Ok(Self { handle })
+        }
+    }
+
+    /// Stores OSIS text at `osis_ref` ("Gen.1.1"). Returns false when the
+    /// reference does not exist in the module's versification.
+    pub fn set_entry(&mut self, osis_ref: &str, text: &str) -> bool {
+        let (Ok(c_ref), Ok(c_text)) = (CString::new(osis_ref), CString::new(text)) else {
+            return false;
+        };
+        unsafe { xbible_ModuleWriter_setEntry(self.handle, c_ref.as_ptr(), c_text.as_ptr()) == 0 }
+    }
+}
+
+impl Drop for ModuleWriter {
+    fn drop(&mut self) {
+        unsafe {
+            xbible_ModuleWriter_close(self.handle);
+        }
+    }
+}
@@ -0,0 +1,703 @@
+//! Safe handles over the SWORD flat API.
+//!
+//! `Manager` owns an `SWMgr`; every `Module` borrows the manager it came
+//! from, so a module handle can never outlive a rebuilt or deleted manager.
+//! Strings coming back from SWORD are copied into owned `String`s before the
+//! next call can overwrite SWORD's buffers.
+
+use std::ffi::{CStr, CString, NulError};
+use std::marker::PhantomData;
+use std::os::raw::{c_char, c_int};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use log::{Level, LevelFilter};
+
+use crate::features::core::module_engine::{
+    bible_sync::BibleSyncMode,
+    sword_module::{SearchHit, SearchType, SwordModule},
+};
+use crate::sword_sys::*;
+
+/// `REG_ICASE` from regex.h, honoured by every search type
+const SEARCH_IGNORE_CASE: i64 = 2;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SwordError {
+    #[error("text passed to SWORD contains a NUL byte")]
+    Nul(#[from] NulError),
+    #[error("could not open a SWORD manager at {0}")]
+    Manager(PathBuf),
+    #[error("could not open the SWORD install manager at {0}")]
+    InstallManager(PathBuf),
+    #[error("module {0} is not installed")]
+    ModuleNotFound(String),
+    #[error("{module} has no entry at {key}")]
+    KeyNotFound { module: String, key: String },
+    #[error("installing {module} from {repository} failed with code {code}")]
+    Install {
+        repository: String,
+        module: String,
+        code: i32,
+    },
+    #[error("removing {module} failed with code {code}")]
+    Uninstall { module: String, code: i32 },
+    #[error("BibleSync is unavailable or could not use the network")]
+    BibleSync,
+}
+
+pub type SwordResult<T> = Result<T, SwordError>;
+
+/// An `SWMgr` reading the modules installed under one SWORD root
+#[derive(Debug)]
+pub struct Manager {
+    handle: isize,
+    path: PathBuf,
+}
+
+impl Manager {
+    /// Opens the modules under `path`, with UTF-8 output switched on
+    pub fn open(path: &Path) -> SwordResult<Self> {
+        let path_str = path.to_string_lossy().replace("\\", "/");
+        let c_path = CString::new(path_str)?;
+
+        let handle = unsafe { org_crosswire_sword_SWMgr_newWithPath(c_path.as_ptr()) };
+        if handle == 0 {
+            return Err(SwordError::Manager(path.to_path_buf()));
+        }
+        let manager = Self {
+            handle,
+            path: path.to_path_buf(),
+        };
+        manager.set_global_option("UTF8", "true")?;
+        Ok(manager)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Metadata of every installed module
+    pub fn modules(&self) -> Vec<SwordModule> {
+        let mut modules = Vec::new();
+        unsafe {
+            let mut ptr = org_crosswire_sword_SWMgr_getModInfoList(self.handle);
+            while !ptr.is_null() && !(*ptr).name.is_null() {
+                modules.push(mod_info(&*ptr));
+                ptr = ptr.offset(1);
+            }
+        }
+        modules
+    }
+
+    pub fn module(&self, name: &str) -> SwordResult<Module<'_>> {
+        let c_name = CString::new(name)?;
+        let handle =
+            unsafe { org_crosswire_sword_SWMgr_getModuleByName(self.handle, c_name.as_ptr()) };
+        if handle == 0 {
+            return Err(SwordError::ModuleNotFound(name.to_string()));
+        }
+        Ok(Module {
+            handle,
+            name: name.to_string(),
+            _manager: PhantomData,
+        })
+    }
+
+    /// Switches a render filter ("Strong's Numbers", "Footnotes", ...) for
+    /// every module of this manager
+    pub fn set_global_option(&self, option: &str, value: &str) -> SwordResult<()> {
+        let c_option = CString::new(option)?;
+        let c_value = CString::new(value)?;
+        unsafe {
+            org_crosswire_sword_SWMgr_setGlobalOption(
+                self.handle,
+                c_option.as_ptr(),
+                c_value.as_ptr(),
+            );
+        }
+        Ok(())
+    }
+
+    pub fn global_option(&self, option: &str) -> SwordResult<Option<String>> {
+        let c_option = CString::new(option)?;
+        Ok(unsafe {
+            owned(org_crosswire_sword_SWMgr_getGlobalOption(
+                self.handle,
+                c_option.as_ptr(),
+            ))
+        })
+    }
+
+    /// Every option offered by a filter of some installed module
+    pub fn global_options(&self) -> Vec<String> {
+        unsafe { owned_list(org_crosswire_sword_SWMgr_getGlobalOptions(self.handle)) }
+    }
+
+    /// The settings `option` accepts ("Off", "On"); empty for unknown options
+    pub fn global_option_values(&self, option: &str) -> SwordResult<Vec<String>> {
+        let c_option = CString::new(option)?;
+        Ok(unsafe {
+            owned_list(org_crosswire_sword_SWMgr_getGlobalOptionValues(
+                self.handle,
+                c_option.as_ptr(),
+            ))
+        })
+    }
+
+    /// SWORD's one-line description of `option`
+    pub fn global_option_tip(&self, option: &str) -> SwordResult<Option<String>> {
+        let c_option = CString::new(option)?;
+        Ok(unsafe {
+            owned(org_crosswire_sword_SWMgr_getGlobalOptionTip(
+                self.handle,
+                c_option.as_ptr(),
+            ))
+            .filter(|tip| !tip.is_empty())
+        })
+    }
+
+    /// Runs the filter behind `option` over `text` at the option's current
+    /// value; meant for the letter filters ("Greek Accents", ...), which
+    /// work on any text
+    pub fn filter_text(&self, option: &str, text: &str) -> SwordResult<String> {
+        let c_option = CString::new(option)?;
+        let c_text = CString::new(text)?;
+        Ok(unsafe {
+            owned(org_crosswire_sword_SWMgr_filterText(
+                self.handle,
+                c_option.as_ptr(),
+                c_text.as_ptr(),
+            ))
+        }
+        .unwrap_or_else(|| text.to_string()))
+    }
+}
+
+impl Drop for Manager {
+    fn drop(&mut self) {
+        unsafe {
+            org_crosswire_sword_SWMgr_delete(self.handle);
+        }
+    }
+}
+
+/// A module of a `Manager`. SWORD keeps one cursor per module, so moving the
+/// key here moves it for every other handle to the same module.
+#[derive(Debug)]
+pub struct Module<'mgr> {
+    handle: isize,
+    name: String,
+    _manager: PhantomData<&'mgr Manager>,
+}
+
+impl Module<'_> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Positions the module at `key` ("John 3:16", "Gen.1.1", "00001")
+    pub fn set_key(&self, key: &str) -> SwordResult<()> {
+        let c_key = CString::new(key)?;
+        unsafe {
+            org_crosswire_sword_SWModule_setKeyText(self.handle, c_key.as_ptr());
+        }
+        if self.pop_error() {
+            return Err(SwordError::KeyNotFound {
+                module: self.name.clone(),
+                key: key.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Moves to the first entry
+    pub fn begin(&self) {
+        unsafe {
+            org_crosswire_sword_SWModule_begin(self.handle);
+        }
+    }
+
+    /// Moves to the following entry; false once the module is exhausted
+    pub fn next(&self) -> bool {
+        unsafe {
+            org_crosswire_sword_SWModule_next(self.handle);
+        }
+        !self.pop_error()
+    }
+
+    /// Whether the last cursor move landed on an entry. SWORD reports this
+    /// once, so ask right after `begin` or `set_key` rather than twice.
+    pub fn is_valid(&self) -> bool {
+        !self.pop_error()
+    }
+
+    pub fn key_text(&self) -> Option<String> {
+        unsafe { owned(org_crosswire_sword_SWModule_getKeyText(self.handle)) }
+    }
+
+    /// The current position broken into its parts; `None` outside Bibles
+    /// and commentaries
+    pub fn key(&self) -> Option<Key> {
+        let children =
+            unsafe { owned_list(org_crosswire_sword_SWModule_getKeyChildren(self.handle)) };
+        Key::from_children(&children)
+    }
+
+    /// The entry as stored, markup included
+    pub fn raw_entry(&self) -> Option<String> {
+        unsafe { owned(org_crosswire_sword_SWModule_getRawEntry(self.handle)) }
+    }
+
+    /// The entry with every tag removed
+    pub fn strip_text(&self) -> Option<String> {
+        unsafe { owned(org_crosswire_sword_SWModule_stripText(self.handle)) }
+    }
+
+    /// The entry rendered through the module's filters
+    pub fn render_text(&self) -> Option<String> {
+        unsafe { owned(org_crosswire_sword_SWModule_renderText(self.handle)) }
+    }
+
+    /// Values SWORD filed under the current entry's attributes, such as
+    /// ("Heading", "Preverse", "")
+    pub fn entry_attribute(
+        &self,
+        level1: &str,
+        level2: &str,
+        level3: &str,
+        filtered: bool,
+    ) -> SwordResult<Vec<String>> {
+        let (c_level1, c_level2, c_level3) = (
+            CString::new(level1)?,
+            CString::new(level2)?,
+            CString::new(level3)?,
+        );
+        Ok(unsafe {
+            owned_list(org_crosswire_sword_SWModule_getEntryAttribute(
+                self.handle,
+                c_level1.as_ptr(),
+                c_level2.as_ptr(),
+                c_level3.as_ptr(),
+                filtered as c_char,
+            ))
+        })
+    }
+
+    /// Expands a verse list ("Rom.8.28-Rom.8.30; John.3.16") through the
+    /// module's versification, one key per verse
+    pub fn parse_key_list(&self, list: &str) -> SwordResult<Vec<String>> {
+        let c_list = CString::new(list)?;
+        Ok(unsafe {
+            owned_list(org_crosswire_sword_SWModule_parseKeyList(
+                self.handle,
+                c_list.as_ptr(),
+            ))
+        })
+    }
+
+    /// A value of the module's conf entry ("Versification", "Lang", ...)
+    pub fn config_entry(&self, key: &str) -> SwordResult<Option<String>> {
+        let c_key = CString::new(key)?;
+        Ok(unsafe {
+            owned(org_crosswire_sword_SWModule_getConfigEntry(
+                self.handle,
+                c_key.as_ptr(),
+            ))
+        })
+    }
+
+    /// Case-insensitive search; `scope` limits it with a verse list such as
+    /// "Mat-Joh"
+    pub fn search(
+        &self,
+        query: &str,
+        search_type: SearchType,
+        scope: Option<&str>,
+    ) -> SwordResult<Vec<SearchHit>> {
+        let c_query = CString::new(query)?;
+        let c_scope = scope.map(CString::new).transpose()?;
+
+        let mut hits = Vec::new();
+        unsafe {
+            let results = org_crosswire_sword_SWModule_search(
+                self.handle,
+                c_query.as_ptr(),
+                search_type as i32,
+                SEARCH_IGNORE_CASE as _,
+                c_scope.as_ref().map_or(std::ptr::null(), |s| s.as_ptr()),
+                None,
+            );
+            if !results.is_null() {
+                let mut hit = results;
+                while !(*hit).modName.is_null() {
+                    hits.push(SearchHit {
+                        module: owned((*hit).modName).unwrap_or_default(),
+                        key: owned((*hit).key).unwrap_or_default(),
+                        score: (*hit).score as i64,
+                    });
+                    hit = hit.offset(1);
+                }
+            }
+        }
+        Ok(hits)
+    }
+
+    /// Reads and clears the error of the last cursor move
+    fn pop_error(&self) -> bool {
+        unsafe { org_crosswire_sword_SWModule_popError(self.handle) != 0 }
+    }
+}
+
+/// A verse position as SWORD's `VerseKey` reports it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Key {
+    pub testament: i32,
+    /// Book number within the testament
+    pub book: i32,
+    pub chapter: i32,
+    pub verse: i32,
+    pub chapter_max: i32,
+    pub verse_max: i32,
+    pub book_name: String,
+    /// "John.3.16"
+    pub osis_ref: String,
+    /// "John 3:16"
+    pub short_text: String,
+    pub book_abbrev: String,
+    /// "John"
+    pub osis_book: String,
+}
+
+impl Key {
+    /// Reads the eleven fields of `SWModule_getKeyChildren` on a verse key
+    fn from_children(children: &[String]) -> Option<Self> {
+        if children.len() < 11 {
+            return None;
+        }
+        let number = |i: usize| children[i].parse::<i32>().ok();
+        Some(Self {
+            testament: number(0)?,
+            book: number(1)?,
+            chapter: number(2)?,
+            verse: number(3)?,
+            chapter_max: number(4)?,
+            verse_max: number(5)?,
+            book_name: children[6].clone(),
+            osis_ref: children[7].clone(),
+            short_text: children[8].clone(),
+            book_abbrev: children[9].clone(),
+            osis_book: children[10].clone(),
+        })
+    }
+}
+
+/// The `InstallMgr` fetching modules from remote repositories
+#[derive(Debug)]
+pub struct InstallManager {
+    handle: isize,
+}
+
+impl InstallManager {
+    /// Opens the installer configuration under `path`, accepting the
+    /// download disclaimer. `status` receives download progress. The
+    /// repository list isn't fetched until `sync_config`.
+    pub fn open(
+        path: &Path,
+        status: org_crosswire_sword_InstallMgr_StatusCallback,
+    ) -> SwordResult<Self> {
+        let path_str = path.to_string_lossy().replace("\\", "/");
+        let c_path = CString::new(path_str)?;
+
+        let handle = unsafe { org_crosswire_sword_InstallMgr_new(c_path.as_ptr(), status) };
+        if handle == 0 {
+            return Err(SwordError::InstallManager(path.to_path_buf()));
+        }
+        let installer = Self { handle };
+        installer.confirm_disclaimer();
+        Ok(installer)
+    }
+
+    pub fn confirm_disclaimer(&self) {
+        unsafe {
+            org_crosswire_sword_InstallMgr_setUserDisclaimerConfirmed(self.handle);
+        }
+    }
+
+    /// Fetches the list of known repositories
+    pub fn sync_config(&self) {
+        unsafe {
+            org_crosswire_sword_InstallMgr_syncConfig(self.handle);
+        }
+    }
+
+    pub fn remote_sources(&self) -> Vec<String> {
+        unsafe { owned_list(org_crosswire_sword_InstallMgr_getRemoteSources(self.handle)) }
+    }
+
+    /// Downloads the module list of a repository
+    pub fn refresh_source(&self, source: &str) -> SwordResult<()> {
+        let c_source = CString::new(source)?;
+        unsafe {
+            org_crosswire_sword_InstallMgr_refreshRemoteSource(self.handle, c_source.as_ptr());
+        }
+        Ok(())
+    }
+
+    /// Modules offered by a refreshed repository, compared against those
+    /// `manager` has installed
+    pub fn remote_modules(&self, manager: &Manager, source: &str) -> SwordResult<Vec<SwordModule>> {
+        let c_source = CString::new(source)?;
+        let mut modules = Vec::new();
+        unsafe {
+            let mut ptr = org_crosswire_sword_InstallMgr_getRemoteModInfoList(
+                self.handle,
+                manager.handle,
+                c_source.as_ptr(),
+            );
+            while !ptr.is_null() && !(*ptr).name.is_null() {
+                modules.push(mod_info(&*ptr));
+                ptr = ptr.offset(1);
+            }
+        }
+        Ok(modules)
+    }
+
+    /// Installs a module into the root `manager` reads from
+    pub fn install(&self, manager: &Manager, source: &str, module: &str) -> SwordResult<()> {
+        let c_source = CString::new(source)?;
+        let c_module = CString::new(module)?;
+        let code = unsafe {
+            org_crosswire_sword_InstallMgr_remoteInstallModule(
+                self.handle,
+                manager.handle,
+                c_source.as_ptr(),
+                c_module.as_ptr(),
+            )
+        };
+        if code != 0 {
+            return Err(SwordError::Install {
+                repository: source.to_string(),
+                module: module.to_string(),
+                code,
+            });
+        }
+        Ok(())
+    }
+
+    /// Removes an installed module. Takes the manager mutably since its
+    /// module list changes underneath any borrowed `Module`.
+    pub fn uninstall(&self, manager: &mut Manager, module: &str) -> SwordResult<()> {
+        let c_module = CString::new(module)?;
+        let code = unsafe {
+            org_crosswire_sword_InstallMgr_uninstallModule(
+                self.handle,
+                manager.handle,
+                c_module.as_ptr(),
+            )
+        };
+        if code != 0 {
+            return Err(SwordError::Uninstall {
+                module: module.to_string(),
+                code,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Drop for InstallManager {
+    fn drop(&mut self) {
+        unsafe {
+            org_crosswire_sword_InstallMgr_delete(self.handle);
+        }
+    }
+}
+
+// ------------------- LOG -------------------
+
+/// Sends SWORD's `SWLog` output through the `log` crate instead of stderr
+pub fn route_log(level: LevelFilter) {
+    unsafe { xbible_SWLog_setCallback(Some(forward_log), sword_log_level(level)) }
+}
+
+/// SWORD drops messages above its own level before formatting them
+pub fn set_log_level(level: LevelFilter) {
+    unsafe { org_crosswire_sword_SWLog_setLogLevel(sword_log_level(level)) }
+}
+
+fn sword_log_level(level: LevelFilter) -> c_int {
+    match level {
+        LevelFilter::Off => 0,
+        LevelFilter::Error => 1,
+        LevelFilter::Warn => 2,
+        // Timed info (4) is SWORD's profiling output
+        LevelFilter::Info => 4,
+        LevelFilter::Debug | LevelFilter::Trace => 5,
+    }
+}
+
+fn log_level(sword_level: c_int) -> Level {
+    match sword_level {
+        ..=1 => Level::Error,
+        2 => Level::Warn,
+        3 | 4 => Level::Info,
+        _ => Level::Debug,
+    }
+}
+
+unsafe extern "C" fn forward_log(level: c_int, message: *const c_char) {
+    if let Some(message) = unsafe { owned(message) } {
+        log::log!(target: "sword", log_level(level), "{}", message.trim_end());
+    }
+}
+
+// ------------------- BIBLESYNC -------------------
+
+/// Whether xBible was built against libbiblesync; without it the calls
+/// below fail or do nothing
+pub const BIBLE_SYNC_AVAILABLE: bool = cfg!(bible_sync);
+
+/// Navigations received during the current `receive_bible_sync` call
+static BIBLE_SYNC_RECEIVED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Joins the BibleSync group for `passphrase` in the protocol mode behind
+/// `mode`, leaving any session already running. Packets are only read by
+/// `receive_bible_sync`.
+pub fn start_bible_sync(
+    app: &str,
+    user: &str,
+    passphrase: &str,
+    mode: BibleSyncMode,
+) -> SwordResult<()> {
+    let app = CString::new(app)?;
+    let version = CString::new(env!("CARGO_PKG_VERSION"))?;
+    let user = CString::new(user)?;
+    let passphrase = CString::new(passphrase)?;
+    let mode = match mode {
+        BibleSyncMode::Leader => XBIBLE_BIBLE_SYNC_SPEAKER,
+        BibleSyncMode::Follower => XBIBLE_BIBLE_SYNC_AUDIENCE,
+        BibleSyncMode::Peer => XBIBLE_BIBLE_SYNC_PERSONAL,
+    };
+
+    let status = unsafe {
+        xbible_BibleSync_start(
+            app.as_ptr(),
+            version.as_ptr(),
+            user.as_ptr(),
+            passphrase.as_ptr(),
+            mode as c_int,
+            Some(receive_navigation),
+        )
+    };
+    if status != 0 {
+        return Err(SwordError::BibleSync);
+    }
+    Ok(())
+}
+
+/// Reads the packets waiting without blocking, and returns the navigation
+/// references among them ("John.3.16", or several separated by ';')
+pub fn receive_bible_sync() -> Vec<String> {
+    unsafe { xbible_BibleSync_receive() }
+    // Only ever holds complete strings, so a poisoned lock is harmless
+    std::mem::take(
+        &mut *BIBLE_SYNC_RECEIVED
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner),
+    )
+}
+
+/// Announces `osis_ref` ("John.3") as read in `module`
+pub fn send_bible_sync(module: &str, osis_ref: &str) -> SwordResult<()> {
+    let module = CString::new(module)?;
+    let osis_ref = CString::new(osis_ref)?;
+    let status = unsafe { xbible_BibleSync_transmit(module.as_ptr(), osis_ref.as_ptr()) };
+    if status != 0 {
+        return Err(SwordError::BibleSync);
+    }
+    Ok(())
+}
+
+/// Leaves the group
+pub fn stop_bible_sync() {
+    unsafe { xbible_BibleSync_stop() }
+}
+
+unsafe extern "C" fn receive_navigation(reference: *const c_char) {
+    if let Some(reference) = unsafe { owned(reference) } {
+        BIBLE_SYNC_RECEIVED
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(reference);
+    }
+}
+
+/// Copies a string SWORD owns; its buffer is reused by the next call
+unsafe fn owned(ptr: *const c_char) -> Option<String> {
+    if ptr.is_null() {
+        return None;
+    }
+    Some(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+/// Copies a NULL-terminated string array SWORD owns
+unsafe fn owned_list(ptr: *const *const c_char) -> Vec<String> {
+    let mut list = Vec::new();
+    if ptr.is_null() {
+        return list;
+    }
+    unsafe {
+        let mut item = ptr;
+        while !(*item).is_null() {
+            list.extend(owned(*item));
+            item = item.offset(1);
+        }
+    }
+    list
+}
+
+unsafe fn mod_info(info: &org_crosswire_sword_ModInfo) -> SwordModule {
+    let text = |ptr: *const c_char| unsafe { owned(ptr) }.unwrap_or_else(|| "Unknown".to_string());
+    SwordModule {
+        name: text(info.name),
+        description: text(info.description),
+        category: text(info.category),
+        language: text(info.language),
+        features: unsafe { owned_list(info.features) },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_verse_key_children() {
+        let children: Vec<String> = "2|4|3|16|21|36|John|John.3.16|John 3:16|Joh|John"
+            .split('|')
+            .map(String::from)
+            .collect();
+
+        let key = Key::from_children(&children).unwrap();
+        assert_eq!(
+            (key.testament, key.book, key.chapter, key.verse),
+            (2, 4, 3, 16)
+        );
+        assert_eq!(key.chapter_max, 21);
+        assert_eq!(key.osis_ref, "John.3.16");
+        assert_eq!(key.short_text, "John 3:16");
+
+        // Generic books report tree children instead
+        assert_eq!(Key::from_children(&children[..3]), None);
+    }
+
+    #[test]
+    fn maps_log_levels() {
+        for level in [Level::Error, Level::Warn, Level::Info, Level::Debug] {
+            assert_eq!(log_level(sword_log_level(level.to_level_filter())), level);
+        }
+        assert_eq!(log_level(3), Level::Info);
+        assert_eq!(sword_log_level(LevelFilter::Off), 0);
+    }
+}
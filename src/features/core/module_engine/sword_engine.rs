@@ -1,60 +1,107 @@
 use directories::ProjectDirs;
-use std::ffi::{CStr, CString};
+use log::{debug, info, warn};
+use std::collections::HashMap;
+use std::ffi::CStr;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, Mutex};
-
-use crate::features::core::module_engine::sword_module::{ModuleBook, ModuleChapter, SwordModule};
-use crate::sword_sys::*;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::features::bible::{model::Book, transilation_engines::osis_engine::writer::OsisWriter};
+use crate::features::core::module_engine::error::{EngineError, EngineResult};
+use crate::features::core::module_engine::module_writer::ModuleWriter;
+use crate::features::core::module_engine::sword::{InstallManager, Manager};
+use crate::features::core::module_engine::sword_module::{
+    LexiconEntry, ModuleBook, ModuleChapter, ModuleSpec, SearchHit, SearchType, StrongsLexicons,
+    SwordModule,
+};
+use crate::features::core::module_engine::text_options::{TextOption, TextOptions, module_options};
+use crate::features::core::settings::Settings;
+
+/// Overrides the data directory, e.g. to run against a test library
+pub const DATA_DIR_VAR: &str = "XBIBLE_DATA_DIR";
 
 static PROGRESS_TOTAL: AtomicU64 = AtomicU64::new(0);
 static PROGRESS_COMPLETED: AtomicU64 = AtomicU64::new(0);
 
 #[derive(Debug)]
 pub struct SwordInner {
-    pub mgr: isize,
-    pub install_mgr: isize,
+    pub mgr: Manager,
+    pub install_mgr: InstallManager,
+    /// Global option values the user picked, set before each module read
+    pub text_options: TextOptions,
+    /// Whether the repository list was fetched since the library was opened
+    sources_synced: bool,
+}
+
+impl SwordInner {
+    /// Sets each of `options` to the user's value; SWORD keeps option
+    /// values per manager, not per module
+    pub fn apply_text_options(&self, options: &[String]) -> EngineResult<()> {
+        for option in options {
+            let values = self.mgr.global_option_values(option)?;
+            if !values.is_empty() {
+                let value = self.text_options.value(option, &values);
+                self.mgr.set_global_option(option, &value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetches the repository list the first time a remote source is
+    /// needed, so reading an installed library needs no network
+    fn sync_sources(&mut self) {
+        if !self.sources_synced {
+            self.install_mgr.confirm_disclaimer();
+            self.install_mgr.sync_config();
+            self.sources_synced = true;
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct SwordEngine {
-    pub inner: Mutex<SwordInner>,
+    inner: Mutex<SwordInner>,
+    /// Option names per module, read from mods.d once per manager
+    module_options: Mutex<HashMap<String, Vec<String>>>,
     pub sword_path: PathBuf,
 }
 
 impl SwordEngine {
-    pub fn new() -> Arc<Self> {
-        let path = Self::get_sword_path();
+    pub fn new() -> EngineResult<Arc<Self>> {
+        Self::open(Self::get_sword_path()?)
+    }
 
+    /// Opens (and if needed creates) a SWORD library at `path`, with the
+    /// text options saved in its settings
+    pub fn open(path: PathBuf) -> EngineResult<Arc<Self>> {
         // Pre-create folders BEFORE initializing the C handles
-        Self::prepare_app_directory(&path);
-
-        let path_str = path.to_string_lossy().replace("\\", "/");
-        let c_path = CString::new(path_str.clone()).unwrap();
-
-        unsafe {
-            println!("[SwordEngine] Initializing InstallMgr at: {}", path_str);
-            let install_mgr =
-                org_crosswire_sword_InstallMgr_new(c_path.as_ptr(), Some(Self::status_reporter));
-
-            // Force disclaimer and a baseline sync
-            org_crosswire_sword_InstallMgr_setUserDisclaimerConfirmed(install_mgr);
-            org_crosswire_sword_InstallMgr_syncConfig(install_mgr);
-
-            println!("[SwordEngine] Initializing SWMgr...");
-            let mgr = org_crosswire_sword_SWMgr_newWithPath(c_path.as_ptr());
-
-            let utf8_key = CString::new("UTF8").unwrap();
-            let on_val = CString::new("true").unwrap();
-            org_crosswire_sword_SWMgr_setGlobalOption(mgr, utf8_key.as_ptr(), on_val.as_ptr());
+        Self::prepare_app_directory(&path)?;
+
+        info!("Initializing InstallMgr at {}", path.display());
+        let install_mgr = InstallManager::open(&path, Some(Self::status_reporter))?;
+
+        debug!("Initializing SWMgr");
+        let mgr = Manager::open(&path)?;
+
+        Ok(Arc::new(Self {
+            inner: Mutex::new(SwordInner {
+                mgr,
+                install_mgr,
+                text_options: Settings::load(&path).text_options,
+                sources_synced: false,
+            }),
+            module_options: Mutex::default(),
+            sword_path: path,
+        }))
+    }
 
-            Arc::new(Self {
-                inner: Mutex::new(SwordInner { mgr, install_mgr }),
-                sword_path: path,
-            })
-        }
+    /// The SWORD handles, shared by the UI and worker threads. A thread that
+    /// panicked mid-call may have left them half-updated, so a poisoned lock
+    /// is reported rather than recovered.
+    pub fn lock(&self) -> EngineResult<MutexGuard<'_, SwordInner>> {
+        self.inner.lock().map_err(|_| EngineError::LockPoisoned)
     }
 
     unsafe extern "C" fn status_reporter(
@@ -62,194 +109,295 @@ impl SwordEngine {
         total: ::std::os::raw::c_ulong,
         completed: ::std::os::raw::c_ulong,
     ) {
-        unsafe {
-            PROGRESS_TOTAL.store(total as u64, Ordering::SeqCst);
-            PROGRESS_COMPLETED.store(completed as u64, Ordering::SeqCst);
-
-            if !msg.is_null() {
-                let message = CStr::from_ptr(msg).to_string_lossy();
-                println!(
-                    "[SwordEngine] Progress: {}/{} - {}",
-                    completed, total, message
-                );
-            }
+        PROGRESS_TOTAL.store(total as u64, Ordering::SeqCst);
+        PROGRESS_COMPLETED.store(completed as u64, Ordering::SeqCst);
+
+        if !msg.is_null() {
+            let message = unsafe { CStr::from_ptr(msg) }.to_string_lossy();
+            debug!("Progress: {}/{} - {}", completed, total, message);
         }
     }
 
-    unsafe fn rebuild_mgr(&self, inner: &mut SwordInner) {
-        println!("[SwordEngine] Rebuilding SWMgr...");
-        org_crosswire_sword_SWMgr_delete(inner.mgr);
-
-        let path_str = self.sword_path.to_string_lossy().replace("\\", "/");
-        let c_path = CString::new(path_str).unwrap();
+    /// Replaces the manager so it rescans mods.d. Callers hold the lock, so
+    /// no `Module` of the old manager can still be borrowed.
+    fn rebuild_mgr(&self, inner: &mut SwordInner) -> EngineResult<()> {
+        debug!("Rebuilding SWMgr");
+        inner.mgr = Manager::open(&self.sword_path)?;
+        self.module_options
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        Ok(())
+    }
 
-        inner.mgr = org_crosswire_sword_SWMgr_newWithPath(c_path.as_ptr());
+    // ------------------- REMOTE SOURCES -------------------
 
-        let utf8_key = CString::new("UTF8").unwrap();
-        let on_val = CString::new("true").unwrap();
-        org_crosswire_sword_SWMgr_setGlobalOption(inner.mgr, utf8_key.as_ptr(), on_val.as_ptr());
-        println!("[SwordEngine] SWMgr rebuilt successfully");
+    pub fn get_remote_source_list(&self) -> EngineResult<Vec<String>> {
+        let mut inner = self.lock()?;
+        inner.sync_sources();
+        let sources = inner.install_mgr.remote_sources();
+        debug!("Remote sources: {:?}", sources);
+        Ok(sources)
     }
 
-    // ------------------- REMOTE SOURCES -------------------
+    pub fn fetch_remote_modules(&self, source_name: &str) -> EngineResult<Vec<SwordModule>> {
+        info!("Refreshing {}", source_name);
+        let mut inner = self.lock()?;
+        inner.sync_sources();
 
-    pub fn get_remote_source_list(&self) -> Vec<String> {
-        let inner = self.inner.lock().unwrap();
-        let mut sources = Vec::new();
-        unsafe {
-            let ptr = org_crosswire_sword_InstallMgr_getRemoteSources(inner.install_mgr);
-            if !ptr.is_null() {
-                let mut i = 0;
-                while !(*ptr.offset(i)).is_null() {
-                    sources.push(self.ptr_to_str(*ptr.offset(i)));
-                    i += 1;
-                }
-            }
-        }
-        println!("[SwordEngine] Remote sources: {:?}", sources);
-        sources
-    }
-
-    pub fn fetch_remote_modules(&self, source_name: &str) -> Vec<SwordModule> {
-        println!("\n[Step 1] Locking Engine...");
-        let mut inner = self.inner.lock().unwrap();
-        let mut modules = Vec::new();
-        let c_source = CString::new(source_name).unwrap();
-
-        unsafe {
-            // 1. Refresh (Downloads to temp)
-            org_crosswire_sword_InstallMgr_setUserDisclaimerConfirmed(inner.install_mgr);
-            org_crosswire_sword_InstallMgr_refreshRemoteSource(
-                inner.install_mgr,
-                c_source.as_ptr(),
-            );
-
-            // 2. Sync (Moves from temp to InstallMgr/RemoteSources)
-            println!("[Step 4] Syncing...");
-            org_crosswire_sword_InstallMgr_syncConfig(inner.install_mgr);
-
-            // 3. Re-syncing and Re-confirming (Forces the internal cache to update)
-            org_crosswire_sword_InstallMgr_setUserDisclaimerConfirmed(inner.install_mgr);
-            org_crosswire_sword_InstallMgr_syncConfig(inner.install_mgr);
-
-            // --- DEBUG: Physical Check ---
-            let remote_path = self
-                .sword_path
-                .join("InstallMgr")
-                .join("RemoteSources")
-                .join(source_name);
-            println!("[Step 5] Checking physical path: {:?}", remote_path);
-            if remote_path.exists() {
-                if let Ok(entries) = fs::read_dir(&remote_path) {
-                    for entry in entries.flatten() {
-                        println!("[Step 5.1] Found file on disk: {:?}", entry.file_name());
-                    }
-                }
-            } else {
-                println!("[Step 5.2] WARNING: Folder still does not exist on disk!");
-            }
+        // 1. Refresh (Downloads to temp)
+        inner.install_mgr.confirm_disclaimer();
+        inner.install_mgr.refresh_source(source_name)?;
 
-            self.rebuild_mgr(&mut inner);
-
-            println!("[Step 6] Final Query...");
-            let info_ptr = org_crosswire_sword_InstallMgr_getRemoteModInfoList(
-                inner.install_mgr,
-                0,
-                std::ptr::null(),
-            );
-
-            if !info_ptr.is_null() {
-                let mut i = 0;
-                loop {
-                    let entry = info_ptr.offset(i);
-                    if entry.is_null() || (*entry).name.is_null() {
-                        break;
-                    }
-                    modules.push(SwordModule {
-                        name: self.ptr_to_str((*entry).name),
-                        description: self.ptr_to_str((*entry).description),
-                        category: self.ptr_to_str((*entry).category),
-                        language: self.ptr_to_str((*entry).language),
-                    });
-                    i += 1;
-                }
-                println!("[Step 9] SUCCESS: Found {} modules", modules.len());
-            } else {
-                println!("[Step 7] Still NULL. API is failing to read its own files.");
+        // 2. Sync (Moves from temp to InstallMgr/RemoteSources)
+        inner.install_mgr.sync_config();
+
+        // 3. Re-syncing and Re-confirming (Forces the internal cache to update)
+        inner.install_mgr.confirm_disclaimer();
+        inner.install_mgr.sync_config();
+
+        let remote_path = self
+            .sword_path
+            .join("InstallMgr")
+            .join("RemoteSources")
+            .join(source_name);
+        if !remote_path.exists() {
+            warn!("{} was not created by the refresh", remote_path.display());
+        } else if log::log_enabled!(log::Level::Debug)
+            && let Ok(entries) = fs::read_dir(&remote_path)
+        {
+            for entry in entries.flatten() {
+                debug!("Found {:?} in {}", entry.file_name(), remote_path.display());
             }
         }
-        modules
+
+        self.rebuild_mgr(&mut inner)?;
+
+        let modules = inner.install_mgr.remote_modules(&inner.mgr, source_name)?;
+        info!("{} offers {} modules", source_name, modules.len());
+        Ok(modules)
     }
     // ------------------- LOCAL MODULES -------------------
 
-    pub fn get_modules(&self) -> Vec<SwordModule> {
-        let mut modules = Vec::new();
-        let inner = self.inner.lock().unwrap();
-        unsafe {
-            let mut ptr = org_crosswire_sword_SWMgr_getModInfoList(inner.mgr);
-            while !ptr.is_null() && !(*ptr).name.is_null() {
-                let info = *ptr;
-                modules.push(SwordModule {
-                    name: self.ptr_to_str(info.name),
-                    description: self.ptr_to_str(info.description),
-                    category: self.ptr_to_str(info.category),
-                    language: self.ptr_to_str(info.language),
-                });
-                ptr = ptr.offset(1);
-            }
-        }
-        println!("[SwordEngine] Local modules found: {}", modules.len());
-        modules
+    pub fn get_modules(&self) -> EngineResult<Vec<SwordModule>> {
+        let modules = self.lock()?.mgr.modules();
+        debug!("Local modules found: {}", modules.len());
+        Ok(modules)
     }
 
-    pub fn get_modules_by_category(&self, categories: Vec<&str>) -> Vec<SwordModule> {
-        self.get_modules()
+    pub fn get_modules_by_category(&self, categories: Vec<&str>) -> EngineResult<Vec<SwordModule>> {
+        Ok(self
+            .get_modules()?
             .into_iter()
             .filter(|m| categories.contains(&m.category.as_str()))
-            .collect()
+            .collect())
     }
 
-    pub fn get_bible_modules(&self) -> Vec<SwordModule> {
+    pub fn get_bible_modules(&self) -> EngineResult<Vec<SwordModule>> {
         self.get_modules_by_category(vec!["Biblical Texts", "Bibles"])
     }
-    pub fn get_commentary_modules(&self) -> Vec<SwordModule> {
+    pub fn get_commentary_modules(&self) -> EngineResult<Vec<SwordModule>> {
         self.get_modules_by_category(vec!["Commentaries"])
     }
-    pub fn get_dictionary_modules(&self) -> Vec<SwordModule> {
+    pub fn get_dictionary_modules(&self) -> EngineResult<Vec<SwordModule>> {
         self.get_modules_by_category(vec!["Lexicons", "Dictionaries"])
     }
-    pub fn get_book_modules(&self) -> Vec<SwordModule> {
+    pub fn get_book_modules(&self) -> EngineResult<Vec<SwordModule>> {
         self.get_modules_by_category(vec!["Generic Books"])
     }
-    pub fn get_map_modules(&self) -> Vec<SwordModule> {
+    pub fn get_map_modules(&self) -> EngineResult<Vec<SwordModule>> {
         self.get_modules_by_category(vec!["Images", "Maps"])
     }
 
+    // ------------------- LEXICONS -------------------
+
+    /// The first installed lexicons advertising the GreekDef and HebrewDef
+    /// features. Finding them reads every module, so callers looking up many
+    /// numbers find them once and use `lookup_strongs_in`.
+    pub fn strongs_lexicons(&self) -> EngineResult<StrongsLexicons> {
+        let dictionaries = self.get_dictionary_modules()?;
+        let with_feature = |feature: &str| {
+            dictionaries
+                .iter()
+                .find(|m| m.features.iter().any(|f| f == feature))
+                .map(|m| m.name.clone())
+        };
+        Ok(StrongsLexicons {
+            greek: with_feature("GreekDef"),
+            hebrew: with_feature("HebrewDef"),
+        })
+    }
+
+    /// Looks a Strong's number ("G3056", "H7225") up in the first installed
+    /// lexicon advertising the matching GreekDef/HebrewDef feature. `None`
+    /// when no lexicon is installed or it lacks the entry.
+    pub fn lookup_strongs(&self, strong: &str) -> EngineResult<Option<LexiconEntry>> {
+        self.lookup_strongs_in(&self.strongs_lexicons()?, strong)
+    }
+
+    /// Looks a Strong's number up in `lexicons`
+    pub fn lookup_strongs_in(
+        &self,
+        lexicons: &StrongsLexicons,
+        strong: &str,
+    ) -> EngineResult<Option<LexiconEntry>> {
+        let lexicon = match strong.chars().next() {
+            Some('G') => lexicons.greek.as_ref(),
+            Some('H') => lexicons.hebrew.as_ref(),
+            _ => None,
+        };
+        let Some(lexicon) = lexicon else {
+            return Ok(None);
+        };
+        let digits: String = strong[1..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        if digits.is_empty() {
+            return Ok(None);
+        }
+
+        // Strong's lexicons key their entries as zero-padded five digits
+        let key = format!("{:0>5}", digits);
+        let text = match self.get_plain_text(lexicon, &key) {
+            Ok(text) => text,
+            Err(EngineError::InvalidKey { .. }) => None,
+            Err(e) => return Err(e),
+        };
+
+        Ok(text.map(|text| LexiconEntry {
+            module: lexicon.clone(),
+            key,
+            text,
+        }))
+    }
+
+    /// Plain (markup-free) text of a single entry; `None` when it is empty.
+    pub fn get_plain_text(&self, module_name: &str, key: &str) -> EngineResult<Option<String>> {
+        let inner = self.lock()?;
+        let module = inner.mgr.module(module_name)?;
+        module.set_key(key)?;
+
+        let text = module.strip_text().unwrap_or_default().trim().to_string();
+        Ok((!text.is_empty()).then_some(text))
+    }
+
+    // ------------------- SEARCH -------------------
+
+    /// Runs a case-insensitive search over a module. `scope` limits the
+    /// range with a verse list such as "Mat-Joh".
+    pub fn search(
+        &self,
+        module_name: &str,
+        query: &str,
+        search_type: SearchType,
+        scope: Option<&str>,
+    ) -> EngineResult<Vec<SearchHit>> {
+        let inner = self.lock()?;
+        let module = inner.mgr.module(module_name)?;
+        Ok(module.search(query, search_type, scope)?)
+    }
+
+    /// Verses whose words are tagged with the given Strong's number.
+    pub fn search_strongs(&self, module_name: &str, strong: &str) -> EngineResult<Vec<SearchHit>> {
+        self.search(
+            module_name,
+            &format!("Word//Lemma./{}", strong),
+            SearchType::EntryAttribute,
+            None,
+        )
+    }
+
+    // ------------------- TEXT OPTIONS -------------------
+
+    /// The options `module_name`'s filters offer, at the values in use.
+    /// Options with a single value (transliteration without ICU) are left out.
+    pub fn get_text_options(&self, module_name: &str) -> EngineResult<Vec<TextOption>> {
+        let names = self.module_option_names(module_name);
+        let inner = self.lock()?;
+        let mut options = Vec::new();
+        for name in names {
+            let values = inner.mgr.global_option_values(&name)?;
+            if values.len() < 2 {
+                continue;
+            }
+            options.push(TextOption {
+                tip: inner.mgr.global_option_tip(&name)?,
+                value: inner.text_options.value(&name, &values),
+                name,
+                values,
+            });
+        }
+        Ok(options)
+    }
+
+    /// Names of the options `module_name` switches through its
+    /// `GlobalOptionFilter` entries
+    pub fn module_option_names(&self, module_name: &str) -> Vec<String> {
+        let mut cache = self
+            .module_options
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(names) = cache.get(module_name) {
+            return names.clone();
+        }
+
+        let mods_dir = self.sword_path.join("mods.d");
+        let names = fs::read_dir(&mods_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "conf"))
+            .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+            .find_map(|conf| module_options(&conf, module_name))
+            .unwrap_or_default();
+        cache.insert(module_name.to_string(), names.clone());
+        names
+    }
+
+    /// The values picked so far, for saving
+    pub fn text_options(&self) -> EngineResult<TextOptions> {
+        Ok(self.lock()?.text_options.clone())
+    }
+
+    /// Replaces the picked values, e.g. with ones saved last session
+    pub fn set_text_options(&self, options: TextOptions) -> EngineResult<()> {
+        self.lock()?.text_options = options;
+        Ok(())
+    }
+
+    pub fn set_text_option(&self, option: &str, value: &str) -> EngineResult<()> {
+        self.lock()?.text_options.set(option, value);
+        Ok(())
+    }
+
     // ------------------- INSTALL MODULE -------------------
 
-    pub fn install_remote_module(&self, source: &str, module_name: &str) -> i32 {
-        let inner = self.inner.lock().unwrap();
-        let c_source = CString::new(source).unwrap();
-        let c_mod = CString::new(module_name).unwrap();
+    pub fn install_remote_module(&self, source: &str, module_name: &str) -> EngineResult<()> {
+        let mut inner = self.lock()?;
+        inner.sync_sources();
 
         PROGRESS_TOTAL.store(0, Ordering::SeqCst);
         PROGRESS_COMPLETED.store(0, Ordering::SeqCst);
 
-        unsafe {
-            println!(
-                "[SwordEngine] Installing '{}' from '{}'",
-                module_name, source
-            );
-            org_crosswire_sword_InstallMgr_setUserDisclaimerConfirmed(inner.install_mgr);
-            let res = org_crosswire_sword_InstallMgr_remoteInstallModule(
-                inner.install_mgr,
-                inner.mgr,
-                c_source.as_ptr(),
-                c_mod.as_ptr(),
-            );
-            println!("[SwordEngine] Install result: {}", res);
-            res
-        }
+        info!("Installing '{}' from '{}'", module_name, source);
+        inner.install_mgr.confirm_disclaimer();
+        inner.install_mgr.install(&inner.mgr, source, module_name)?;
+        info!("Installed '{}'", module_name);
+        Ok(())
+    }
+
+    pub fn uninstall_module(&self, module_name: &str) -> EngineResult<()> {
+        let mut inner = self.lock()?;
+        let SwordInner {
+            mgr, install_mgr, ..
+        } = &mut *inner;
+        // Fail on typos instead of reporting success for a missing module
+        mgr.module(module_name)?;
+
+        info!("Removing '{}'", module_name);
+        install_mgr.uninstall(mgr, module_name)?;
+        self.rebuild_mgr(&mut inner)
     }
 
     pub fn get_download_progress(&self) -> f64 {
@@ -262,119 +410,167 @@ impl SwordEngine {
         }
     }
 
-    // ------------------- BIBLE STRUCTURE -------------------
+    // ------------------- CREATE MODULE -------------------
 
-    pub fn get_bible_structure(&self, module_name: &str) -> Vec<ModuleBook> {
-        let mut books = Vec::new();
-        let c_mod_name = CString::new(module_name).unwrap();
-        let inner = self.inner.lock().unwrap();
-
-        unsafe {
-            let h_module =
-                org_crosswire_sword_SWMgr_getModuleByName(inner.mgr, c_mod_name.as_ptr());
-            if h_module == 0 {
-                return books;
+    /// Compiles imported books into a zText module under `sword_path` and
+    /// registers it in mods.d. Returns the number of verses written; verses
+    /// outside the chosen versification are skipped.
+    pub fn create_module(&self, spec: &ModuleSpec, books: &[Book]) -> EngineResult<usize> {
+        if spec.name.is_empty() {
+            return Err(EngineError::InvalidModuleName);
+        }
+        let conf_path = self
+            .sword_path
+            .join("mods.d")
+            .join(format!("{}.conf", spec.name.to_lowercase()));
+        if conf_path.exists() {
+            return Err(EngineError::ModuleExists(spec.name.clone()));
+        }
+
+        let data_path = self.sword_path.join(spec.data_path());
+        info!("Creating module '{}' at {}", spec.name, data_path.display());
+
+        let mut written = 0;
+        let mut has_strongs = false;
+        {
+            let mut writer = ModuleWriter::create(&data_path, &spec.versification)?;
+            let verses = books
+                .iter()
+                .flat_map(|b| b.chapters.iter())
+                .flat_map(|c| c.verses.iter());
+            for verse in verses {
+                has_strongs |= verse
+                    .words
+                    .iter()
+                    .any(|w| w.lex.as_ref().is_some_and(|l| !l.strongs.is_empty()));
+                if writer.set_entry(&verse.osis_id, &OsisWriter::verse_text(verse)) {
+                    written += 1;
+                } else {
+                    warn!("Skipping {} (not in {})", verse.osis_id, spec.versification);
+                }
             }
+        }
+
+        if written == 0 {
+            let _ = fs::remove_dir_all(&data_path);
+            return Err(EngineError::NoVersesFit(spec.versification.clone()));
+        }
 
-            org_crosswire_sword_SWModule_begin(h_module);
-            let mut current_book: Option<String> = None;
-            let mut chapters = Vec::new();
-            let mut current_chapter = 0;
-            let mut verse_count = 0;
+        fs::write(&conf_path, spec.conf(has_strongs))
+            .map_err(|e| EngineError::io(&conf_path, e))?;
 
-            loop {
-                if org_crosswire_sword_SWModule_popError(h_module) != 0 {
-                    break;
-                }
-                let key_ptr = org_crosswire_sword_SWModule_getKeyText(h_module);
-                if key_ptr.is_null() {
-                    break;
-                }
+        let mut inner = self.lock()?;
+        self.rebuild_mgr(&mut inner)?;
+        info!("Module '{}' created ({} verses)", spec.name, written);
+        Ok(written)
+    }
 
-                let key = CStr::from_ptr(key_ptr).to_string_lossy();
-                let parts: Vec<&str> = key.split_whitespace().collect();
-                if parts.len() < 2 {
-                    org_crosswire_sword_SWModule_next(h_module);
-                    continue;
-                }
+    // ------------------- BIBLE STRUCTURE -------------------
 
-                let chap_part = parts.last().unwrap();
-                let book_part = parts[..parts.len() - 1].join(" ");
-                let chapter: i32 = chap_part
-                    .split(':')
-                    .next()
-                    .and_then(|c| c.parse().ok())
-                    .unwrap_or(0);
-
-                if current_book.as_deref() != Some(&book_part) {
-                    if let Some(prev) = current_book.take() {
-                        if verse_count > 0 {
-                            chapters.push(ModuleChapter {
-                                number: current_chapter,
-                                verse_count,
-                            });
-                        }
-                        books.push(ModuleBook {
-                            name: prev,
-                            chapters: chapters.clone(),
-                        });
-                    }
-                    current_book = Some(book_part);
-                    chapters.clear();
-                    current_chapter = chapter;
-                    verse_count = 0;
-                }
+    pub fn get_bible_structure(&self, module_name: &str) -> EngineResult<Vec<ModuleBook>> {
+        let mut books = Vec::new();
+        let inner = self.lock()?;
+        let module = inner.mgr.module(module_name)?;
+
+        module.begin();
+        let mut current_book: Option<String> = None;
+        let mut chapters = Vec::new();
+        let mut current_chapter = 0;
+        let mut verse_count = 0;
+
+        let mut more = module.is_valid();
+        while more {
+            let Some(key) = module.key_text() else {
+                break;
+            };
+
+            let parts: Vec<&str> = key.split_whitespace().collect();
+            if parts.len() < 2 {
+                more = module.next();
+                continue;
+            }
 
-                if chapter != current_chapter {
+            let chap_part = parts.last().unwrap();
+            let book_part = parts[..parts.len() - 1].join(" ");
+            let chapter: i32 = chap_part
+                .split(':')
+                .next()
+                .and_then(|c| c.parse().ok())
+                .unwrap_or(0);
+
+            if current_book.as_deref() != Some(&book_part) {
+                if let Some(prev) = current_book.take() {
                     if verse_count > 0 {
                         chapters.push(ModuleChapter {
                             number: current_chapter,
                             verse_count,
                         });
                     }
-                    current_chapter = chapter;
-                    verse_count = 0;
+                    books.push(ModuleBook {
+                        name: prev,
+                        chapters: chapters.clone(),
+                    });
                 }
-                verse_count += 1;
-                org_crosswire_sword_SWModule_next(h_module);
+                current_book = Some(book_part);
+                chapters.clear();
+                current_chapter = chapter;
+                verse_count = 0;
             }
-            if let Some(last) = current_book {
+
+            if chapter != current_chapter {
                 if verse_count > 0 {
                     chapters.push(ModuleChapter {
                         number: current_chapter,
                         verse_count,
                     });
                 }
-                books.push(ModuleBook {
-                    name: last,
-                    chapters,
+                current_chapter = chapter;
+                verse_count = 0;
+            }
+            verse_count += 1;
+            more = module.next();
+        }
+        if let Some(last) = current_book {
+            if verse_count > 0 {
+                chapters.push(ModuleChapter {
+                    number: current_chapter,
+                    verse_count,
                 });
             }
+            books.push(ModuleBook {
+                name: last,
+                chapters,
+            });
         }
-        books
+        Ok(books)
     }
 
     // ------------------- HELPERS -------------------
 
-    fn ptr_to_str(&self, ptr: *const i8) -> String {
-        if ptr.is_null() {
-            "Unknown".to_string()
-        } else {
-            unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() }
+    /// The per-user data directory holding the SWORD library and logs,
+    /// unless `XBIBLE_DATA_DIR` names another
+    pub fn get_sword_path() -> EngineResult<PathBuf> {
+        if let Some(path) = std::env::var_os(DATA_DIR_VAR) {
+            let path = PathBuf::from(path);
+            fs::create_dir_all(&path).map_err(|e| EngineError::io(&path, e))?;
+            return Ok(path);
         }
-    }
 
-    fn get_sword_path() -> PathBuf {
-        let proj_dirs = ProjectDirs::from("org", "flame", "xbible").expect("Path error");
+        let proj_dirs =
+            ProjectDirs::from("org", "flame", "xbible").ok_or(EngineError::NoDataDirectory)?;
         let path = proj_dirs.data_local_dir().to_path_buf();
-        fs::create_dir_all(&path).ok();
-        path
+        fs::create_dir_all(&path).map_err(|e| EngineError::io(&path, e))?;
+        Ok(path)
     }
 
-    fn prepare_app_directory(path: &PathBuf) {
+    /// Creates the folders SWORD expects, and a `sword.conf` for a new
+    /// library. An existing configuration, such as that of `~/.sword`
+    /// opened with `--data-dir`, is left as it is.
+    fn prepare_app_directory(path: &Path) -> EngineResult<()> {
         // 1. Create the fundamental SWORD structure
-        let _ = fs::create_dir_all(path.join("mods.d"));
-        let _ = fs::create_dir_all(path.join("modules"));
+        for dir in [path.join("mods.d"), path.join("modules")] {
+            fs::create_dir_all(&dir).map_err(|e| EngineError::io(&dir, e))?;
+        }
 
         // 2. CRITICAL: Create the specific folder the InstallMgr uses for Remote Sources
         // If this isn't here, the 'syncConfig' download has nowhere to land.
@@ -382,10 +578,13 @@ impl SwordEngine {
             .join("InstallMgr")
             .join("RemoteSources")
             .join("CrossWire");
-        let _ = fs::create_dir_all(&remote_sources);
+        fs::create_dir_all(&remote_sources).map_err(|e| EngineError::io(&remote_sources, e))?;
 
-        let abs_path_str = path.to_string_lossy().replace("\\", "/");
         let conf_path = path.join("sword.conf");
+        if conf_path.exists() {
+            return Ok(());
+        }
+        let abs_path_str = path.to_string_lossy().replace("\\", "/");
 
         // Use the absolute path for DataPath.
         // We remove the #[wrap] logic here as per your permanent fix requirements.
@@ -404,19 +603,8 @@ Directory=/ftpmirror/pub/sword/raw
             abs_path_str
         );
 
-        if let Ok(mut file) = fs::File::create(conf_path) {
-            let _ = writeln!(file, "{}", config);
-        }
-    }
-}
-
-impl Drop for SwordEngine {
-    fn drop(&mut self) {
-        let inner = self.inner.lock().unwrap();
-        unsafe {
-            println!("[SwordEngine] Cleaning up SWORD handles...");
-            org_crosswire_sword_InstallMgr_delete(inner.install_mgr);
-            org_crosswire_sword_SWMgr_delete(inner.mgr);
-        }
+        fs::File::create(&conf_path)
+            .and_then(|mut file| writeln!(file, "{}", config))
+            .map_err(|e| EngineError::io(&conf_path, e))
     }
 }
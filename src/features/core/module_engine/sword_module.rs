@@ -1,9 +1,13 @@
-#[derive(Debug, Clone)]
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
 pub struct SwordModule {
     pub name: String,
     pub description: String,
     pub category: String,
     pub language: String,
+    /// "StrongsNumbers", "GreekDef", "HebrewDef", ...
+    pub features: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -16,4 +20,115 @@ pub struct ModuleBook {
 pub struct ModuleChapter {
     pub number: i32,
     pub verse_count: i32,
-}
\ No newline at end of file
+}
+
+/// Search strategies understood by `SWModule::search`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchType {
+    Regex = 1,
+    Phrase = -1,
+    MultiWord = -2,
+    EntryAttribute = -3,
+    Lucene = -4,
+}
+
+impl SearchType {
+    /// The names the CLI and HTTP API accept: phrase, words or regex
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "phrase" => Some(Self::Phrase),
+            "words" | "multiword" => Some(Self::MultiWord),
+            "regex" => Some(Self::Regex),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub module: String,
+    pub key: String,
+    pub score: i64,
+}
+
+/// An entry looked up in a dictionary/lexicon module
+#[derive(Debug, Clone, Serialize)]
+pub struct LexiconEntry {
+    pub module: String,
+    pub key: String,
+    pub text: String,
+}
+
+/// Lexicons that Strong's numbers are looked up in, found once for many lookups
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StrongsLexicons {
+    /// The first installed module with the GreekDef feature
+    pub greek: Option<String>,
+    /// The first installed module with the HebrewDef feature
+    pub hebrew: Option<String>,
+}
+
+/// Metadata for a text module compiled locally from an imported Bible
+#[derive(Debug, Clone)]
+pub struct ModuleSpec {
+    pub name: String,
+    pub description: String,
+    pub language: String,
+    pub version: String,
+    pub versification: String,
+    /// Where the text came from, recorded in the conf's About field
+    pub source: String,
+}
+
+impl ModuleSpec {
+    pub fn new(name: &str, description: &str) -> Self {
+        Self {
+            name: name
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+                .collect(),
+            description: description.replace('\n', " "),
+            language: "en".to_string(),
+            version: "1.0".to_string(),
+            versification: "KJV".to_string(),
+            source: String::new(),
+        }
+    }
+
+    /// DataPath relative to the SWORD root, as written in the conf
+    pub fn data_path(&self) -> String {
+        format!("./modules/texts/ztext/{}/", self.name.to_lowercase())
+    }
+
+    /// The mods.d entry describing a zText module written by `SwordEngine::create_module`
+    pub fn conf(&self, has_strongs: bool) -> String {
+        let mut conf = format!(
+            "[{}]\nDataPath={}\nModDrv=zText\nSourceType=OSIS\nEncoding=UTF-8\n\
+             CompressType=ZIP\nBlockType=BOOK\nVersification={}\nLang={}\n\
+             Description={}\nVersion={}\nMinimumVersion=1.5.9\n",
+            self.name,
+            self.data_path(),
+            self.versification,
+            self.language,
+            self.description,
+            self.version
+        );
+        if !self.source.is_empty() {
+            conf.push_str(&format!("About=Compiled by xBible from {}\n", self.source));
+        }
+        for filter in [
+            "OSISStrongs",
+            "OSISMorph",
+            "OSISFootnotes",
+            "OSISScripref",
+            "OSISHeadings",
+            "OSISRedLetterWords",
+        ] {
+            conf.push_str(&format!("GlobalOptionFilter={filter}\n"));
+        }
+        if has_strongs {
+            conf.push_str("Feature=StrongsNumbers\n");
+        }
+        conf
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Offered for every module; SWORD attaches the transliterator to all of them
+const TRANSLITERATION: &str = "Transliteration";
+
+/// Options whose filters rewrite letters rather than markup. The reader
+/// parses raw entries, so it runs these over each word itself.
+pub const LETTER_OPTIONS: [&str; 5] = [
+    "Hebrew Vowel Points",
+    "Hebrew Cantillation",
+    "Greek Accents",
+    "Arabic Vowel Points",
+    TRANSLITERATION,
+];
+
+/// A SWORD global option as offered for one module
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TextOption {
+    pub name: String,
+    /// SWORD's one-line description
+    pub tip: Option<String>,
+    pub values: Vec<String>,
+    pub value: String,
+}
+
+/// The option a `GlobalOptionFilter` entry switches, as SWORD's filters name it
+pub fn option_name(filter: &str) -> Option<&'static str> {
+    let name = match filter {
+        "OSISHeadings" | "GBFHeadings" | "ThMLHeadings" => "Headings",
+        "OSISStrongs" | "GBFStrongs" | "ThMLStrongs" => "Strong's Numbers",
+        "OSISMorph" | "GBFMorph" | "ThMLMorph" => "Morphological Tags",
+        "OSISLemma" | "ThMLLemma" => "Lemmas",
+        "OSISFootnotes" | "GBFFootnotes" | "ThMLFootnotes" => "Footnotes",
+        "OSISScripref" | "ThMLScripref" => "Cross-references",
+        "OSISRedLetterWords" | "GBFRedLetterWords" => "Words of Christ in Red",
+        "OSISMorphSegmentation" => "Morpheme Segmentation",
+        "OSISGlosses" | "OSISRuby" => "Glosses",
+        "OSISXlit" => "Transliterated Forms",
+        "OSISEnum" => "Enumerations",
+        "OSISVariants" | "ThMLVariants" => "Textual Variants",
+        "UTF8GreekAccents" => "Greek Accents",
+        "UTF8HebrewPoints" => "Hebrew Vowel Points",
+        "UTF8ArabicPoints" => "Arabic Vowel Points",
+        "UTF8Cantillation" => "Hebrew Cantillation",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Options switched by the `GlobalOptionFilter` lines of `module`'s section
+/// in a mods.d file; `None` when the file does not describe `module`
+pub fn module_options(conf: &str, module: &str) -> Option<Vec<String>> {
+    let mut lines = conf.lines().map(str::trim);
+    lines.find(|line| {
+        line.strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+            .is_some_and(|name| name.eq_ignore_ascii_case(module))
+    })?;
+
+    let mut options: Vec<String> = Vec::new();
+    for line in lines.take_while(|line| !line.starts_with('[')) {
+        let Some((key, filter)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim() != "GlobalOptionFilter" {
+            continue;
+        }
+        // "OSISReferenceLinks|Option Name|Tip|type|subtype|default"
+        let filter = filter.trim();
+        let name = match filter.split_once('|') {
+            Some(("OSISReferenceLinks", params)) => params.split('|').next(),
+            _ => option_name(filter),
+        };
+        if let Some(name) = name
+            && !options.iter().any(|o| o == name)
+        {
+            options.push(name.to_string());
+        }
+    }
+    if !options.iter().any(|o| o == TRANSLITERATION) {
+        options.push(TRANSLITERATION.to_string());
+    }
+    Some(options)
+}
+
+/// The values the user picked, by option name, kept across launches
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TextOptions(BTreeMap<String, String>);
+
+impl TextOptions {
+    /// The picked value when `values` still has it; otherwise switches are
+    /// on and other options take SWORD's first value
+    pub fn value(&self, option: &str, values: &[String]) -> String {
+        if let Some(value) = self.0.get(option)
+            && values.contains(value)
+        {
+            return value.clone();
+        }
+        let fallback = values.iter().find(|v| *v == "On").or(values.first());
+        fallback.cloned().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, option: &str, value: &str) {
+        self.0.insert(option.to_string(), value.to_string());
+    }
+
+    /// False only once the user switched `option` off
+    pub fn is_on(&self, option: &str) -> bool {
+        self.0.get(option).is_none_or(|value| value != "Off")
+    }
+
+    /// Whether the filter behind a letter option changes text at the
+    /// picked value: stripping when off, transliterating when not
+    pub fn rewrites(&self, option: &str) -> bool {
+        if option == TRANSLITERATION {
+            self.0.get(option).is_some_and(|value| value != "Off")
+        } else {
+            !self.is_on(option)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONF: &str = "[KJV]
+DataPath=./modules/texts/ztext/kjv/
+GlobalOptionFilter=OSISStrongs
+GlobalOptionFilter=OSISMorph
+GlobalOptionFilter=OSISHeadings
+GlobalOptionFilter=OSISStrongs
+GlobalOptionFilter=OSISReferenceLinks|Reference Material Links|Hide or show links|RM|X-Ref|On
+GlobalOptionFilter=UnknownFilter
+
+[WLC]
+GlobalOptionFilter=UTF8HebrewPoints
+";
+
+    #[test]
+    fn reads_a_modules_options() {
+        assert_eq!(
+            module_options(CONF, "kjv").unwrap(),
+            [
+                "Strong's Numbers",
+                "Morphological Tags",
+                "Headings",
+                "Reference Material Links",
+                "Transliteration",
+            ]
+        );
+        assert_eq!(
+            module_options(CONF, "WLC").unwrap(),
+            ["Hebrew Vowel Points", "Transliteration"]
+        );
+        assert_eq!(module_options(CONF, "ESV"), None);
+    }
+
+    #[test]
+    fn falls_back_to_sword_defaults() {
+        let switch = ["Off".to_string(), "On".to_string()];
+        let scripts = ["Off".to_string(), "Latin".to_string()];
+        let mut options = TextOptions::default();
+        assert_eq!(options.value("Footnotes", &switch), "On");
+        assert_eq!(options.value("Transliteration", &scripts), "Off");
+        assert!(!options.rewrites("Greek Accents"));
+
+        options.set("Greek Accents", "Off");
+        options.set("Transliteration", "Cyrillic");
+        assert!(!options.is_on("Greek Accents"));
+        assert!(options.rewrites("Greek Accents"));
+        // A value the module no longer offers falls back
+        assert_eq!(options.value("Transliteration", &scripts), "Off");
+    }
+}
@@ -2,7 +2,9 @@ use adw::prelude::*;
 use relm4::{Component, ComponentParts, factory::FactoryVecDeque, prelude::*};
 use std::sync::Arc;
 
-use crate::features::core::module_engine::sword_engine::SwordEngine;
+use crate::features::core::{
+    components::toast::show_error, module_engine::sword_engine::SwordEngine,
+};
 
 // --- 1. The Book Cover Factory Component ---
 
@@ -119,6 +121,7 @@ pub struct LibraryPage {
     engine: Arc<SwordEngine>,
     modules: FactoryVecDeque<ModuleItem>,
     is_sidebar_visible: bool,
+    toasts: adw::ToastOverlay,
 }
 
 #[derive(Debug)]
@@ -164,24 +167,33 @@ impl Component for LibraryPage {
                         }
                     },
 
-                     #[wrap(Some)]
-                    set_content=&gtk::ScrolledWindow {
-                        set_hscrollbar_policy: gtk::PolicyType::Never,
-                        set_vexpand: true,
-
-                        // We name this container so we can mount the factory into it manually
-                        #[name = "library_grid"]
-                        gtk::FlowBox {
-                            set_valign: gtk::Align::Start,
-                            set_max_children_per_line: 8,
-                            set_min_children_per_line: 2,
-                            set_selection_mode: gtk::SelectionMode::None,
-                            set_activate_on_single_click: true,
-                            set_margin_all: 24,
-                            set_column_spacing: 12,
-                            set_row_spacing: 12,
+                    #[wrap(Some)]
+                    set_content = &gtk::Box {
+                        #[local_ref]
+                        toasts -> adw::ToastOverlay {
+                            set_hexpand: true,
+                            set_vexpand: true,
+
+                            #[wrap(Some)]
+                            set_child = &gtk::ScrolledWindow {
+                                set_hscrollbar_policy: gtk::PolicyType::Never,
+                                set_vexpand: true,
+
+                                // We name this container so we can mount the factory into it manually
+                                #[name = "library_grid"]
+                                gtk::FlowBox {
+                                    set_valign: gtk::Align::Start,
+                                    set_max_children_per_line: 8,
+                                    set_min_children_per_line: 2,
+                                    set_selection_mode: gtk::SelectionMode::None,
+                                    set_activate_on_single_click: true,
+                                    set_margin_all: 24,
+                                    set_column_spacing: 12,
+                                    set_row_spacing: 12,
+                                }
+                            }
                         }
-                }
+                    }
             }
         }
     }
@@ -202,12 +214,14 @@ impl Component for LibraryPage {
             category,
             engine,
             modules,
-            is_sidebar_visible
+            is_sidebar_visible,
+            toasts: adw::ToastOverlay::new(),
         };
 
         // Populate initial data
         model.sync_modules();
 
+        let toasts = &model.toasts.clone();
         let widgets = view_output!();
 
         // MANUALLY mount the factory's internal widget into the FlowBox in our view
@@ -242,8 +256,12 @@ impl LibraryPage {
             LibraryPageCategory::Map => self.engine.get_map_modules(),
             // For categories not yet specifically handled in the engine,
             // you can return an empty vec or a general fetcher
-            LibraryPageCategory::AudioBible => Vec::new(),
+            LibraryPageCategory::AudioBible => Ok(Vec::new()),
         };
+        let modules = modules.unwrap_or_else(|e| {
+            show_error(&self.toasts, &e);
+            Vec::new()
+        });
 
         // 3. Push the results into the UI Factory
         for module in modules {
@@ -1,3 +1,4 @@
 pub mod library;
+pub mod plans;
 pub mod study;
 pub mod store;
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod plans_page;
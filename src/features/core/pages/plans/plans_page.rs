@@ -0,0 +1,487 @@
+use adw::prelude::*;
+use relm4::prelude::*;
+use std::{path::PathBuf, sync::Arc};
+
+use chrono::{Local, NaiveDate};
+
+use crate::features::core::{
+    components::toast::show_error,
+    module_engine::{error::EngineResult, sword_engine::SwordEngine, sword_module::ModuleBook},
+    reading_plan::{self, BuiltInPlan, ReadingPlan, progress::PlanProgress},
+    settings::Settings,
+};
+
+/// Missed days listed at once; catching up clears the rest
+const OVERDUE_SHOWN: usize = 7;
+
+pub struct PlansPage {
+    engine: Arc<SwordEngine>,
+    is_sidebar_visible: bool,
+    imported: Vec<ReadingPlan>,
+    progress: Option<PlanProgress>,
+    content: gtk::Box,
+    toasts: adw::ToastOverlay,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum PlanChoice {
+    BuiltIn(BuiltInPlan),
+    /// Index into the imported plans
+    Imported(usize),
+}
+
+#[derive(Debug)]
+pub enum PlansPageInput {
+    /// Show the readings for the current date
+    Refresh,
+    Start(PlanChoice),
+    Import,
+    Imported(PathBuf),
+    MarkRead(usize, bool),
+    /// Move the schedule so the first missed day is due today
+    CatchUp,
+    Stop,
+}
+
+#[derive(Debug)]
+pub enum PlansPageOutput {
+    ToggleSidebar,
+    /// Show a reading in the Study page
+    OpenReading(String),
+}
+
+#[derive(Debug)]
+pub enum PlansPageCommand {
+    /// The book outline a built-in plan is laid out over
+    StructureLoaded(BuiltInPlan, EngineResult<Vec<ModuleBook>>),
+}
+
+#[relm4::component(pub)]
+impl Component for PlansPage {
+    type Init = (Arc<SwordEngine>, bool);
+    type Input = PlansPageInput;
+    type Output = PlansPageOutput;
+    type CommandOutput = PlansPageCommand;
+
+    view! {
+        adw::NavigationPage {
+            set_title: "Reading Plan",
+
+            connect_map[sender] => move |_| {
+                sender.input(PlansPageInput::Refresh);
+            },
+
+            #[wrap(Some)]
+            set_child = &adw::ToolbarView {
+                add_top_bar = &adw::HeaderBar {
+                    #[wrap(Some)]
+                    set_title_widget = &adw::WindowTitle {
+                        set_title: "Reading Plan",
+                    },
+
+                    pack_start = &gtk::ToggleButton {
+                        set_icon_name: "sidebar-show-symbolic",
+                        #[watch]
+                        set_active: model.is_sidebar_visible,
+
+                        connect_clicked[sender] => move |_| {
+                            let _ = sender.output(PlansPageOutput::ToggleSidebar);
+                        }
+                    },
+
+                    pack_end = &gtk::Button {
+                        set_icon_name: "document-open-symbolic",
+                        set_tooltip_text: Some("Import Plan"),
+                        connect_clicked => PlansPageInput::Import,
+                    },
+                },
+
+                #[wrap(Some)]
+                set_content = &gtk::Box {
+                    #[local_ref]
+                    toasts -> adw::ToastOverlay {
+                        set_hexpand: true,
+                        set_vexpand: true,
+
+                        #[wrap(Some)]
+                        set_child = &gtk::ScrolledWindow {
+                            set_hscrollbar_policy: gtk::PolicyType::Never,
+
+                            adw::Clamp {
+                                set_maximum_size: 720,
+                                set_margin_all: 24,
+
+                                #[local_ref]
+                                content -> gtk::Box {
+                                    set_orientation: gtk::Orientation::Vertical,
+                                    set_spacing: 24,
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+        }
+    }
+
+    fn init(
+        init: Self::Init,
+        _root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let (engine, is_sidebar_visible) = init;
+        let settings = Settings::load(&engine.sword_path);
+
+        let model = PlansPage {
+            engine,
+            is_sidebar_visible,
+            imported: settings.imported_plans,
+            progress: settings.reading_plan,
+            content: gtk::Box::default(),
+            toasts: adw::ToastOverlay::new(),
+        };
+
+        let toasts = &model.toasts.clone();
+        let content = &model.content.clone();
+        let widgets = view_output!();
+
+        model.render(&sender);
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>, root: &Self::Root) {
+        match message {
+            PlansPageInput::Refresh => {}
+            PlansPageInput::Start(PlanChoice::BuiltIn(plan)) => {
+                let Some(module) = self.outline_module() else {
+                    show_error(&self.toasts, &"Install a Bible to follow this plan");
+                    return;
+                };
+                let engine = self.engine.clone();
+                sender.spawn_oneshot_command(move || {
+                    PlansPageCommand::StructureLoaded(plan, engine.get_bible_structure(&module))
+                });
+                return;
+            }
+            PlansPageInput::Start(PlanChoice::Imported(idx)) => {
+                if let Some(plan) = self.imported.get(idx).cloned() {
+                    self.start(plan);
+                }
+            }
+            PlansPageInput::Import => {
+                let filter = gtk::FileFilter::new();
+                filter.set_name(Some("Reading Plans"));
+                filter.add_pattern("*.txt");
+                filter.add_pattern("*.plan");
+
+                let dialog = gtk::FileDialog::builder()
+                    .title("Import Reading Plan")
+                    .default_filter(&filter)
+                    .build();
+                let window = root.root().and_downcast::<gtk::Window>();
+                let sender = sender.clone();
+                dialog.open(
+                    window.as_ref(),
+                    gtk::gio::Cancellable::NONE,
+                    move |result| {
+                        if let Ok(file) = result
+                            && let Some(path) = file.path()
+                        {
+                            sender.input(PlansPageInput::Imported(path));
+                        }
+                    },
+                );
+                return;
+            }
+            PlansPageInput::Imported(path) => match ReadingPlan::open(&path) {
+                Ok(plan) => {
+                    self.imported.retain(|imported| imported.name != plan.name);
+                    self.imported.push(plan);
+                    self.save();
+                }
+                Err(e) => {
+                    let message = format!("Could not import {}: {}", path.display(), e);
+                    show_error(&self.toasts, &message);
+                }
+            },
+            PlansPageInput::MarkRead(day, read) => {
+                if let Some(progress) = &mut self.progress {
+                    progress.mark(day, read);
+                    self.save();
+                }
+            }
+            PlansPageInput::CatchUp => {
+                if let Some(progress) = &mut self.progress {
+                    progress.catch_up(today());
+                    self.save();
+                }
+            }
+            PlansPageInput::Stop => {
+                self.progress = None;
+                self.save();
+            }
+        }
+        self.render(&sender);
+    }
+
+    fn update_cmd(
+        &mut self,
+        message: Self::CommandOutput,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match message {
+            PlansPageCommand::StructureLoaded(plan, Ok(structure)) => {
+                self.start(plan.build(&structure));
+            }
+            PlansPageCommand::StructureLoaded(_, Err(e)) => show_error(&self.toasts, &e),
+        }
+        self.render(&sender);
+    }
+}
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+impl PlansPage {
+    /// The Bible built-in plans are laid out over: the one in the front
+    /// study tab, or else the first installed
+    fn outline_module(&self) -> Option<String> {
+        let modules = self.engine.get_bible_modules().unwrap_or_else(|e| {
+            show_error(&self.toasts, &e);
+            Vec::new()
+        });
+        let session = Settings::load(&self.engine.sword_path).session;
+        let front = session.tabs.get(session.selected).map(|tab| &tab.module);
+        modules
+            .iter()
+            .find(|module| Some(&module.name) == front)
+            .or(modules.first())
+            .map(|module| module.name.clone())
+    }
+
+    fn start(&mut self, plan: ReadingPlan) {
+        self.progress = Some(PlanProgress::new(plan, today()));
+        self.save();
+    }
+
+    fn save(&self) {
+        let dir = &self.engine.sword_path;
+        let mut settings = Settings::load(dir);
+        settings.imported_plans = self.imported.clone();
+        settings.reading_plan = self.progress.clone();
+        if let Err(e) = settings.save(dir) {
+            let message = format!("Could not save {}: {}", Settings::path(dir).display(), e);
+            show_error(&self.toasts, &message);
+        }
+    }
+
+    /// Rebuilds the page for the plan's state today
+    fn render(&self, sender: &ComponentSender<Self>) {
+        let content = &self.content;
+        while let Some(child) = content.first_child() {
+            content.remove(&child);
+        }
+
+        match &self.progress {
+            Some(progress) => {
+                let today = today();
+                content.append(&Self::plan_group(progress, today, sender));
+                if let Some(day) = progress.day_on(today) {
+                    content.append(&Self::today_group(progress, day, sender));
+                }
+                let overdue = progress.overdue(today);
+                if !overdue.is_empty() {
+                    content.append(&Self::overdue_group(progress, &overdue, sender));
+                }
+            }
+            None => content.append(&self.choice_group(sender)),
+        }
+    }
+
+    /// Name, how far along, and a way to stop
+    fn plan_group(
+        progress: &PlanProgress,
+        today: NaiveDate,
+        sender: &ComponentSender<Self>,
+    ) -> adw::PreferencesGroup {
+        let total = progress.plan.days.len();
+        let read = progress.read_count();
+        let description = if progress.is_finished() {
+            format!("All {} days read", total)
+        } else if today < progress.start {
+            format!("Starts {}", progress.start.format("%B %-d"))
+        } else {
+            match progress.day_on(today) {
+                Some(day) => format!("Day {} of {} · {} read", day + 1, total, read),
+                None => format!("Every day is due · {} of {} read", read, total),
+            }
+        };
+
+        let group = adw::PreferencesGroup::builder()
+            .title(&progress.plan.name)
+            .description(description)
+            .build();
+
+        let finished = progress.is_finished();
+        let stop = gtk::Button::builder()
+            .label(if finished { "Finish" } else { "Stop Plan" })
+            .valign(gtk::Align::Center)
+            .css_classes(vec!["flat"])
+            .build();
+        let input = sender.input_sender().clone();
+        let name = progress.plan.name.clone();
+        stop.connect_clicked(move |button| {
+            if finished {
+                input.emit(PlansPageInput::Stop);
+                return;
+            }
+            let dialog = adw::AlertDialog::new(
+                Some("Stop the Plan?"),
+                Some(&format!("Progress through “{}” will be lost.", name)),
+            );
+            dialog.add_responses(&[("cancel", "_Cancel"), ("stop", "_Stop")]);
+            dialog.set_response_appearance("stop", adw::ResponseAppearance::Destructive);
+            dialog.set_close_response("cancel");
+            let input = input.clone();
+            dialog.connect_response(Some("stop"), move |_, _| input.emit(PlansPageInput::Stop));
+            dialog.present(Some(button));
+        });
+        group.set_header_suffix(Some(&stop));
+
+        let bar = gtk::ProgressBar::builder()
+            .fraction(read as f64 / total.max(1) as f64)
+            .build();
+        group.add(&bar);
+        group
+    }
+
+    /// Today's readings, each opening in the Study page
+    fn today_group(
+        progress: &PlanProgress,
+        day: usize,
+        sender: &ComponentSender<Self>,
+    ) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder().title("Today").build();
+        for reading in &progress.plan.days[day] {
+            group.add(&Self::reading_row(reading, sender));
+        }
+
+        let done = adw::SwitchRow::builder()
+            .title("Read")
+            .active(progress.is_read(day))
+            .build();
+        let input = sender.input_sender().clone();
+        done.connect_active_notify(move |row| {
+            input.emit(PlansPageInput::MarkRead(day, row.is_active()));
+        });
+        group.add(&done);
+        group
+    }
+
+    /// Days missed, with the choice of reading them or moving the schedule
+    fn overdue_group(
+        progress: &PlanProgress,
+        overdue: &[usize],
+        sender: &ComponentSender<Self>,
+    ) -> adw::PreferencesGroup {
+        let description = match overdue.len() {
+            1 => "1 day behind".to_string(),
+            n => format!("{} days behind", n),
+        };
+        let group = adw::PreferencesGroup::builder()
+            .title("Missed")
+            .description(description)
+            .build();
+
+        let catch_up = gtk::Button::builder()
+            .label("Catch Up")
+            .tooltip_text("Continue from the first missed day, starting today")
+            .valign(gtk::Align::Center)
+            .css_classes(vec!["suggested-action"])
+            .build();
+        let input = sender.input_sender().clone();
+        catch_up.connect_clicked(move |_| input.emit(PlansPageInput::CatchUp));
+        group.set_header_suffix(Some(&catch_up));
+
+        for &day in overdue.iter().take(OVERDUE_SHOWN) {
+            let readings = &progress.plan.days[day];
+            let summary = reading_plan::summary(readings);
+            let row = adw::ActionRow::builder()
+                .title(format!("Day {}", day + 1))
+                .subtitle(gtk::glib::markup_escape_text(&summary))
+                .activatable(true)
+                .build();
+
+            let read = gtk::CheckButton::builder()
+                .tooltip_text("Read")
+                .valign(gtk::Align::Center)
+                .build();
+            let input = sender.input_sender().clone();
+            read.connect_toggled(move |check| {
+                input.emit(PlansPageInput::MarkRead(day, check.is_active()));
+            });
+            row.add_prefix(&read);
+
+            row.add_suffix(&gtk::Image::from_icon_name("go-next-symbolic"));
+            let output = sender.output_sender().clone();
+            let first = readings.first().cloned().unwrap_or_default();
+            row.connect_activated(move |_| {
+                output.emit(PlansPageOutput::OpenReading(first.clone()));
+            });
+            group.add(&row);
+        }
+        group
+    }
+
+    /// The plans to start, when none is being followed
+    fn choice_group(&self, sender: &ComponentSender<Self>) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder()
+            .title("Plans")
+            .description("Plans start today; import more as text files with one day per line")
+            .build();
+
+        let built_in = BuiltInPlan::ALL.map(|plan| {
+            let subtitle = plan.description().to_string();
+            (plan.name().to_string(), subtitle, PlanChoice::BuiltIn(plan))
+        });
+        let imported = self.imported.iter().enumerate().map(|(idx, plan)| {
+            let subtitle = format!("{} days · imported", plan.days.len());
+            (plan.name.clone(), subtitle, PlanChoice::Imported(idx))
+        });
+
+        for (title, subtitle, choice) in built_in.into_iter().chain(imported) {
+            let row = adw::ActionRow::builder()
+                .title(gtk::glib::markup_escape_text(&title))
+                .subtitle(subtitle)
+                .build();
+            let start = gtk::Button::builder()
+                .label("Start")
+                .valign(gtk::Align::Center)
+                .build();
+            let input = sender.input_sender().clone();
+            start.connect_clicked(move |_| input.emit(PlansPageInput::Start(choice)));
+            row.add_suffix(&start);
+            group.add(&row);
+        }
+        group
+    }
+
+    fn reading_row(reading: &str, sender: &ComponentSender<Self>) -> adw::ActionRow {
+        let summary = reading_plan::summary(&[reading.to_string()]);
+        let row = adw::ActionRow::builder()
+            .title(gtk::glib::markup_escape_text(&summary))
+            .activatable(true)
+            .build();
+        row.add_suffix(&gtk::Image::from_icon_name("go-next-symbolic"));
+
+        let output = sender.output_sender().clone();
+        let reading = reading.to_string();
+        row.connect_activated(move |_| {
+            output.emit(PlansPageOutput::OpenReading(reading.clone()));
+        });
+        row
+    }
+}
@@ -1,5 +1,7 @@
+use crate::features::core::components::toast::show_error;
 use crate::features::core::module_engine::sword_engine::SwordEngine;
 use crate::features::core::module_engine::sword_module::SwordModule;
+use crate::features::store::error::StoreError;
 use adw::prelude::*;
 use relm4::{Component, ComponentParts, ComponentSender, prelude::*, factory::FactoryVecDeque};
 use std::sync::Arc;
@@ -12,6 +14,8 @@ pub enum StorePageInput {
     UpdateList(Vec<SwordModule>),
     TriggerDownload(String),
     UpdateProgress(f64),
+    /// A refresh or download failed on the worker
+    Failed(Arc<StoreError>),
 }
 
 #[derive(Debug)]
@@ -25,6 +29,7 @@ pub struct StorePage {
     remote_modules: FactoryVecDeque<ModuleRow>,
     is_sidebar_visible: bool,
     is_loading: bool,
+    toasts: adw::ToastOverlay,
 }
 
 #[relm4::component(pub)]
@@ -58,27 +63,36 @@ impl Component for StorePage {
                             }
                         },
                         #[wrap(Some)]
-                        set_content = &gtk::ScrolledWindow {
-                            set_hscrollbar_policy: gtk::PolicyType::Never,
-                            gtk::Box {
-                                set_orientation: gtk::Orientation::Vertical,
-                                set_margin_all: 12,
-                                set_spacing: 6,
-
-                                gtk::Spinner {
-                                    #[watch]
-                                    set_visible: model.is_loading,
-                                    #[watch]
-                                    set_spinning: model.is_loading,
-                                    set_halign: gtk::Align::Center,
-                                },
-
-                                #[name = "module_list"]
-                                gtk::ListBox {
-                                    set_selection_mode: gtk::SelectionMode::None,
-                                    add_css_class: "boxed-list",
-                                    #[watch]
-                                    set_visible: !model.is_loading,
+                        set_content = &gtk::Box {
+                            #[local_ref]
+                            toasts -> adw::ToastOverlay {
+                                set_hexpand: true,
+                                set_vexpand: true,
+
+                                #[wrap(Some)]
+                                set_child = &gtk::ScrolledWindow {
+                                    set_hscrollbar_policy: gtk::PolicyType::Never,
+                                    gtk::Box {
+                                        set_orientation: gtk::Orientation::Vertical,
+                                        set_margin_all: 12,
+                                        set_spacing: 6,
+
+                                        gtk::Spinner {
+                                            #[watch]
+                                            set_visible: model.is_loading,
+                                            #[watch]
+                                            set_spinning: model.is_loading,
+                                            set_halign: gtk::Align::Center,
+                                        },
+
+                                        #[name = "module_list"]
+                                        gtk::ListBox {
+                                            set_selection_mode: gtk::SelectionMode::None,
+                                            add_css_class: "boxed-list",
+                                            #[watch]
+                                            set_visible: !model.is_loading,
+                                        }
+                                    }
                                 }
                             }
                         }
@@ -114,8 +128,10 @@ impl Component for StorePage {
             remote_modules,
             is_sidebar_visible,
             is_loading: false,
+            toasts: adw::ToastOverlay::new(),
         };
 
+        let toasts = &model.toasts.clone();
         let widgets = view_output!();
         widgets.module_list.append(&model.remote_modules.widget().clone());
 
@@ -146,7 +162,11 @@ impl Component for StorePage {
                 });
             }
             StorePageInput::UpdateProgress(p) => {
-                println!("Download progress: {}%", (p * 100.0) as i32);
+                log::debug!("Download progress: {}%", (p * 100.0) as i32);
+            }
+            StorePageInput::Failed(e) => {
+                self.is_loading = false;
+                show_error(&self.toasts, &e);
             }
         }
     }
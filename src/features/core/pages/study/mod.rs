@@ -1 +1,2 @@
-pub mod study_page;
\ No newline at end of file
+pub mod study_page;
+pub mod study_tabs;
@@ -1,12 +1,30 @@
 use adw::prelude::*;
 use relm4::prelude::*;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::features::{
-    bible::components::page::model::{BiblePage, StudyInput},
-    core::module_engine::{
-        sword_engine::SwordEngine,
-        sword_module::{ModuleBook, SwordModule},
+    bible::{
+        components::page::{
+            model::{BiblePage, BiblePageOutput, StudyInput},
+            position::ChapterPosition,
+            word::{InterlinearRow, InterlinearRows},
+        },
+        export::{ExportFormat, ExportOptions, ExportRequest},
+        transilation_engines::document::{DocumentBible, open_document},
+    },
+    core::{
+        components::toast::show_error,
+        module_engine::{
+            error::EngineResult,
+            sword_engine::SwordEngine,
+            sword_module::{ModuleBook, ModuleSpec, SearchHit, SwordModule},
+            text_options::TextOption,
+        },
+        settings::{
+            ReadingSettings, Settings,
+            session::{History, StudyTab},
+        },
     },
 };
 
@@ -16,6 +34,8 @@ pub struct StudyPage {
 
     // Data Structure
     available_modules: Vec<SwordModule>,
+    /// Local files (OSIS, USFM, USX, Zefania, VPL), listed after the SWORD modules in the dropdown
+    documents: Vec<Arc<dyn DocumentBible>>,
     bible_structure: Vec<ModuleBook>,
 
     // UI Models
@@ -24,6 +44,33 @@ pub struct StudyPage {
     chapter_list: gtk::StringList,
 
     bible_page: Controller<BiblePage>,
+    /// Read on past chapter and book ends instead of one chapter at a time
+    continuous: bool,
+    interlinear: bool,
+    interlinear_rows: InterlinearRows,
+    /// Appearance preferences last handed to the reader
+    reading: ReadingSettings,
+    /// Strong's-tagged modules offered for reverse-interlinear pairing
+    pairing_modules: Vec<String>,
+    /// SWORD options the selected module's filters offer
+    text_options: Vec<TextOption>,
+    text_options_box: gtk::Box,
+
+    // Lemma search
+    search_title: String,
+    search_results: Vec<SearchHit>,
+    is_search_visible: bool,
+    results_box: gtk::ListBox,
+
+    /// Range field of the export menu, following the selected chapter
+    export_entry: gtk::Entry,
+    toasts: adw::ToastOverlay,
+    /// Chapters this tab went back and forward through
+    history: History,
+    /// Reference to open once the outline arrives, for a restored tab or
+    /// one followed before the outline was read
+    restore: Option<String>,
+
     // Selection State
     selected_module_idx: usize,
     selected_book_idx: usize,
@@ -33,21 +80,65 @@ pub struct StudyPage {
 #[derive(Debug)]
 pub enum StudyPageInput {
     UpdateModule(u32),
+    OpenDocument,
+    DocumentOpened(PathBuf),
+    /// Compile the selected document into an installed SWORD module
+    CreateModule,
     UpdateBook(u32),
     UpdateChapter(u32),
+    /// The reader scrolled another chapter to the top of the view
+    ChapterInView(ChapterPosition),
+    ToggleContinuous(bool),
+    ToggleInterlinear(bool),
+    SetInterlinearRow(InterlinearRow, bool),
+    /// Appearance preferences changed
+    SetReading(ReadingSettings),
+    /// Index into the pairing dropdown; 0 means no pairing
+    SetPairedModule(u32),
+    /// Set a SWORD global option ("Greek Accents") and keep it for next time
+    SetTextOption(String, String),
+    SearchLemma(String),
+    /// Go to a reference, from a cross-reference in the reader or a
+    /// reading plan
+    FollowReference(String),
+    GoBack,
+    GoForward,
+    OpenSearchResult(usize),
+    SetSearchVisible(bool),
+    /// Ask where to save, then export
+    ExportPassage(ExportRequest),
+    ExportTo(ExportRequest, PathBuf),
+    /// Another BibleSync app navigated to this OSIS reference
+    BibleSyncNavigate(String),
 }
 
 #[derive(Debug)]
 pub enum StudyPageOutPut {
     ToggleSidebar,
+    /// The module, chapter or history changed
+    TabChanged(StudyTab),
+}
+
+#[derive(Debug)]
+pub enum StudyPageCommand {
+    /// Book outline of the module at this dropdown index, read off the UI thread
+    StructureLoaded(usize, EngineResult<Vec<ModuleBook>>),
+    /// A document was written out as a module; the named module and the
+    /// module list that now includes it
+    ModuleCreated(String, EngineResult<Vec<SwordModule>>),
+    /// Verses tagged with this Strong's number
+    LemmaFound(String, EngineResult<Vec<SearchHit>>),
+    /// A local file was parsed, or why it could not be
+    DocumentRead(PathBuf, Result<Arc<dyn DocumentBible>, String>),
 }
 
 #[relm4::component(pub)]
 impl Component for StudyPage {
-    type Init = (Arc<SwordEngine>, bool);
+    /// The tab to show comes last; a default one opens the first module
+    type Init = (Arc<SwordEngine>, bool, ReadingSettings, StudyTab);
     type Input = StudyPageInput;
     type Output = StudyPageOutPut;
-    type CommandOutput = ();
+    type CommandOutput = StudyPageCommand;
 
     view! {
         adw::NavigationPage {
@@ -64,6 +155,7 @@ impl Component for StudyPage {
                                 add_css_class: "linked",
 
                                 // 1. MODULE DROPDOWN
+                                #[name = "module_dropdown"]
                                 gtk::DropDown {
                                     set_model: Some(&model.module_list),
                                     connect_selected_item_notify[sender] => move |dd| {
@@ -72,6 +164,7 @@ impl Component for StudyPage {
                                 },
 
                                 // 2. BOOK DROPDOWN
+                                #[name = "book_dropdown"]
                                 gtk::DropDown {
                                     #[watch]
                                     set_model: Some(&model.book_list),
@@ -81,6 +174,7 @@ impl Component for StudyPage {
                                 },
 
                                 // 3. CHAPTER DROPDOWN
+                                #[name = "chapter_dropdown"]
                                 gtk::DropDown {
                                     #[watch]
                                     set_model: Some(&model.chapter_list),
@@ -97,11 +191,145 @@ impl Component for StudyPage {
                                 connect_clicked[sender] => move |_| {
                                     let _ = sender.output(StudyPageOutPut::ToggleSidebar);
                                 }
-                            }
+                            },
+
+                            pack_start = &gtk::Box {
+                                add_css_class: "linked",
+
+                                gtk::Button {
+                                    set_icon_name: "go-previous-symbolic",
+                                    set_tooltip_text: Some("Back"),
+                                    #[watch]
+                                    set_sensitive: model.history.can_go_back(),
+                                    connect_clicked => StudyPageInput::GoBack,
+                                },
+
+                                gtk::Button {
+                                    set_icon_name: "go-next-symbolic",
+                                    set_tooltip_text: Some("Forward"),
+                                    #[watch]
+                                    set_sensitive: model.history.can_go_forward(),
+                                    connect_clicked => StudyPageInput::GoForward,
+                                },
+                            },
+
+                            pack_start = &gtk::Button {
+                                set_icon_name: "document-open-symbolic",
+                                set_tooltip_text: Some("Open Bible Text"),
+                                connect_clicked => StudyPageInput::OpenDocument,
+                            },
+
+                            pack_start = &gtk::Button {
+                                set_icon_name: "document-save-symbolic",
+                                set_tooltip_text: Some("Create SWORD Module"),
+                                #[watch]
+                                set_visible: model.selected_document().is_some(),
+                                connect_clicked => StudyPageInput::CreateModule,
+                            },
+
+                            pack_end = &gtk::Box {
+                                add_css_class: "linked",
+
+                                gtk::ToggleButton {
+                                    set_icon_name: "view-dual-symbolic",
+                                    set_tooltip_text: Some("Interlinear"),
+                                    #[watch]
+                                    set_sensitive: model.current_module_has_strongs(),
+                                    #[watch]
+                                    set_active: model.interlinear,
+                                    connect_toggled[sender] => move |button| {
+                                        sender.input(StudyPageInput::ToggleInterlinear(button.is_active()));
+                                    }
+                                },
+
+                                #[local_ref]
+                                interlinear_menu -> gtk::MenuButton {
+                                    set_icon_name: "view-more-symbolic",
+                                    set_tooltip_text: Some("Interlinear Rows"),
+                                    #[watch]
+                                    set_sensitive: model.interlinear,
+                                },
+                            },
+
+                            pack_end = &gtk::Box {
+                                #[local_ref]
+                                text_options_menu -> gtk::MenuButton {
+                                    set_icon_name: "document-properties-symbolic",
+                                    set_tooltip_text: Some("Text Options"),
+                                    #[watch]
+                                    set_sensitive: !model.text_options.is_empty(),
+                                },
+                            },
+
+                            pack_end = &gtk::ToggleButton {
+                                set_icon_name: "view-continuous-symbolic",
+                                set_tooltip_text: Some("Continuous Scrolling"),
+                                #[watch]
+                                set_active: model.continuous,
+                                connect_toggled[sender] => move |button| {
+                                    sender.input(StudyPageInput::ToggleContinuous(button.is_active()));
+                                }
+                            },
+
+                            pack_end = &gtk::Box {
+                                #[local_ref]
+                                export_menu -> gtk::MenuButton {
+                                    set_icon_name: "document-send-symbolic",
+                                    set_tooltip_text: Some("Export Passage"),
+                                },
+                            },
                         },
 
                         #[wrap(Some)]
-                        set_content = model.bible_page.widget(),
+                        set_content = &gtk::Box {
+                            #[local_ref]
+                            toasts -> adw::ToastOverlay {
+                                set_hexpand: true,
+                                set_vexpand: true,
+
+                                #[wrap(Some)]
+                                set_child = &adw::OverlaySplitView {
+                                    set_sidebar_position: gtk::PackType::End,
+                                    #[watch]
+                                    set_show_sidebar: model.is_search_visible,
+                                    connect_show_sidebar_notify[sender] => move |split| {
+                                        sender.input(StudyPageInput::SetSearchVisible(split.shows_sidebar()));
+                                    },
+
+                                    #[wrap(Some)]
+                                    set_content = model.bible_page.widget(),
+
+                                    #[wrap(Some)]
+                                    set_sidebar = &gtk::Box {
+                                        set_orientation: gtk::Orientation::Vertical,
+                                        set_spacing: 6,
+                                        set_margin_all: 12,
+
+                                        gtk::Label {
+                                            add_css_class: "heading",
+                                            set_xalign: 0.0,
+                                            #[watch]
+                                            set_label: &model.search_title,
+                                        },
+
+                                        gtk::ScrolledWindow {
+                                            set_vexpand: true,
+                                            set_hscrollbar_policy: gtk::PolicyType::Never,
+
+                                            #[local_ref]
+                                            results_box -> gtk::ListBox {
+                                                add_css_class: "navigation-sidebar",
+                                                set_selection_mode: gtk::SelectionMode::None,
+                                                set_activate_on_single_click: true,
+                                                connect_row_activated[sender] => move |_, row| {
+                                                    sender.input(StudyPageInput::OpenSearchResult(row.index() as usize));
+                                                }
+                                            }
+                                        }
+                                    },
+                                },
+                            },
+                        },
                     }
                 }
             }
@@ -113,17 +341,29 @@ impl Component for StudyPage {
         _root: Self::Root,
         sender: ComponentSender<Self>,
     ) -> ComponentParts<Self> {
-        let (engine, is_sidebar_visible) = init;
+        let (engine, is_sidebar_visible, reading, tab) = init;
 
         // 1. Get all available Bible modules
-        let available_modules = engine.get_bible_modules();
+        let toasts = adw::ToastOverlay::new();
+        let available_modules = engine.get_bible_modules().unwrap_or_else(|e| {
+            show_error(&toasts, &e);
+            Vec::new()
+        });
         let module_names: Vec<String> = available_modules.iter().map(|m| m.name.clone()).collect();
         let module_list =
             gtk::StringList::new(&module_names.iter().map(|s| s.as_str()).collect::<Vec<_>>());
 
-        // 2. Load the initial structure for the first module
-        let initial_module_name = module_names.first().map(|s| s.as_str()).unwrap_or("");
-        let bible_structure = engine.get_bible_structure(initial_module_name);
+        // 2. Load the initial structure for the tab's module, else the first one
+        let initial_module_idx = module_names
+            .iter()
+            .position(|name| *name == tab.module)
+            .unwrap_or(0);
+        let initial_module_name = module_names
+            .get(initial_module_idx)
+            .map(|s| s.as_str())
+            .unwrap_or("");
+        Self::load_structure(&engine, &sender, initial_module_idx, initial_module_name);
+        let restore = Some(tab.reference).filter(|reference| !reference.is_empty());
 
         let book_list = gtk::StringList::new(&[]);
         let chapter_list = gtk::StringList::new(&[]);
@@ -132,20 +372,50 @@ impl Component for StudyPage {
             .launch((
                 engine.clone(),
                 initial_module_name.to_string(),
-                "Gen 1".to_string(),
+                restore.clone().unwrap_or_else(|| "Gen 1".to_string()),
+                reading.clone(),
             ))
-            .detach();
+            .forward(sender.input_sender(), |output| match output {
+                BiblePageOutput::SearchLemma(strong) => StudyPageInput::SearchLemma(strong),
+                BiblePageOutput::FollowReference(osis_ref) => {
+                    StudyPageInput::FollowReference(osis_ref)
+                }
+                BiblePageOutput::ChapterInView(chapter) => StudyPageInput::ChapterInView(chapter),
+            });
 
         let mut model = StudyPage {
             engine,
             is_sidebar_visible,
             available_modules,
-            bible_structure,
+            documents: Vec::new(),
+            bible_structure: Vec::new(),
             module_list,
             book_list,
             chapter_list,
             bible_page: bible_page,
-            selected_module_idx: 0,
+            continuous: false,
+            interlinear: reading.interlinear,
+            interlinear_rows: InterlinearRows::default(),
+            reading,
+            pairing_modules: Vec::new(),
+            text_options: Vec::new(),
+            text_options_box: gtk::Box::builder()
+                .orientation(gtk::Orientation::Vertical)
+                .spacing(6)
+                .margin_top(8)
+                .margin_bottom(8)
+                .margin_start(8)
+                .margin_end(8)
+                .build(),
+            search_title: String::new(),
+            search_results: Vec::new(),
+            is_search_visible: false,
+            results_box: gtk::ListBox::new(),
+            export_entry: gtk::Entry::new(),
+            toasts,
+            history: tab.history,
+            restore,
+            selected_module_idx: initial_module_idx,
             selected_book_idx: 0,
             selected_chapter: 0,
         };
@@ -154,47 +424,386 @@ impl Component for StudyPage {
         model.rebuild_books();
         model.rebuild_chapters(0);
 
+        model.pairing_modules = model
+            .available_modules
+            .iter()
+            .filter(|m| m.features.iter().any(|f| f == "StrongsNumbers"))
+            .map(|m| m.name.clone())
+            .collect();
+        let interlinear_menu = &model.build_interlinear_menu(&sender);
+        let export_menu = &model.build_export_menu(&sender);
+        let text_options_menu = &gtk::MenuButton::builder()
+            .popover(&gtk::Popover::builder().child(&model.text_options_box).build())
+            .build();
+        model.export_entry.set_text(&model.build_query_string());
+
+        let results_box = &model.results_box.clone();
+        let toasts = &model.toasts.clone();
         let widgets = view_output!();
+        widgets
+            .module_dropdown
+            .set_selected(model.selected_module_idx as u32);
 
         ComponentParts { model, widgets }
     }
 
-    fn update(&mut self, message: Self::Input, _sender: ComponentSender<Self>, _root: &Self::Root) {
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        sender: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
         match message {
+            StudyPageInput::OpenDocument => {
+                let filter = gtk::FileFilter::new();
+                filter.set_name(Some("Bible Texts"));
+                for pattern in [
+                    "*.osis.xml", "*.xml", "*.usx", "*.usfm", "*.sfm", "*.ptx", "*.vpl", "*.txt",
+                ] {
+                    filter.add_pattern(pattern);
+                }
+
+                let dialog = gtk::FileDialog::builder()
+                    .title("Open Bible Text")
+                    .default_filter(&filter)
+                    .build();
+                let window = root.root().and_downcast::<gtk::Window>();
+                let sender = sender.clone();
+                dialog.open(window.as_ref(), gtk::gio::Cancellable::NONE, move |result| {
+                    if let Ok(file) = result
+                        && let Some(path) = file.path()
+                    {
+                        sender.input(StudyPageInput::DocumentOpened(path));
+                    }
+                });
+            }
+            StudyPageInput::DocumentOpened(path) => {
+                sender.spawn_oneshot_command(move || {
+                    let document = open_document(&path);
+                    StudyPageCommand::DocumentRead(path, document)
+                });
+            }
+            StudyPageInput::CreateModule => {
+                if let Some(document) = self.selected_document().cloned() {
+                    let mut spec = ModuleSpec::new(&document.name(), &document.name());
+                    spec.source = document.name();
+
+                    let engine = self.engine.clone();
+                    sender.spawn_oneshot_command(move || {
+                        let created = engine
+                            .create_module(&spec, &document.books())
+                            .and_then(|_| engine.get_bible_modules());
+                        StudyPageCommand::ModuleCreated(spec.name, created)
+                    });
+                }
+            }
             StudyPageInput::UpdateModule(idx) => {
                 let idx = idx as usize;
-                if let Some(module) = self.available_modules.get(idx) {
-                    let module_name = module.name.clone();
+                // Selecting a restored tab's module at start-up reports it again
+                if idx == self.selected_module_idx {
+                    self.update_view(widgets, sender);
+                    return;
+                }
+                if let Some(document) = idx
+                    .checked_sub(self.available_modules.len())
+                    .and_then(|i| self.documents.get(i))
+                {
                     self.selected_module_idx = idx;
-                    // Re-fetch the whole structure for the new module
-                    self.bible_structure = self.engine.get_bible_structure(&module_name.clone());
+                    self.bible_structure = document.structure();
+                    self.selected_book_idx = 0;
+                    self.selected_chapter = 0;
                     self.rebuild_books();
                     self.rebuild_chapters(0);
-
-                     self.bible_page
-                    .emit( StudyInput::SetModule(module_name));
+                    // Local files are not run through SWORD's filters
+                    self.text_options.clear();
+                    self.rebuild_text_options(&sender);
+                    self.bible_page
+                        .emit(StudyInput::SetStructure(self.bible_structure.clone()));
+                    self.bible_page.emit(StudyInput::SetDocument(document.clone()));
+                    self.bible_page
+                        .emit(StudyInput::LoadReference(self.build_query_string()));
+                    self.chapter_changed(&sender);
+                } else if let Some(module) = self.available_modules.get(idx) {
+                    self.selected_module_idx = idx;
+                    // Re-fetch the whole structure for the new module
+                    Self::load_structure(&self.engine, &sender, idx, &module.name);
                 }
-
-               
-                
             }
             StudyPageInput::UpdateBook(idx) => {
+                // Notifications queued before a model swap or a scroll-driven
+                // selection no longer describe the dropdown
+                if idx == gtk::INVALID_LIST_POSITION
+                    || idx != widgets.book_dropdown.selected()
+                    || idx as usize == self.selected_book_idx
+                {
+                    self.update_view(widgets, sender);
+                    return;
+                }
                 let idx = idx as usize;
+                self.remember(Some(ChapterPosition {
+                    book: idx,
+                    chapter: 0,
+                }));
                 self.selected_book_idx = idx;
+                self.selected_chapter = 0;
                 self.rebuild_chapters(idx);
+                self.export_entry.set_text(&self.build_query_string());
                 self.bible_page
                     .emit(StudyInput::LoadReference(self.build_query_string()));
+                self.chapter_changed(&sender);
             }
             StudyPageInput::UpdateChapter(idx) => {
+                if idx == gtk::INVALID_LIST_POSITION
+                    || idx != widgets.chapter_dropdown.selected()
+                    || idx as usize == self.selected_chapter
+                {
+                    self.update_view(widgets, sender);
+                    return;
+                }
+                self.remember(Some(ChapterPosition {
+                    book: self.selected_book_idx,
+                    chapter: idx as usize,
+                }));
                 self.selected_chapter = idx as usize;
+                self.export_entry.set_text(&self.build_query_string());
                 self.bible_page
                     .emit(StudyInput::LoadReference(self.build_query_string()));
+                self.chapter_changed(&sender);
+            }
+            StudyPageInput::ChapterInView(chapter) => {
+                // Follow the reader without asking it to load anything
+                self.select_chapter(widgets, chapter);
+                self.chapter_changed(&sender);
+            }
+            StudyPageInput::ToggleContinuous(continuous) => {
+                self.continuous = continuous;
+                self.bible_page.emit(StudyInput::SetContinuous(continuous));
+            }
+            StudyPageInput::ToggleInterlinear(interlinear) => {
+                self.interlinear = interlinear;
+                self.bible_page.emit(StudyInput::SetInterlinear(interlinear));
+            }
+            StudyPageInput::SetInterlinearRow(row, shown) => {
+                self.interlinear_rows.set_shown(row, shown);
+                self.bible_page
+                    .emit(StudyInput::SetInterlinearRows(self.interlinear_rows));
+            }
+            StudyPageInput::SetReading(reading) => {
+                // The toggle stays as the user left it unless the preference itself changed
+                if reading.interlinear != self.reading.interlinear {
+                    self.interlinear = reading.interlinear;
+                    self.bible_page.emit(StudyInput::SetInterlinear(self.interlinear));
+                }
+                self.bible_page.emit(StudyInput::SetReading(reading.clone()));
+                self.reading = reading;
+            }
+            StudyPageInput::SetPairedModule(idx) => {
+                let paired = (idx as usize)
+                    .checked_sub(1)
+                    .and_then(|i| self.pairing_modules.get(i))
+                    .cloned();
+                self.bible_page.emit(StudyInput::SetPairedModule(paired));
+            }
+            StudyPageInput::SetTextOption(option, value) => {
+                match self.engine.set_text_option(&option, &value) {
+                    Ok(()) => {
+                        self.save_text_options();
+                        self.bible_page
+                            .emit(StudyInput::LoadReference(self.build_query_string()));
+                    }
+                    Err(e) => show_error(&self.toasts, &e),
+                }
+            }
+            StudyPageInput::SearchLemma(strong) => {
+                let module_name = self
+                    .available_modules
+                    .get(self.selected_module_idx)
+                    .map(|m| m.name.clone())
+                    .unwrap_or_default();
+                let engine = self.engine.clone();
+                sender.spawn_oneshot_command(move || {
+                    let hits = engine.search_strongs(&module_name, &strong);
+                    StudyPageCommand::LemmaFound(strong, hits)
+                });
+            }
+            StudyPageInput::FollowReference(reference) => {
+                if self.bible_structure.is_empty() {
+                    // The outline is still loading; open it when it arrives
+                    self.restore = Some(reference);
+                } else if !self.go_to(widgets, &sender, &reference) {
+                    let message = format!("“{}” is not in this module", reference);
+                    show_error(&self.toasts, &message);
+                }
+            }
+            StudyPageInput::GoBack => {
+                let current = self.current_chapter().osis_ref(&self.bible_structure);
+                let target = current.and_then(|current| self.history.back(current));
+                self.travel(widgets, &sender, target);
+            }
+            StudyPageInput::GoForward => {
+                let current = self.current_chapter().osis_ref(&self.bible_structure);
+                let target = current.and_then(|current| self.history.forward(current));
+                self.travel(widgets, &sender, target);
+            }
+            StudyPageInput::OpenSearchResult(idx) => {
+                if let Some(key) = self.search_results.get(idx).map(|hit| hit.key.clone()) {
+                    self.go_to(widgets, &sender, &key);
+                }
+            }
+            StudyPageInput::SetSearchVisible(visible) => self.is_search_visible = visible,
+            StudyPageInput::ExportPassage(request) => {
+                let dialog = gtk::FileDialog::builder()
+                    .title("Export Passage")
+                    .initial_name(format!(
+                        "{}.{}",
+                        request.range.replace(':', "_"),
+                        request.format.extension()
+                    ))
+                    .build();
+                let window = root.root().and_downcast::<gtk::Window>();
+                let sender = sender.clone();
+                dialog.save(window.as_ref(), gtk::gio::Cancellable::NONE, move |result| {
+                    if let Ok(file) = result
+                        && let Some(path) = file.path()
+                    {
+                        sender.input(StudyPageInput::ExportTo(request, path));
+                    }
+                });
+            }
+            StudyPageInput::ExportTo(request, path) => {
+                self.bible_page.emit(StudyInput::Export(request, path));
+            }
+            StudyPageInput::BibleSyncNavigate(osis_ref) => {
+                // A message can carry several references; follow the first
+                let first = osis_ref.split(';').next().unwrap_or_default().trim();
+                if !self.go_to(widgets, &sender, first) {
+                    log::info!("BibleSync: not following {}", osis_ref);
+                }
             }
         }
+        self.update_view(widgets, sender);
+    }
+
+    fn update_cmd_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::CommandOutput,
+        sender: ComponentSender<Self>,
+        _root: &Self::Root,
+    ) {
+        match message {
+            StudyPageCommand::StructureLoaded(idx, structure) => {
+                // The user may have moved on to another module meanwhile
+                if idx != self.selected_module_idx {
+                    return;
+                }
+                let Some(module) = self.available_modules.get(idx) else {
+                    return;
+                };
+                let module_name = module.name.clone();
+                self.bible_structure = match structure {
+                    Ok(structure) => structure,
+                    Err(e) => {
+                        show_error(&self.toasts, &e);
+                        Vec::new()
+                    }
+                };
+                let chapter = self
+                    .restore
+                    .take()
+                    .and_then(|reference| ChapterPosition::find(&reference, &self.bible_structure))
+                    .unwrap_or(ChapterPosition {
+                        book: 0,
+                        chapter: 0,
+                    });
+                self.selected_book_idx = chapter.book;
+                self.selected_chapter = chapter.chapter;
+                self.rebuild_books();
+                self.rebuild_chapters(chapter.book);
+                // The lists were replaced; point the dropdowns at the chapter
+                widgets.book_dropdown.set_model(Some(&self.book_list));
+                widgets.chapter_dropdown.set_model(Some(&self.chapter_list));
+                widgets.book_dropdown.set_selected(chapter.book as u32);
+                widgets.chapter_dropdown.set_selected(chapter.chapter as u32);
+                self.text_options = self
+                    .engine
+                    .get_text_options(&module_name)
+                    .unwrap_or_else(|e| {
+                        show_error(&self.toasts, &e);
+                        Vec::new()
+                    });
+                self.rebuild_text_options(&sender);
+                self.export_entry.set_text(&self.build_query_string());
+                self.bible_page
+                    .emit(StudyInput::SetStructure(self.bible_structure.clone()));
+                self.bible_page.emit(StudyInput::SetModule(module_name));
+                self.bible_page
+                    .emit(StudyInput::LoadReference(self.build_query_string()));
+                self.chapter_changed(&sender);
+            }
+            StudyPageCommand::ModuleCreated(name, created) => match created {
+                Ok(modules) => {
+                    self.available_modules = modules;
+                    let mut names: Vec<String> = self
+                        .available_modules
+                        .iter()
+                        .map(|m| m.name.clone())
+                        .collect();
+                    names.extend(self.documents.iter().map(|d| d.name()));
+                    let names: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+                    self.module_list
+                        .splice(0, self.module_list.n_items(), &names);
+
+                    if let Some(idx) = self.available_modules.iter().position(|m| m.name == name) {
+                        widgets.module_dropdown.set_selected(idx as u32);
+                    }
+                }
+                Err(e) => show_error(&self.toasts, &e),
+            },
+            StudyPageCommand::DocumentRead(path, document) => match document {
+                Ok(document) => {
+                    self.module_list.append(&document.name());
+                    self.documents.push(document);
+                    widgets
+                        .module_dropdown
+                        .set_selected(self.module_list.n_items().saturating_sub(1));
+                }
+                Err(e) => {
+                    let message = format!("Could not open {}: {}", path.display(), e);
+                    show_error(&self.toasts, &message);
+                }
+            },
+            StudyPageCommand::LemmaFound(strong, hits) => match hits {
+                Ok(hits) => {
+                    self.search_results = hits;
+                    self.search_title =
+                        format!("{} — {} verses", strong, self.search_results.len());
+                    self.rebuild_search_results();
+                    self.is_search_visible = true;
+                }
+                Err(e) => show_error(&self.toasts, &e),
+            },
+        }
+        self.update_view(widgets, sender);
     }
 }
 
 impl StudyPage {
+    /// Reads a module's book outline on a worker thread
+    fn load_structure(
+        engine: &Arc<SwordEngine>,
+        sender: &ComponentSender<Self>,
+        idx: usize,
+        module_name: &str,
+    ) {
+        let engine = engine.clone();
+        let module_name = module_name.to_string();
+        sender.spawn_oneshot_command(move || {
+            StudyPageCommand::StructureLoaded(idx, engine.get_bible_structure(&module_name))
+        });
+    }
+
     fn build_query_string(&self) -> String {
         if let Some(book) = self.bible_structure.get(self.selected_book_idx) {
             // Use the index + 1 for the chapter number to keep it clean,
@@ -208,6 +817,294 @@ impl StudyPage {
         }
     }
 
+    /// Moves the dropdowns to `chapter` without loading it
+    fn select_chapter(&mut self, widgets: &StudyPageWidgets, chapter: ChapterPosition) {
+        if chapter.book != self.selected_book_idx {
+            self.selected_book_idx = chapter.book;
+            self.rebuild_chapters(chapter.book);
+            widgets.chapter_dropdown.set_model(Some(&self.chapter_list));
+            widgets.book_dropdown.set_selected(chapter.book as u32);
+        }
+        self.selected_chapter = chapter.chapter;
+        widgets.chapter_dropdown.set_selected(chapter.chapter as u32);
+        self.export_entry.set_text(&self.build_query_string());
+    }
+
+    fn current_chapter(&self) -> ChapterPosition {
+        ChapterPosition {
+            book: self.selected_book_idx,
+            chapter: self.selected_chapter,
+        }
+    }
+
+    /// Puts the selected chapter in the history before leaving it for
+    /// `destination`
+    fn remember(&mut self, destination: Option<ChapterPosition>) {
+        let current = self.current_chapter();
+        if destination == Some(current) {
+            return;
+        }
+        if let Some(osis_ref) = current.osis_ref(&self.bible_structure) {
+            self.history.visit(osis_ref);
+        }
+    }
+
+    /// Opens a chapter taken from the history
+    fn travel(
+        &mut self,
+        widgets: &StudyPageWidgets,
+        sender: &ComponentSender<Self>,
+        target: Option<String>,
+    ) {
+        let Some(chapter) =
+            target.and_then(|osis_ref| ChapterPosition::find(&osis_ref, &self.bible_structure))
+        else {
+            return;
+        };
+        self.open_chapter(widgets, sender, chapter);
+    }
+
+    /// Opens the chapter holding `reference`, after putting the current one
+    /// in the history; false when the module has no such chapter
+    fn go_to(
+        &mut self,
+        widgets: &StudyPageWidgets,
+        sender: &ComponentSender<Self>,
+        reference: &str,
+    ) -> bool {
+        let Some(chapter) = ChapterPosition::find(reference, &self.bible_structure) else {
+            return false;
+        };
+        self.remember(Some(chapter));
+        self.open_chapter(widgets, sender, chapter);
+        true
+    }
+
+    /// Loads `chapter` with the dropdowns, tab and session following along
+    fn open_chapter(
+        &mut self,
+        widgets: &StudyPageWidgets,
+        sender: &ComponentSender<Self>,
+        chapter: ChapterPosition,
+    ) {
+        self.select_chapter(widgets, chapter);
+        self.bible_page
+            .emit(StudyInput::LoadReference(self.build_query_string()));
+        self.chapter_changed(sender);
+    }
+
+    /// The module, chapter and history, for the tab bar and the next launch
+    fn tab(&self) -> StudyTab {
+        let module = match self.available_modules.get(self.selected_module_idx) {
+            Some(module) => module.name.clone(),
+            None => self.selected_document().map(|d| d.name()).unwrap_or_default(),
+        };
+        StudyTab {
+            module,
+            reference: self
+                .current_chapter()
+                .osis_ref(&self.bible_structure)
+                .unwrap_or_default(),
+            history: self.history.clone(),
+        }
+    }
+
+    /// Reports the tab to the tab view, which keeps it and shares it over BibleSync
+    fn chapter_changed(&self, sender: &ComponentSender<Self>) {
+        let _ = sender.output(StudyPageOutPut::TabChanged(self.tab()));
+    }
+
+    fn selected_document(&self) -> Option<&Arc<dyn DocumentBible>> {
+        self.selected_module_idx
+            .checked_sub(self.available_modules.len())
+            .and_then(|i| self.documents.get(i))
+    }
+
+    fn current_module_has_strongs(&self) -> bool {
+        match self.available_modules.get(self.selected_module_idx) {
+            Some(module) => module.features.iter().any(|f| f == "StrongsNumbers"),
+            // Documents carry no feature list; let the reader decide per word
+            None => !self.documents.is_empty(),
+        }
+    }
+
+    /// Row checkboxes plus the module to pair with for a reverse interlinear.
+    fn build_interlinear_menu(&self, sender: &ComponentSender<Self>) -> gtk::MenuButton {
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(6)
+            .margin_top(8)
+            .margin_bottom(8)
+            .margin_start(8)
+            .margin_end(8)
+            .build();
+
+        for row in InterlinearRow::ALL {
+            let check = gtk::CheckButton::builder()
+                .label(row.label())
+                .active(self.interlinear_rows.is_shown(row))
+                .build();
+            let sender = sender.clone();
+            check.connect_toggled(move |check| {
+                sender.input(StudyPageInput::SetInterlinearRow(row, check.is_active()));
+            });
+            content.append(&check);
+        }
+
+        let pair_label = gtk::Label::builder()
+            .label("Pair With")
+            .xalign(0.0)
+            .margin_top(6)
+            .css_classes(["heading"])
+            .build();
+        content.append(&pair_label);
+
+        let mut names = vec!["None"];
+        names.extend(self.pairing_modules.iter().map(|s| s.as_str()));
+        let pair_dropdown = gtk::DropDown::from_strings(&names);
+        let sender = sender.clone();
+        pair_dropdown.connect_selected_notify(move |dd| {
+            sender.input(StudyPageInput::SetPairedModule(dd.selected()));
+        });
+        content.append(&pair_dropdown);
+
+        let popover = gtk::Popover::builder().child(&content).build();
+        gtk::MenuButton::builder().popover(&popover).build()
+    }
+
+    /// Range, format and text options for exporting a passage
+    fn build_export_menu(&self, sender: &ComponentSender<Self>) -> gtk::MenuButton {
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(6)
+            .margin_top(8)
+            .margin_bottom(8)
+            .margin_start(8)
+            .margin_end(8)
+            .build();
+
+        self.export_entry.set_placeholder_text(Some("John 3:16-18"));
+        content.append(&self.export_entry);
+
+        let formats: Vec<&str> = ExportFormat::ALL.iter().map(|f| f.label()).collect();
+        let format_dropdown = gtk::DropDown::from_strings(&formats);
+        content.append(&format_dropdown);
+
+        let defaults = ExportOptions::default();
+        let verse_numbers = gtk::CheckButton::builder()
+            .label("Verse Numbers")
+            .active(defaults.verse_numbers)
+            .build();
+        let red_letter = gtk::CheckButton::builder()
+            .label("Red Letter")
+            .active(defaults.red_letter)
+            .build();
+        content.append(&verse_numbers);
+        content.append(&red_letter);
+
+        let export_button = gtk::Button::builder()
+            .label("Export…")
+            .css_classes(["suggested-action"])
+            .build();
+        content.append(&export_button);
+
+        let popover = gtk::Popover::builder().child(&content).build();
+        let entry = self.export_entry.clone();
+        let sender = sender.clone();
+        export_button.connect_clicked({
+            let popover = popover.clone();
+            move |_| {
+                popover.popdown();
+                let format = ExportFormat::ALL
+                    .get(format_dropdown.selected() as usize)
+                    .copied()
+                    .unwrap_or(ExportFormat::PlainText);
+                sender.input(StudyPageInput::ExportPassage(ExportRequest {
+                    range: entry.text().trim().to_string(),
+                    format,
+                    options: ExportOptions {
+                        verse_numbers: verse_numbers.is_active(),
+                        red_letter: red_letter.is_active(),
+                    },
+                }));
+            }
+        });
+
+        gtk::MenuButton::builder().popover(&popover).build()
+    }
+
+    /// A switch per on/off option and a dropdown for the rest
+    fn rebuild_text_options(&self, sender: &ComponentSender<Self>) {
+        while let Some(child) = self.text_options_box.first_child() {
+            self.text_options_box.remove(&child);
+        }
+
+        for option in &self.text_options {
+            let name = option.name.clone();
+            let sender = sender.clone();
+            if option.values == ["Off", "On"] {
+                let check = gtk::CheckButton::builder()
+                    .label(&option.name)
+                    .active(option.value == "On")
+                    .build();
+                check.set_tooltip_text(option.tip.as_deref());
+                check.connect_toggled(move |check| {
+                    let value = if check.is_active() { "On" } else { "Off" };
+                    sender.input(StudyPageInput::SetTextOption(name.clone(), value.into()));
+                });
+                self.text_options_box.append(&check);
+                continue;
+            }
+
+            let label = gtk::Label::builder()
+                .label(&option.name)
+                .xalign(0.0)
+                .css_classes(["heading"])
+                .build();
+            label.set_tooltip_text(option.tip.as_deref());
+            let values: Vec<&str> = option.values.iter().map(|v| v.as_str()).collect();
+            let dropdown = gtk::DropDown::from_strings(&values);
+            if let Some(idx) = option.values.iter().position(|v| *v == option.value) {
+                dropdown.set_selected(idx as u32);
+            }
+            let values = option.values.clone();
+            dropdown.connect_selected_notify(move |dd| {
+                if let Some(value) = values.get(dd.selected() as usize) {
+                    sender.input(StudyPageInput::SetTextOption(name.clone(), value.clone()));
+                }
+            });
+            self.text_options_box.append(&label);
+            self.text_options_box.append(&dropdown);
+        }
+    }
+
+    /// Keeps the picked text options for the next launch
+    fn save_text_options(&self) {
+        let dir = &self.engine.sword_path;
+        let mut settings = Settings::load(dir);
+        match self.engine.text_options() {
+            Ok(options) => settings.text_options = options,
+            Err(e) => return show_error(&self.toasts, &e),
+        }
+        if let Err(e) = settings.save(dir) {
+            let message = format!("Could not save {}: {}", Settings::path(dir).display(), e);
+            show_error(&self.toasts, &message);
+        }
+    }
+
+    fn rebuild_search_results(&self) {
+        self.results_box.remove_all();
+        for hit in &self.search_results {
+            let label = gtk::Label::builder()
+                .label(&hit.key)
+                .xalign(0.0)
+                .margin_top(6)
+                .margin_bottom(6)
+                .build();
+            self.results_box.append(&label);
+        }
+    }
+
     fn rebuild_books(&mut self) {
         let book_names: Vec<String> = self
             .bible_structure
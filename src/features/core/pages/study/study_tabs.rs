@@ -0,0 +1,408 @@
+use adw::prelude::*;
+use relm4::prelude::*;
+use std::sync::Arc;
+
+use crate::features::core::{
+    module_engine::{
+        bible_sync::{BibleSyncMode, BibleSyncSession},
+        sword::BIBLE_SYNC_AVAILABLE,
+        sword_engine::SwordEngine,
+    },
+    pages::study::study_page::{StudyPage, StudyPageInput, StudyPageOutPut},
+    settings::{
+        ReadingSettings, Settings,
+        session::{StudySession, StudyTab},
+    },
+};
+
+/// Closed tabs kept for reopening
+const CLOSED_LIMIT: usize = 10;
+
+struct OpenTab {
+    /// Tags the tab's output, as pages move around the view
+    id: usize,
+    page: adw::TabPage,
+    study: Controller<StudyPage>,
+    state: StudyTab,
+}
+
+/// Study sessions side by side, each with its own module, chapter and history
+pub struct StudyTabs {
+    engine: Arc<SwordEngine>,
+    reading: ReadingSettings,
+    /// Handed to new tabs
+    is_sidebar_visible: bool,
+    tab_view: adw::TabView,
+    tabs: Vec<OpenTab>,
+    next_id: usize,
+    /// Most recently closed last
+    closed: Vec<StudyTab>,
+    /// Shared navigation with other BibleSync apps, following the tab in front
+    bible_sync: Option<BibleSyncSession>,
+}
+
+#[derive(Debug)]
+pub enum StudyTabsInput {
+    NewTab,
+    ReopenClosed,
+    CloseSelected,
+    /// The view is closing this page
+    TabClosed(adw::TabPage),
+    TabChanged(usize, StudyTab),
+    /// Another tab came to the front or the order changed
+    Rearranged,
+    SetReading(ReadingSettings),
+    /// Join BibleSync in a mode with a passphrase, or leave with `None`
+    SetBibleSync(Option<BibleSyncMode>, String),
+    /// Another BibleSync app navigated to this OSIS reference
+    BibleSyncNavigate(String),
+    /// Show a reference in the tab in front, as a reading plan does
+    OpenReference(String),
+    ToggleSidebar,
+}
+
+#[derive(Debug)]
+pub enum StudyTabsOutput {
+    ToggleSidebar,
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for StudyTabs {
+    type Init = (Arc<SwordEngine>, bool, ReadingSettings);
+    type Input = StudyTabsInput;
+    type Output = StudyTabsOutput;
+
+    view! {
+        adw::NavigationPage {
+            set_title: "Bible Study",
+
+            #[wrap(Some)]
+            set_child = &adw::ToolbarView {
+                add_top_bar = &adw::TabBar {
+                    set_view: Some(&model.tab_view),
+
+                    #[wrap(Some)]
+                    set_start_action_widget = &gtk::Box {
+                        #[local_ref]
+                        bible_sync_menu -> gtk::MenuButton {
+                            add_css_class: "flat",
+                            set_icon_name: "network-workgroup-symbolic",
+                            set_sensitive: BIBLE_SYNC_AVAILABLE,
+                            set_tooltip_text: Some(if BIBLE_SYNC_AVAILABLE {
+                                "BibleSync"
+                            } else {
+                                "xBible was built without BibleSync"
+                            }),
+                        },
+                    },
+
+                    #[wrap(Some)]
+                    set_end_action_widget = &gtk::Box {
+                        gtk::Button {
+                            add_css_class: "flat",
+                            set_icon_name: "edit-undo-symbolic",
+                            set_tooltip_text: Some("Reopen Closed Tab"),
+                            #[watch]
+                            set_sensitive: !model.closed.is_empty(),
+                            connect_clicked => StudyTabsInput::ReopenClosed,
+                        },
+
+                        gtk::Button {
+                            add_css_class: "flat",
+                            set_icon_name: "tab-new-symbolic",
+                            set_tooltip_text: Some("New Tab"),
+                            connect_clicked => StudyTabsInput::NewTab,
+                        },
+                    },
+                },
+
+                set_content: Some(&model.tab_view),
+            },
+        }
+    }
+
+    fn init(
+        init: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let (engine, is_sidebar_visible, reading) = init;
+        let session = Settings::load(&engine.sword_path).session;
+
+        let mut model = StudyTabs {
+            engine,
+            reading,
+            is_sidebar_visible,
+            tab_view: adw::TabView::new(),
+            tabs: Vec::new(),
+            next_id: 0,
+            closed: Vec::new(),
+            bible_sync: None,
+        };
+
+        let mut tabs = session.tabs;
+        if tabs.is_empty() {
+            tabs.push(StudyTab::default());
+        }
+        for tab in tabs {
+            model.open_tab(tab, &sender);
+        }
+        if let Some(tab) = model.tabs.get(session.selected) {
+            model.tab_view.set_selected_page(&tab.page);
+        }
+
+        model.tab_view.connect_close_page({
+            let sender = sender.clone();
+            move |_, page| {
+                sender.input(StudyTabsInput::TabClosed(page.clone()));
+                gtk::glib::Propagation::Proceed
+            }
+        });
+        model.tab_view.connect_selected_page_notify({
+            let sender = sender.clone();
+            move |_| sender.input(StudyTabsInput::Rearranged)
+        });
+        model.tab_view.connect_page_reordered({
+            let sender = sender.clone();
+            move |_, _, _| sender.input(StudyTabsInput::Rearranged)
+        });
+
+        let shortcuts = gtk::ShortcutController::new();
+        let inputs: [(&str, fn() -> StudyTabsInput); 3] = [
+            ("<Control>t", || StudyTabsInput::NewTab),
+            ("<Control><Shift>t", || StudyTabsInput::ReopenClosed),
+            ("<Control>w", || StudyTabsInput::CloseSelected),
+        ];
+        for (trigger, input) in inputs {
+            let sender = sender.clone();
+            shortcuts.add_shortcut(gtk::Shortcut::new(
+                gtk::ShortcutTrigger::parse_string(trigger),
+                Some(gtk::CallbackAction::new(move |_, _| {
+                    sender.input(input());
+                    gtk::glib::Propagation::Stop
+                })),
+            ));
+        }
+        root.add_controller(shortcuts);
+
+        let bible_sync_menu = &Self::build_bible_sync_menu(&sender);
+        let widgets = view_output!();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, message: Self::Input, sender: ComponentSender<Self>) {
+        match message {
+            StudyTabsInput::NewTab => {
+                let tab = self.open_tab(StudyTab::default(), &sender);
+                self.tab_view.set_selected_page(&tab);
+            }
+            StudyTabsInput::ReopenClosed => {
+                if let Some(state) = self.closed.pop() {
+                    let tab = self.open_tab(state, &sender);
+                    self.tab_view.set_selected_page(&tab);
+                }
+            }
+            StudyTabsInput::CloseSelected => {
+                if let Some(page) = self.tab_view.selected_page() {
+                    self.tab_view.close_page(&page);
+                }
+            }
+            StudyTabsInput::TabClosed(page) => {
+                if let Some(idx) = self.tabs.iter().position(|tab| tab.page == page) {
+                    let tab = self.tabs.remove(idx);
+                    self.closed.push(tab.state);
+                    if self.closed.len() > CLOSED_LIMIT {
+                        self.closed.remove(0);
+                    }
+                }
+                // Closing the last tab leaves a fresh one
+                if self.tabs.is_empty() {
+                    sender.input(StudyTabsInput::NewTab);
+                }
+                self.save_session();
+            }
+            StudyTabsInput::TabChanged(id, state) => {
+                let Some(tab) = self.tabs.iter_mut().find(|tab| tab.id == id) else {
+                    return;
+                };
+                tab.page.set_title(&state.title());
+                tab.state = state;
+                if self.tab_view.selected_page().as_ref() == Some(&tab.page) {
+                    self.announce_selected();
+                }
+                self.save_session();
+            }
+            StudyTabsInput::Rearranged => {
+                self.announce_selected();
+                self.save_session();
+            }
+            StudyTabsInput::SetReading(reading) => {
+                for tab in &self.tabs {
+                    tab.study.emit(StudyPageInput::SetReading(reading.clone()));
+                }
+                self.reading = reading;
+            }
+            StudyTabsInput::SetBibleSync(mode, passphrase) => {
+                // There is a single session; leave before joining again
+                self.bible_sync = None;
+                if let Some(mode) = mode {
+                    let sender = sender.clone();
+                    let session = BibleSyncSession::start(mode, &passphrase, move |osis_ref| {
+                        sender.input(StudyTabsInput::BibleSyncNavigate(osis_ref));
+                    });
+                    match session {
+                        Ok(session) => {
+                            self.bible_sync = Some(session);
+                            self.announce_selected();
+                        }
+                        Err(e) => log::warn!("BibleSync could not start: {}", e),
+                    }
+                }
+            }
+            StudyTabsInput::BibleSyncNavigate(osis_ref) => {
+                let Some(session) = self.bible_sync.as_mut() else {
+                    return;
+                };
+                session.received(&osis_ref);
+                if let Some(tab) = self.selected_tab() {
+                    tab.study.emit(StudyPageInput::BibleSyncNavigate(osis_ref));
+                }
+            }
+            StudyTabsInput::OpenReference(reference) => match self.selected_tab() {
+                Some(tab) => tab.study.emit(StudyPageInput::FollowReference(reference)),
+                None => {
+                    let state = StudyTab {
+                        reference,
+                        ..StudyTab::default()
+                    };
+                    let tab = self.open_tab(state, &sender);
+                    self.tab_view.set_selected_page(&tab);
+                }
+            },
+            StudyTabsInput::ToggleSidebar => {
+                let _ = sender.output(StudyTabsOutput::ToggleSidebar);
+            }
+        }
+    }
+}
+
+impl StudyTabs {
+    /// Adds a tab after the others, not yet in front
+    fn open_tab(&mut self, state: StudyTab, sender: &ComponentSender<Self>) -> adw::TabPage {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let study = StudyPage::builder()
+            .launch((
+                self.engine.clone(),
+                self.is_sidebar_visible,
+                self.reading.clone(),
+                state.clone(),
+            ))
+            .forward(sender.input_sender(), move |message| match message {
+                StudyPageOutPut::ToggleSidebar => StudyTabsInput::ToggleSidebar,
+                StudyPageOutPut::TabChanged(state) => StudyTabsInput::TabChanged(id, state),
+            });
+        let page = self.tab_view.append(study.widget());
+        page.set_title(&state.title());
+
+        self.tabs.push(OpenTab {
+            id,
+            page: page.clone(),
+            study,
+            state,
+        });
+        page
+    }
+
+    fn selected_tab(&self) -> Option<&OpenTab> {
+        let page = self.tab_view.selected_page()?;
+        self.tabs.iter().find(|tab| tab.page == page)
+    }
+
+    /// Sends the chapter of the tab in front to the BibleSync group, if leading
+    fn announce_selected(&mut self) {
+        let Some(tab) = self.selected_tab() else {
+            return;
+        };
+        if tab.state.reference.is_empty() {
+            return;
+        }
+        let (module, reference) = (tab.state.module.clone(), tab.state.reference.clone());
+        if let Some(session) = self.bible_sync.as_mut() {
+            session.navigate(&module, &reference);
+        }
+    }
+
+    /// The tabs in the order shown, for the next launch
+    fn session(&self) -> StudySession {
+        let pages = (0..self.tab_view.n_pages()).map(|i| self.tab_view.nth_page(i));
+        let tabs: Vec<&OpenTab> = pages
+            .filter_map(|page| self.tabs.iter().find(|tab| tab.page == page))
+            .collect();
+        let selected = self.tab_view.selected_page();
+        StudySession {
+            selected: tabs
+                .iter()
+                .position(|tab| Some(&tab.page) == selected.as_ref())
+                .unwrap_or_default(),
+            tabs: tabs.into_iter().map(|tab| tab.state.clone()).collect(),
+        }
+    }
+
+    fn save_session(&self) {
+        let dir = &self.engine.sword_path;
+        let mut settings = Settings::load(dir);
+        settings.session = self.session();
+        if let Err(e) = settings.save(dir) {
+            log::warn!("Could not save {}: {}", Settings::path(dir).display(), e);
+        }
+    }
+
+    /// Mode and passphrase for joining a BibleSync group
+    fn build_bible_sync_menu(sender: &ComponentSender<Self>) -> gtk::MenuButton {
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(6)
+            .margin_top(8)
+            .margin_bottom(8)
+            .margin_start(8)
+            .margin_end(8)
+            .build();
+
+        let mut modes = vec!["Off"];
+        modes.extend(BibleSyncMode::ALL.iter().map(|m| m.label()));
+        let mode_dropdown = gtk::DropDown::from_strings(&modes);
+        content.append(&mode_dropdown);
+
+        // Apps only hear each other when their passphrases match
+        let passphrase = gtk::Entry::builder()
+            .text("BibleSync")
+            .placeholder_text("Passphrase")
+            .build();
+        content.append(&passphrase);
+
+        let join = {
+            let sender = sender.clone();
+            let mode_dropdown = mode_dropdown.clone();
+            let passphrase = passphrase.clone();
+            move || {
+                let mode = (mode_dropdown.selected() as usize)
+                    .checked_sub(1)
+                    .and_then(|i| BibleSyncMode::ALL.get(i))
+                    .copied();
+                let text = passphrase.text().to_string();
+                sender.input(StudyTabsInput::SetBibleSync(mode, text));
+            }
+        };
+        mode_dropdown.connect_selected_notify({
+            let join = join.clone();
+            move |_| join()
+        });
+        passphrase.connect_activate(move |_| join());
+
+        let popover = gtk::Popover::builder().child(&content).build();
+        gtk::MenuButton::builder().popover(&popover).build()
+    }
+}
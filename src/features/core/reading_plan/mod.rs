@@ -0,0 +1,316 @@
+//! Reading plans: the chapters to read each day, and progress through them.
+//!
+//! Built-in plans are laid out over a module's book outline, weighing each
+//! day by verse count so the days come out about the same length. Plan files
+//! are plain text with one day per line:
+//!
+//! ```text
+//! # Lines starting with '#' are comments
+//! Title: The Gospels in a Month
+//! Matt.1; Matt.2
+//! Matthew 3
+//! ```
+//!
+//! Readings on a line are separated by `;` and are written any way the
+//! Study page understands ("John 3", "John.3", "John 3:16-21").
+
+pub mod progress;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::features::{
+    bible::transilation_engines::usfm_engine::books::resolve_book_name,
+    core::module_engine::sword_module::ModuleBook,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum PlanError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("“{0}” has no readings")]
+    Empty(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadingPlan {
+    pub name: String,
+    /// References to read, day by day
+    pub days: Vec<Vec<String>>,
+}
+
+/// The plans that come with the app
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltInPlan {
+    /// Genesis to Revelation in a year
+    OneYear,
+    /// The whole Bible in a year, books in the order of the events
+    Chronological,
+    NewTestament,
+}
+
+/// Old Testament books by OSIS id, roughly in the order their events took place
+const OLD_TESTAMENT_EVENTS: [&str; 39] = [
+    "Gen", "Job", "Exod", "Lev", "Num", "Deut", "Josh", "Judg", "Ruth", "1Sam", "2Sam", "1Chr",
+    "Ps", "1Kgs", "Prov", "Eccl", "Song", "2Kgs", "2Chr", "Jonah", "Amos", "Hos", "Isa", "Mic",
+    "Joel", "Obad", "Nah", "Zeph", "Hab", "Jer", "Lam", "Ezek", "Dan", "Ezra", "Hag", "Zech",
+    "Esth", "Neh", "Mal",
+];
+
+/// New Testament books, the letters roughly in the order they were written
+const NEW_TESTAMENT_EVENTS: [&str; 27] = [
+    "Matt", "Mark", "Luke", "John", "Acts", "Jas", "Gal", "1Thess", "2Thess", "1Cor", "2Cor",
+    "Rom", "Eph", "Phil", "Col", "Phlm", "1Tim", "Titus", "1Pet", "2Tim", "2Pet", "Heb", "Jude",
+    "1John", "2John", "3John", "Rev",
+];
+
+impl BuiltInPlan {
+    pub const ALL: [Self; 3] = [Self::OneYear, Self::Chronological, Self::NewTestament];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::OneYear => "Bible in a Year",
+            Self::Chronological => "Chronological Bible in a Year",
+            Self::NewTestament => "New Testament in 90 Days",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::OneYear => "Every book in canonical order",
+            Self::Chronological => "Every book in the order of events",
+            Self::NewTestament => "Matthew to Revelation",
+        }
+    }
+
+    fn length(self) -> usize {
+        match self {
+            Self::OneYear | Self::Chronological => 365,
+            Self::NewTestament => 90,
+        }
+    }
+
+    /// Lays the plan out over the chapters of `structure`. Books the plan
+    /// doesn't order, such as the deuterocanon, are left out of the
+    /// chronological plan.
+    pub fn build(self, structure: &[ModuleBook]) -> ReadingPlan {
+        let books: Vec<(String, &ModuleBook)> = match self {
+            Self::OneYear => structure.iter().map(|book| (osis_id(book), book)).collect(),
+            Self::Chronological => OLD_TESTAMENT_EVENTS
+                .iter()
+                .chain(&NEW_TESTAMENT_EVENTS)
+                .filter_map(|id| {
+                    let book = structure.iter().find(|book| osis_id(book) == *id)?;
+                    Some((id.to_string(), book))
+                })
+                .collect(),
+            Self::NewTestament => structure
+                .iter()
+                .map(|book| (osis_id(book), book))
+                .filter(|(id, _)| NEW_TESTAMENT_EVENTS.contains(&id.as_str()))
+                .collect(),
+        };
+        let chapters: Vec<(String, usize)> = books
+            .iter()
+            .flat_map(|(id, book)| {
+                book.chapters.iter().map(move |chapter| {
+                    let reference = format!("{}.{}", id, chapter.number);
+                    (reference, chapter.verse_count.max(1) as usize)
+                })
+            })
+            .collect();
+
+        ReadingPlan {
+            name: self.name().to_string(),
+            days: spread(&chapters, self.length()),
+        }
+    }
+}
+
+/// Splits weighted chapters over `days`, each day getting at least one and
+/// about an equal share of the verses
+fn spread(chapters: &[(String, usize)], days: usize) -> Vec<Vec<String>> {
+    let days = days.min(chapters.len());
+    let total: usize = chapters.iter().map(|(_, verses)| verses).sum();
+
+    let mut plan = Vec::with_capacity(days);
+    let mut next = 0;
+    let mut read = 0;
+    for day in 0..days {
+        let target = total * (day + 1) / days;
+        let later_days = days - day - 1;
+        let mut readings = Vec::new();
+        while let Some((reference, verses)) = chapters.get(next) {
+            // A chapter belongs to the day its middle falls on
+            let fits = readings.is_empty() || read + verses / 2 <= target;
+            if !fits || chapters.len() - next <= later_days {
+                break;
+            }
+            readings.push(reference.clone());
+            read += verses;
+            next += 1;
+        }
+        plan.push(readings);
+    }
+    plan
+}
+
+fn osis_id(book: &ModuleBook) -> String {
+    resolve_book_name(&book.name)
+        .map(|(osis, _)| osis.to_string())
+        .unwrap_or_else(|| book.name.replace(' ', ""))
+}
+
+impl ReadingPlan {
+    /// Reads a plan file, named after the file unless it has a title
+    pub fn open(path: &Path) -> Result<Self, PlanError> {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self::parse(&fs::read_to_string(path)?, &name)
+    }
+
+    pub fn parse(text: &str, name: &str) -> Result<Self, PlanError> {
+        let mut plan = ReadingPlan {
+            name: name.to_string(),
+            days: Vec::new(),
+        };
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, title)) = line.split_once(':')
+                && key.trim().eq_ignore_ascii_case("title")
+            {
+                plan.name = title.trim().to_string();
+                continue;
+            }
+            let readings: Vec<String> = line
+                .split(';')
+                .map(str::trim)
+                .filter(|reading| !reading.is_empty())
+                .map(str::to_string)
+                .collect();
+            if !readings.is_empty() {
+                plan.days.push(readings);
+            }
+        }
+
+        if plan.days.is_empty() {
+            return Err(PlanError::Empty(plan.name));
+        }
+        Ok(plan)
+    }
+}
+
+/// "Gen 1–3; Matt 1", running chapters of a book together
+pub fn summary(readings: &[String]) -> String {
+    let mut parts: Vec<(String, Option<(u32, u32)>)> = Vec::new();
+    for reading in readings {
+        let chapter = reading
+            .rsplit_once(['.', ' '])
+            .and_then(|(book, chapter)| Some((book.trim(), chapter.parse::<u32>().ok()?)));
+        match (chapter, parts.last_mut()) {
+            (Some((book, chapter)), Some((last, Some((_, end)))))
+                if last == book && *end + 1 == chapter =>
+            {
+                *end = chapter;
+            }
+            (Some((book, chapter)), _) => parts.push((book.to_string(), Some((chapter, chapter)))),
+            (None, _) => parts.push((reading.clone(), None)),
+        }
+    }
+
+    parts
+        .into_iter()
+        .map(|(book, chapters)| match chapters {
+            Some((start, end)) if start == end => format!("{} {}", book, start),
+            Some((start, end)) => format!("{} {}–{}", book, start, end),
+            None => book,
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::core::module_engine::sword_module::ModuleChapter;
+
+    fn outline(books: &[(&str, i32)]) -> Vec<ModuleBook> {
+        books
+            .iter()
+            .map(|(name, chapters)| ModuleBook {
+                name: name.to_string(),
+                chapters: (1..=*chapters)
+                    .map(|number| ModuleChapter {
+                        number,
+                        verse_count: 20,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn spreads_every_chapter_over_the_days() {
+        let structure = outline(&[("Genesis", 50), ("Psalms", 150), ("Matthew", 28)]);
+        let plan = BuiltInPlan::NewTestament.build(&structure);
+        assert_eq!(plan.days.len(), 28);
+        assert_eq!(plan.days[0], ["Matt.1"]);
+
+        let plan = BuiltInPlan::OneYear.build(&structure);
+        assert_eq!(plan.days.len(), 228);
+        assert_eq!(plan.days.iter().flatten().count(), 228);
+
+        let chapters: Vec<(String, usize)> = (1..=10).map(|n| (format!("Ps.{}", n), 20)).collect();
+        let days = spread(&chapters, 3);
+        assert!(days.iter().all(|day| !day.is_empty()));
+        assert_eq!(days.concat().len(), 10);
+        assert_eq!(days[0], ["Ps.1", "Ps.2", "Ps.3"]);
+    }
+
+    #[test]
+    fn orders_books_by_events() {
+        let mut sorted = [OLD_TESTAMENT_EVENTS.as_slice(), &NEW_TESTAMENT_EVENTS].concat();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 66);
+
+        let structure = outline(&[("Genesis", 2), ("Exodus", 2), ("Job", 2), ("Matthew", 2)]);
+        let plan = BuiltInPlan::Chronological.build(&structure);
+        let order: Vec<String> = plan.days.concat();
+        assert_eq!(order[..4], ["Gen.1", "Gen.2", "Job.1", "Job.2"]);
+    }
+
+    #[test]
+    fn reads_plan_files() {
+        let text = "# Four days\nTitle: Gospel Week\nJohn 1; John 2\n\n;John.3;\nJohn 4:1-26\n";
+        let plan = ReadingPlan::parse(text, "gospel").unwrap();
+        assert_eq!(plan.name, "Gospel Week");
+        assert_eq!(
+            plan.days,
+            [
+                vec!["John 1", "John 2"],
+                vec!["John.3"],
+                vec!["John 4:1-26"]
+            ]
+        );
+
+        assert!(matches!(
+            ReadingPlan::parse("# nothing yet\n", "empty"),
+            Err(PlanError::Empty(name)) if name == "empty"
+        ));
+    }
+
+    #[test]
+    fn summarizes_runs_of_chapters() {
+        let readings =
+            ["Gen.1", "Gen.2", "Gen.3", "Matt.1", "Matt.3", "John 3:16"].map(String::from);
+        assert_eq!(summary(&readings), "Gen 1–3; Matt 1; Matt 3; John 3:16");
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::BTreeSet;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::features::core::reading_plan::ReadingPlan;
+
+/// The plan being followed and the days read so far
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanProgress {
+    /// Kept whole, so a plan doesn't change under the reader when the
+    /// module it was laid out from does
+    pub plan: ReadingPlan,
+    /// When the first day is due
+    #[serde(with = "iso_date")]
+    pub start: NaiveDate,
+    /// Indices of the days read
+    #[serde(default)]
+    completed: BTreeSet<usize>,
+}
+
+impl PlanProgress {
+    pub fn new(plan: ReadingPlan, start: NaiveDate) -> Self {
+        Self {
+            plan,
+            start,
+            completed: BTreeSet::new(),
+        }
+    }
+
+    /// Index of the day due on `date`, if the plan is running then
+    pub fn day_on(&self, date: NaiveDate) -> Option<usize> {
+        let day = usize::try_from((date - self.start).num_days()).ok()?;
+        (day < self.plan.days.len()).then_some(day)
+    }
+
+    /// Days due before `date` that haven't been read, oldest first
+    pub fn overdue(&self, date: NaiveDate) -> Vec<usize> {
+        let due = usize::try_from((date - self.start).num_days()).unwrap_or_default();
+        (0..due.min(self.plan.days.len()))
+            .filter(|day| !self.is_read(*day))
+            .collect()
+    }
+
+    pub fn is_read(&self, day: usize) -> bool {
+        self.completed.contains(&day)
+    }
+
+    pub fn mark(&mut self, day: usize, read: bool) {
+        if read {
+            self.completed.insert(day);
+        } else {
+            self.completed.remove(&day);
+        }
+    }
+
+    /// The earliest day not read yet
+    pub fn next_unread(&self) -> Option<usize> {
+        (0..self.plan.days.len()).find(|day| !self.is_read(*day))
+    }
+
+    pub fn read_count(&self) -> usize {
+        self.completed.len()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_unread().is_none()
+    }
+
+    /// Moves the schedule later so the earliest unread day is due on
+    /// `date`, instead of reading the missed days all at once
+    pub fn catch_up(&mut self, date: NaiveDate) {
+        if let Some(day) = self.next_unread() {
+            self.start = date - chrono::Days::new(day as u64);
+        }
+    }
+}
+
+/// Dates as "2025-01-31"; chrono's own serde support isn't enabled
+mod iso_date {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    const FORMAT: &str = "%Y-%m-%d";
+
+    pub fn serialize<S: Serializer>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&date.format(FORMAT))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        NaiveDate::parse_from_str(&text, FORMAT).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, day).unwrap()
+    }
+
+    fn progress() -> PlanProgress {
+        let plan = ReadingPlan {
+            name: "Gospel Week".to_string(),
+            days: (1..=7).map(|n| vec![format!("John.{}", n)]).collect(),
+        };
+        PlanProgress::new(plan, date(1))
+    }
+
+    #[test]
+    fn finds_the_day_and_what_was_missed() {
+        let mut progress = progress();
+        assert_eq!(progress.day_on(date(1)), Some(0));
+        assert_eq!(progress.day_on(date(4)), Some(3));
+        assert_eq!(progress.day_on(date(8)), None);
+        assert_eq!(
+            progress.day_on(NaiveDate::from_ymd_opt(2025, 2, 28).unwrap()),
+            None
+        );
+
+        progress.mark(0, true);
+        progress.mark(2, true);
+        assert_eq!(progress.overdue(date(4)), [1]);
+        assert_eq!(progress.overdue(date(20)), [1, 3, 4, 5, 6]);
+
+        progress.mark(2, false);
+        assert_eq!(progress.next_unread(), Some(1));
+        assert!(!progress.is_finished());
+    }
+
+    #[test]
+    fn catches_up_from_the_first_missed_day() {
+        let mut progress = progress();
+        progress.mark(0, true);
+        progress.mark(3, true);
+
+        progress.catch_up(date(6));
+        assert_eq!(progress.start, date(5));
+        assert_eq!(progress.day_on(date(6)), Some(1));
+        assert!(progress.overdue(date(6)).is_empty());
+        assert!(progress.is_read(3));
+    }
+
+    #[test]
+    fn keeps_the_start_as_a_date() {
+        let mut progress = progress();
+        progress.mark(1, true);
+        let json = serde_json::to_string(&progress).unwrap();
+        assert!(json.contains("\"start\":\"2025-03-01\""));
+        assert_eq!(
+            serde_json::from_str::<PlanProgress>(&json).unwrap(),
+            progress
+        );
+    }
+}
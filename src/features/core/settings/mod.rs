@@ -0,0 +1,263 @@
+//! Preferences kept across launches.
+//!
+//! Stored as `settings.json` in the data directory. A missing or unreadable
+//! file yields the defaults; fields added later default when absent, so old
+//! files keep loading.
+
+pub mod session;
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use log::LevelFilter;
+use serde::{Deserialize, Serialize};
+
+use crate::features::core::{
+    module_engine::text_options::TextOptions,
+    reading_plan::{ReadingPlan, progress::PlanProgress},
+    settings::session::StudySession,
+};
+
+pub const FILE_NAME: &str = "settings.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// SWORD global option values, by option name
+    pub text_options: TextOptions,
+    pub reading: ReadingSettings,
+    pub session: StudySession,
+    /// Plans read from files, offered next to the built-in ones
+    pub imported_plans: Vec<ReadingPlan>,
+    /// The plan being followed, if any
+    pub reading_plan: Option<PlanProgress>,
+    /// Detail of the log; `XBIBLE_LOG` overrides it
+    pub log_level: LogLevel,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub const ALL: [Self; 6] = [
+        Self::Off,
+        Self::Error,
+        Self::Warn,
+        Self::Info,
+        Self::Debug,
+        Self::Trace,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Error => "Errors",
+            Self::Warn => "Warnings",
+            Self::Info => "Information",
+            Self::Debug => "Debugging",
+            Self::Trace => "Everything",
+        }
+    }
+
+    pub fn filter(self) -> LevelFilter {
+        match self {
+            Self::Off => LevelFilter::Off,
+            Self::Error => LevelFilter::Error,
+            Self::Warn => LevelFilter::Warn,
+            Self::Info => LevelFilter::Info,
+            Self::Debug => LevelFilter::Debug,
+            Self::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+/// How words the translators supplied are set apart
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AddedWordStyle {
+    #[default]
+    Italic,
+    Brackets,
+}
+
+impl AddedWordStyle {
+    pub const ALL: [Self; 2] = [Self::Italic, Self::Brackets];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Italic => "Italic",
+            Self::Brackets => "Brackets",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerseNumberStyle {
+    /// As large as the text
+    #[default]
+    Normal,
+    /// Smaller, at the top of the line
+    Superscript,
+    Hidden,
+}
+
+impl VerseNumberStyle {
+    pub const ALL: [Self; 3] = [Self::Normal, Self::Superscript, Self::Hidden];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Normal => "Normal",
+            Self::Superscript => "Superscript",
+            Self::Hidden => "Hidden",
+        }
+    }
+}
+
+/// Appearance of the Bible text in the reader
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReadingSettings {
+    /// Font family of the verse text; `None` keeps the theme's font
+    pub font_family: Option<String>,
+    /// In points
+    pub font_size: f64,
+    /// Height of a line as a multiple of the font size
+    pub line_spacing: f64,
+    pub added_style: AddedWordStyle,
+    /// Draw the words of Christ in red
+    pub red_letter: bool,
+    pub verse_numbers: VerseNumberStyle,
+    /// Open modules with Strong's numbers as an interlinear
+    pub interlinear: bool,
+}
+
+impl Default for ReadingSettings {
+    fn default() -> Self {
+        Self {
+            font_family: None,
+            font_size: 14.0,
+            line_spacing: 1.4,
+            added_style: AddedWordStyle::Italic,
+            red_letter: true,
+            verse_numbers: VerseNumberStyle::Normal,
+            interlinear: false,
+        }
+    }
+}
+
+impl ReadingSettings {
+    /// Superscript verse numbers are drawn at this share of the text size
+    const SUPERSCRIPT_SCALE: f64 = 0.7;
+
+    /// Stylesheet sizing the verse text and numbers, loaded over the
+    /// application's own
+    pub fn css(&self) -> String {
+        let mut text = format!(
+            "font-size: {:.1}pt; line-height: {:.2};",
+            self.font_size, self.line_spacing
+        );
+        if let Some(family) = &self.font_family {
+            let family = family.replace('\\', "\\\\").replace('"', "\\\"");
+            text.push_str(&format!(" font-family: \"{}\";", family));
+        }
+        let number_size = match self.verse_numbers {
+            VerseNumberStyle::Superscript => self.font_size * Self::SUPERSCRIPT_SCALE,
+            VerseNumberStyle::Normal | VerseNumberStyle::Hidden => self.font_size,
+        };
+        format!(
+            ".bible-text {{ {} }}\n.verser-number {{ font-size: {:.1}pt; }}\n",
+            text, number_size
+        )
+    }
+}
+
+impl Settings {
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join(FILE_NAME)
+    }
+
+    /// Reads the settings under `dir`, falling back to the defaults
+    pub fn load(dir: &Path) -> Self {
+        let path = Self::path(dir);
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                log::warn!("Could not read {}: {}", path.display(), e);
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&text).unwrap_or_else(|e| {
+            log::warn!("Ignoring {}: {}", path.display(), e);
+            Self::default()
+        })
+    }
+
+    /// Writes the settings under `dir`, replacing the file in one step so a
+    /// crash never leaves half of it
+    pub fn save(&self, dir: &Path) -> io::Result<()> {
+        let path = Self::path(dir);
+        let staged = path.with_extension("json.tmp");
+        fs::write(&staged, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&staged, &path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_tolerates_bad_files() {
+        let dir = std::env::temp_dir().join(format!("xbible-settings-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(Settings::load(&dir), Settings::default());
+
+        let mut settings = Settings::default();
+        settings.text_options.set("Greek Accents", "Off");
+        settings.save(&dir).unwrap();
+        assert_eq!(Settings::load(&dir), settings);
+
+        fs::write(Settings::path(&dir), "{ not json").unwrap();
+        assert_eq!(Settings::load(&dir), Settings::default());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn writes_the_reading_stylesheet() {
+        let mut reading = ReadingSettings::default();
+        assert_eq!(
+            reading.css(),
+            ".bible-text { font-size: 14.0pt; line-height: 1.40; }\n\
+             .verser-number { font-size: 14.0pt; }\n"
+        );
+
+        reading.font_family = Some("Gentium \"Plus\"".to_string());
+        reading.verse_numbers = VerseNumberStyle::Superscript;
+        let css = reading.css();
+        assert!(css.contains(r#"font-family: "Gentium \"Plus\"";"#), "{css}");
+        assert!(
+            css.contains(".verser-number { font-size: 9.8pt; }"),
+            "{css}"
+        );
+    }
+
+    #[test]
+    fn old_files_get_the_default_reading() {
+        let settings: Settings = serde_json::from_str(r#"{"text_options": {}}"#).unwrap();
+        assert_eq!(settings.reading, ReadingSettings::default());
+        assert_eq!(settings.log_level.filter(), LevelFilter::Info);
+
+        let settings: Settings = serde_json::from_str(r#"{"log_level": "debug"}"#).unwrap();
+        assert_eq!(settings.log_level, LogLevel::Debug);
+    }
+}
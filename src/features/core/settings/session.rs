@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+/// Chapters left behind in a study tab, as OSIS references ("John.3")
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct History {
+    back: Vec<String>,
+    forward: Vec<String>,
+}
+
+impl History {
+    /// The oldest chapters are forgotten past this many
+    const LIMIT: usize = 50;
+
+    /// Records leaving `chapter` for another one. Going somewhere new
+    /// forgets the chapters ahead.
+    pub fn visit(&mut self, chapter: String) {
+        self.forward.clear();
+        if self.back.last() != Some(&chapter) {
+            self.back.push(chapter);
+        }
+        if self.back.len() > Self::LIMIT {
+            self.back.remove(0);
+        }
+    }
+
+    /// The chapter visited before `current`, which moves ahead
+    pub fn back(&mut self, current: String) -> Option<String> {
+        let previous = self.back.pop()?;
+        self.forward.push(current);
+        Some(previous)
+    }
+
+    /// The chapter gone back from, with `current` behind again
+    pub fn forward(&mut self, current: String) -> Option<String> {
+        let next = self.forward.pop()?;
+        self.back.push(current);
+        Some(next)
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+}
+
+/// What a study tab shows
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StudyTab {
+    /// Module or document name; unknown names open the first module
+    pub module: String,
+    /// OSIS reference of the chapter ("John.3"); empty for the first one
+    pub reference: String,
+    pub history: History,
+}
+
+impl StudyTab {
+    /// "John 3 · KJV"
+    pub fn title(&self) -> String {
+        match self.reference.split_once('.') {
+            Some((book, chapter)) => format!("{} {} · {}", book, chapter, self.module),
+            None if self.module.is_empty() => "Bible Study".to_string(),
+            None => self.module.clone(),
+        }
+    }
+}
+
+/// The study tabs open when the app last ran
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StudySession {
+    pub tabs: Vec<StudyTab>,
+    /// Index of the tab in front
+    pub selected: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goes_back_and_forward() {
+        let mut history = History::default();
+        assert_eq!(history.back("Gen.1".into()), None);
+
+        history.visit("Gen.1".into());
+        history.visit("John.3".into());
+        history.visit("John.3".into());
+        assert_eq!(history.back("Rom.8".into()).as_deref(), Some("John.3"));
+        assert_eq!(history.back("John.3".into()).as_deref(), Some("Gen.1"));
+        assert!(!history.can_go_back());
+        assert_eq!(history.forward("Gen.1".into()).as_deref(), Some("John.3"));
+
+        // A new chapter drops the way forward to Rom.8
+        history.visit("John.3".into());
+        assert!(!history.can_go_forward());
+        assert_eq!(history.back("Ps.23".into()).as_deref(), Some("John.3"));
+    }
+
+    #[test]
+    fn forgets_the_oldest_chapters() {
+        let mut history = History::default();
+        for chapter in 1..=History::LIMIT + 5 {
+            history.visit(format!("Ps.{}", chapter));
+        }
+        let mut oldest = None;
+        while let Some(chapter) = history.back(String::new()) {
+            oldest = Some(chapter);
+        }
+        assert_eq!(oldest.as_deref(), Some("Ps.6"));
+    }
+
+    #[test]
+    fn titles_tabs_by_chapter_and_module() {
+        let tab = StudyTab {
+            module: "KJV".to_string(),
+            reference: "1John.3".to_string(),
+            ..StudyTab::default()
+        };
+        assert_eq!(tab.title(), "1John 3 · KJV");
+        assert_eq!(StudyTab::default().title(), "Bible Study");
+    }
+}
@@ -1,3 +1,4 @@
 pub mod bible;
 pub mod core;
-pub mod store;
\ No newline at end of file
+pub mod server;
+pub mod store;
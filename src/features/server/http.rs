@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+
+use serde::Serialize;
+use url::Url;
+
+/// A request line with its decoded query. Headers are read past and
+/// dropped; every route is a GET without a body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+}
+
+impl Request {
+    /// `None` when the client closed the connection without a request
+    pub fn read(reader: &mut impl BufRead) -> io::Result<Option<Self>> {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let mut header = String::new();
+        loop {
+            header.clear();
+            if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+                break;
+            }
+        }
+
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("malformed request line {:?}", line.trim_end()),
+            ));
+        };
+        Self::parse(method, target).map(Some)
+    }
+
+    pub fn parse(method: &str, target: &str) -> io::Result<Self> {
+        let url = Url::parse("http://localhost")
+            .and_then(|base| base.join(target))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Self {
+            method: method.to_string(),
+            path: url.path().to_string(),
+            query: url.query_pairs().into_owned().collect(),
+        })
+    }
+
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.query
+            .get(name)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn json(value: &impl Serialize) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self { status: 200, body },
+            Err(e) => Self::error(500, e),
+        }
+    }
+
+    /// `{"error": message}` with the given status
+    pub fn error(status: u16, message: impl Display) -> Self {
+        let body = serde_json::json!({ "error": message.to_string() }).to_string();
+        Self { status, body }
+    }
+
+    /// Browsers let pages from `allow_origin` read the response; without
+    /// one, only same-origin and non-browser clients can
+    pub fn write_to(&self, out: &mut impl Write, allow_origin: Option<&str>) -> io::Result<()> {
+        write!(
+            out,
+            "HTTP/1.1 {} {}\r\n\
+             Content-Type: application/json; charset=utf-8\r\n\
+             Content-Length: {}\r\n",
+            self.status,
+            reason(self.status),
+            self.body.len()
+        )?;
+        if let Some(origin) = allow_origin {
+            write!(out, "Access-Control-Allow-Origin: {}\r\n", origin)?;
+        }
+        write!(out, "Connection: close\r\n\r\n")?;
+        out.write_all(self.body.as_bytes())?;
+        out.flush()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_request_line_and_decodes_the_query() {
+        let raw = "GET /passage?module=KJV&ref=John+3%3A16 HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let request = Request::read(&mut raw.as_bytes()).unwrap().unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/passage");
+        assert_eq!(request.param("ref"), Some("John 3:16"));
+        assert_eq!(request.param("scope"), None);
+
+        assert_eq!(Request::read(&mut "".as_bytes()).unwrap(), None);
+        assert!(Request::read(&mut "GARBAGE\r\n\r\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn writes_json_responses() {
+        let mut out = Vec::new();
+        Response::error(404, "no such module")
+            .write_to(&mut out, None)
+            .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(text.contains("Content-Length: 26\r\n"));
+        assert!(!text.contains("Access-Control-Allow-Origin"));
+        assert!(text.ends_with("\r\n\r\n{\"error\":\"no such module\"}"));
+
+        let mut out = Vec::new();
+        Response::json(&["KJV"])
+            .write_to(&mut out, Some("http://localhost:3000"))
+            .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("Access-Control-Allow-Origin: http://localhost:3000\r\n"));
+    }
+}
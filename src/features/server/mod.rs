@@ -0,0 +1,266 @@
+//! Local HTTP/JSON API over the installed modules, for presentation and web
+//! tools that want verse text without linking SWORD themselves.
+//!
+//! - `GET /modules`: every installed module
+//! - `GET /passage?module=KJV&ref=John+3:16`: the `Passage` model, verses
+//!   and words included
+//! - `GET /search?module=KJV&q=loved&type=phrase&scope=Mat-Joh`: matching keys
+//! - `GET /strongs/G3056`: the lexicon entry for a Strong's number
+//!
+//! Errors come back as `{"error": "..."}` with a 4xx or 5xx status.
+
+pub mod http;
+
+use std::io::{self, BufReader, Read};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::features::{
+    bible::{components::page::reader::ChapterReader, export::PassageRange},
+    core::module_engine::{
+        error::EngineError, sword_engine::SwordEngine, sword_module::SearchType,
+    },
+    server::http::{Request, Response},
+};
+
+/// Upper bound on the request line and headers together
+const MAX_REQUEST_LEN: u64 = 16 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, thiserror::Error)]
+enum ApiError {
+    #[error("Missing query parameter “{0}”")]
+    MissingParam(&'static str),
+    #[error("“{0}” is not a verse reference")]
+    NotAReference(String),
+    #[error("Unknown search type “{0}”; use phrase, words or regex")]
+    SearchType(String),
+    #[error("{module} has no verses in {reference}")]
+    NoVerses { module: String, reference: String },
+    #[error("No lexicon entry for {0}")]
+    NoEntry(String),
+    #[error(transparent)]
+    Engine(#[from] EngineError),
+}
+
+impl ApiError {
+    fn status(&self) -> u16 {
+        match self {
+            Self::MissingParam(_) | Self::NotAReference(_) | Self::SearchType(_) => 400,
+            Self::NoVerses { .. }
+            | Self::NoEntry(_)
+            | Self::Engine(EngineError::ModuleNotFound(_) | EngineError::InvalidKey { .. }) => 404,
+            Self::Engine(_) => 500,
+        }
+    }
+}
+
+/// Serves the API from one thread per connection. Bind it to a loopback
+/// address: there is no authentication. Web pages can't read the answers
+/// unless their origin is allowed.
+#[derive(Debug)]
+pub struct Server {
+    listener: TcpListener,
+    engine: Arc<SwordEngine>,
+    allow_origin: Option<Arc<str>>,
+}
+
+impl Server {
+    pub fn bind(addr: impl ToSocketAddrs, engine: Arc<SwordEngine>) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            engine,
+            allow_origin: None,
+        })
+    }
+
+    /// Lets pages from `origin`, e.g. "http://localhost:3000", read the API
+    pub fn allow_origin(mut self, origin: Option<String>) -> Self {
+        self.allow_origin = origin.map(Arc::from);
+        self
+    }
+
+    /// The bound address, with the port the OS picked for port 0
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts connections until the listener fails
+    pub fn run(&self) -> io::Result<()> {
+        log::info!("Serving on http://{}", self.local_addr()?);
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log::warn!("Could not accept a connection: {}", e);
+                    continue;
+                }
+            };
+            let engine = self.engine.clone();
+            let allow_origin = self.allow_origin.clone();
+            thread::spawn(move || {
+                if let Err(e) = handle_connection(stream, &engine, allow_origin.as_deref()) {
+                    log::debug!("Connection dropped: {}", e);
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    engine: &Arc<SwordEngine>,
+    allow_origin: Option<&str>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_REQUEST_LEN));
+    let response = match Request::read(&mut reader) {
+        Ok(Some(request)) => {
+            let response = route(&request, engine);
+            log::info!("{} {} {}", request.method, request.path, response.status);
+            response
+        }
+        Ok(None) => return Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => Response::error(400, e),
+        Err(e) => return Err(e),
+    };
+    response.write_to(&mut stream, allow_origin)
+}
+
+/// Answers one request; public so it can be exercised without a socket
+pub fn route(request: &Request, engine: &Arc<SwordEngine>) -> Response {
+    if request.method != "GET" {
+        return Response::error(405, "Only GET is supported");
+    }
+
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let result = match segments.as_slice() {
+        ["modules"] => engine
+            .get_modules()
+            .map(|modules| Response::json(&modules))
+            .map_err(ApiError::from),
+        ["passage"] => passage(request, engine),
+        ["search"] => search(request, engine),
+        ["strongs", number] => strongs(number, engine),
+        _ => return Response::error(404, format!("No route for {}", request.path)),
+    };
+    result.unwrap_or_else(|e| Response::error(e.status(), e))
+}
+
+fn passage(request: &Request, engine: &Arc<SwordEngine>) -> Result<Response, ApiError> {
+    let module = request
+        .param("module")
+        .ok_or(ApiError::MissingParam("module"))?;
+    let reference = request.param("ref").ok_or(ApiError::MissingParam("ref"))?;
+    let range =
+        PassageRange::parse(reference).ok_or_else(|| ApiError::NotAReference(reference.into()))?;
+
+    let reader = ChapterReader::new(engine.clone());
+    let passage = reader.read_passage(module, None, &range, reference)?;
+    if passage.book.chapters.is_empty() {
+        return Err(ApiError::NoVerses {
+            module: module.to_string(),
+            reference: reference.to_string(),
+        });
+    }
+    Ok(Response::json(&passage))
+}
+
+fn search(request: &Request, engine: &SwordEngine) -> Result<Response, ApiError> {
+    let module = request
+        .param("module")
+        .ok_or(ApiError::MissingParam("module"))?;
+    let query = request.param("q").ok_or(ApiError::MissingParam("q"))?;
+    let search_type = match request.param("type") {
+        Some(name) => {
+            SearchType::from_name(name).ok_or_else(|| ApiError::SearchType(name.into()))?
+        }
+        None => SearchType::MultiWord,
+    };
+
+    let hits = engine.search(module, query, search_type, request.param("scope"))?;
+    Ok(Response::json(&hits))
+}
+
+fn strongs(number: &str, engine: &SwordEngine) -> Result<Response, ApiError> {
+    match engine.lookup_strongs(&number.to_ascii_uppercase())? {
+        Some(entry) => Ok(Response::json(&entry)),
+        None => Err(ApiError::NoEntry(number.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::features::{
+        bible::transilation_engines::usfm_engine::usfm::UsfmParser,
+        core::module_engine::sword_module::ModuleSpec,
+    };
+
+    const FIXTURE: &str = r#"\id JHN Fixture
+\c 1
+\p
+\v 1 In the beginning was the \w Word|strong="G3056"\w*, and the Word was with God.
+\c 3
+\p
+\v 16 For God so loved the world, that he gave his only Son.
+\v 17 For God did not send his Son into the world to condemn the world.
+"#;
+
+    fn get(addr: SocketAddr, target: &str) -> (u16, serde_json::Value) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", target).unwrap();
+        let mut text = String::new();
+        stream.read_to_string(&mut text).unwrap();
+
+        let (head, body) = text.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn serves_a_fixture_module_over_loopback() {
+        let dir = std::env::temp_dir().join(format!("xbible-server-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        // Opening stays offline; remote sources are only fetched when asked for
+        let engine = SwordEngine::open(dir.clone()).unwrap();
+        engine
+            .create_module(
+                &ModuleSpec::new("Fixture", "Server fixture"),
+                &UsfmParser::parse(FIXTURE),
+            )
+            .unwrap();
+
+        let server = Server::bind("127.0.0.1:0", engine).unwrap();
+        let addr = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+
+        let (status, modules) = get(addr, "/modules");
+        assert_eq!(status, 200);
+        assert_eq!(modules[0]["name"], "Fixture");
+
+        let (status, passage) = get(addr, "/passage?module=Fixture&ref=John+3:16-17");
+        assert_eq!(status, 200);
+        let verses = &passage["book"]["chapters"][0]["verses"];
+        assert_eq!(verses.as_array().unwrap().len(), 2);
+        assert_eq!(verses[0]["number"], 16);
+        assert!(verses[0]["words"].to_string().contains("loved"));
+
+        let (status, hits) = get(addr, "/search?module=Fixture&q=loved&type=phrase");
+        assert_eq!(status, 200);
+        assert_eq!(hits[0]["key"], "John 3:16");
+
+        assert_eq!(get(addr, "/passage?module=Fixture").0, 400);
+        assert_eq!(get(addr, "/passage?module=Missing&ref=John+1").0, 404);
+        // The fixture library has no lexicon
+        assert_eq!(get(addr, "/strongs/G3056").0, 404);
+        assert_eq!(get(addr, "/verses").0, 404);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,13 @@
+use crate::features::core::module_engine::error::EngineError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum StoreError {
+    #[error(transparent)]
+    Engine(#[from] EngineError),
+    #[error("{0} lists no modules; check the connection and refresh")]
+    EmptySource(String),
+    #[error("{0} is already installed")]
+    AlreadyInstalled(String),
+}
+
+pub type StoreResult<T> = Result<T, StoreError>;
@@ -1,2 +1,4 @@
+pub mod error;
 pub mod services;
-pub  mod workers;
\ No newline at end of file
+#[cfg(feature = "gui")]
+pub mod workers;
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use crate::features::core::module_engine::sword_engine::SwordEngine;
 use crate::features::core::module_engine::sword_module::SwordModule;
+use crate::features::store::error::{StoreError, StoreResult};
 
 #[derive(Debug)]
 pub struct DownloadService {
@@ -19,8 +20,12 @@ impl DownloadService {
    // }
 
     /// 2. Get metadata for modules available at a specific remote source
-    pub fn get_available_modules(&self, source_name: &str) -> Vec<SwordModule> {
-        self.engine.fetch_remote_modules(source_name)
+    pub fn get_available_modules(&self, source_name: &str) -> StoreResult<Vec<SwordModule>> {
+        let modules = self.engine.fetch_remote_modules(source_name)?;
+        if modules.is_empty() {
+            return Err(StoreError::EmptySource(source_name.to_string()));
+        }
+        Ok(modules)
     }
 
     /// 3. Check progress of an active download
@@ -29,12 +34,11 @@ impl DownloadService {
     }
 
     /// 4. Execute the download
-    pub fn download(&self, source: &str, module_name: &str) -> Result<(), String> {
-        let res = self.engine.install_remote_module(source, module_name);
-        if res == 0 { 
-            Ok(()) 
-        } else { 
-            Err(format!("Download failed with SWORD error code: {}", res)) 
+    pub fn download(&self, source: &str, module_name: &str) -> StoreResult<()> {
+        if self.engine.get_modules()?.iter().any(|m| m.name == module_name) {
+            return Err(StoreError::AlreadyInstalled(module_name.to_string()));
         }
+        self.engine.install_remote_module(source, module_name)?;
+        Ok(())
     }
-}
\ No newline at end of file
+}
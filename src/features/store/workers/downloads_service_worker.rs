@@ -42,10 +42,13 @@ impl Worker for DownloadWorker {
                 let subs = self.subscribers.clone();
 
                 std::thread::spawn(move || {
-                    let modules = service.get_available_modules(&source);
+                    let result = service.get_available_modules(&source).map_err(Arc::new);
                     for sub in &subs {
                         // Added & to borrow subs
-                        sub.input(StorePageInput::UpdateList(modules.clone()));
+                        sub.input(match &result {
+                            Ok(modules) => StorePageInput::UpdateList(modules.clone()),
+                            Err(e) => StorePageInput::Failed(e.clone()),
+                        });
                     }
                 });
             }
@@ -61,14 +64,13 @@ impl Worker for DownloadWorker {
                 });
 
                 std::thread::spawn(move || {
-                    let result = service.download(&source, &name);
+                    let result = service.download(&source, &name).map_err(Arc::new);
 
                     // We borrow subs and match on a reference to result
                     for sub in &subs {
                         match &result {
-                            // Match on &result to avoid moving the error string
                             Ok(_) => sub.input(StorePageInput::UpdateProgress(1.0)),
-                            Err(e) => println!("Download failed: {:?}", e),
+                            Err(e) => sub.input(StorePageInput::Failed(e.clone())),
                         }
                     }
                 });
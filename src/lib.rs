@@ -0,0 +1,11 @@
+//! xBible's Bible engine, models and parsers. The GTK application is built
+//! on top of them behind the default `gui` feature; `xbible` uses them
+//! without it.
+
+#[cfg(feature = "gui")]
+pub mod app;
+pub mod features;
+
+mod sword_sys {
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
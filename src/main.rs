@@ -1,13 +1,18 @@
-use crate::app::AppModel;
 use relm4::RelmApp;
+use x_bible::app::AppModel;
+use x_bible::features::core::{
+    logging, module_engine::sword_engine::SwordEngine, settings::Settings,
+};
 
-mod app;
-mod features;
-
-mod sword_sys {
-    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
-}
 fn main() {
+    let data_dir = SwordEngine::get_sword_path().ok();
+    let level = data_dir
+        .as_deref()
+        .map(|dir| Settings::load(dir).log_level)
+        .unwrap_or_default();
+    let log_dir = data_dir.map(|path| path.join("logs"));
+    logging::init(log_dir.as_deref(), level.filter());
+
     let app = RelmApp::new("org.flame.xbible");
 
     // 1. Register both resource files